@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use criterion::{Criterion, criterion_group, criterion_main};
+use flux_backend::mutation::engine::{MutationEngine, RunTimings};
+use flux_backend::mutation::error::MutationError;
+use flux_backend::mutation::runner::{TestOutcome, TestRunner};
+use flux_backend::mutation::types::MutationTestConfig;
+use std::hint::black_box;
+use std::time::Duration;
+
+const FIXTURE: &str = r#"
+pub fn clamp(value: i32, low: i32, high: i32) -> i32 {
+    if value < low {
+        return low;
+    }
+    if value > high && high >= low {
+        return high;
+    }
+    value
+}
+
+pub fn score(hits: u32, misses: u32) -> f64 {
+    hits as f64 / (hits + misses) as f64 * 100.0
+}
+
+pub fn is_ready(started: bool, failed: bool) -> bool {
+    started && !failed || false
+}
+
+#[test]
+fn test_clamp() {
+    assert_eq!(clamp(5, 0, 10), 5);
+}
+"#;
+
+/// Kills every mutant instantly, so a run measures the engine rather than cargo.
+struct StubRunner;
+
+#[async_trait]
+impl TestRunner for StubRunner {
+    async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+        Ok(())
+    }
+
+    async fn run_tests_for_mutation(&self, _mutated_code: &str) -> TestOutcome {
+        TestOutcome::Killed { killing_tests: vec!["test_clamp".to_string()] }
+    }
+}
+
+fn engine() -> MutationEngine {
+    let config = MutationTestConfig {
+        parallel_jobs: Some(1),
+        ..Default::default()
+    };
+    MutationEngine::new(config).with_test_runner(StubRunner)
+}
+
+fn bench_engine(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let engine = engine();
+
+    c.bench_function("discovery", |b| {
+        b.iter(|| runtime.block_on(engine.dry_run(black_box(FIXTURE))).unwrap())
+    });
+
+    let mut totals = RunTimings::default();
+    let mut runs = 0u32;
+    c.bench_function("full_run", |b| {
+        b.iter(|| {
+            let report = runtime.block_on(engine.run_mutation_testing(black_box(FIXTURE))).unwrap();
+            let timings = engine.last_timings();
+            totals.discovery += timings.discovery;
+            totals.orchestration += timings.orchestration;
+            totals.test_execution += timings.test_execution;
+            runs += 1;
+            report
+        })
+    });
+
+    let mean = |total: Duration| total / runs.max(1);
+    println!(
+        "mean per run over {} runs: discovery {:?}, orchestration {:?}, test execution {:?}",
+        runs,
+        mean(totals.discovery),
+        mean(totals.orchestration),
+        mean(totals.test_execution)
+    );
+}
+
+criterion_group!(benches, bench_engine);
+criterion_main!(benches);
@@ -1,4 +1,5 @@
 // Example: Conditional logic for mutation testing
+#[allow(clippy::needless_bool)]
 pub fn is_even(n: i32) -> bool {
     if n % 2 == 0 { true } else { false }
 }
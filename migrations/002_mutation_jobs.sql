@@ -0,0 +1,21 @@
+-- Track API runs so stuck jobs can be told apart from slow ones
+DO $$ BEGIN
+    CREATE TYPE mutation_job_status AS ENUM ('running', 'completed', 'failed');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS mutation_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    mutation_test_id UUID NOT NULL REFERENCES mutation_tests(id) ON DELETE CASCADE,
+    status mutation_job_status NOT NULL DEFAULT 'running',
+    total_mutations INTEGER,
+    completed_mutations INTEGER NOT NULL DEFAULT 0,
+    error_message TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_mutation_jobs_test_id ON mutation_jobs(mutation_test_id);
+CREATE INDEX IF NOT EXISTS idx_mutation_jobs_status_heartbeat ON mutation_jobs(status, heartbeat_at);
@@ -0,0 +1,11 @@
+-- Record how a reviewer has triaged each result, mostly survivors
+DO $$ BEGIN
+    CREATE TYPE triage_status AS ENUM ('untriaged', 'accepted', 'needs_test', 'false_positive');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+ALTER TABLE mutation_results
+    ADD COLUMN IF NOT EXISTS triage_status triage_status NOT NULL DEFAULT 'untriaged';
+
+CREATE INDEX IF NOT EXISTS idx_mutation_results_triage_status ON mutation_results(mutation_test_id, triage_status);
@@ -0,0 +1,3 @@
+-- Keep the aggregate report of each completed run alongside its result rows
+ALTER TABLE mutation_tests
+    ADD COLUMN IF NOT EXISTS report_json JSONB;
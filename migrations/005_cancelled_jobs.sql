@@ -0,0 +1,2 @@
+-- Jobs stopped through the cancel endpoint
+ALTER TYPE mutation_job_status ADD VALUE IF NOT EXISTS 'cancelled';
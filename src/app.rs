@@ -1,9 +1,32 @@
 use crate::config::AppConfig;
 use crate::database::DatabasePool;
+use crate::models::RunEvent;
+use dashmap::DashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
 #[derive(Clone)]
 #[allow(dead_code)]
 pub struct AppState {
     pub db: DatabasePool,
     pub config: AppConfig,
+    /// Runs started through the API, by test id.
+    pub running: Arc<DashMap<Uuid, RunHandle>>,
+}
+
+/// Controls for an in-flight run: `cancel` stops it and `events` carries its
+/// progress to any `/events` subscribers.
+#[derive(Clone)]
+pub struct RunHandle {
+    pub cancel: CancellationToken,
+    pub events: broadcast::Sender<RunEvent>,
+}
+
+impl Default for RunHandle {
+    fn default() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self { cancel: CancellationToken::new(), events }
+    }
 }
@@ -4,6 +4,8 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde_json::json;
+
+use crate::mutation::error::MutationError;
 use thiserror::Error;
 use tracing::error;
 
@@ -34,15 +36,25 @@ pub enum AppError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Unauthorized")]
     Unauthorized,
 
     #[error("Forbidden")]
     Forbidden,
+
+    #[error("Mutation error: {0}")]
+    Mutation(#[from] MutationError),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let mutation_message = match &self {
+            AppError::Mutation(err) => err.to_string(),
+            _ => String::new(),
+        };
         let (status, error_message) = match &self {
             AppError::Database(err) => {
                 error!(error = %err, kind = "database", "Database error");
@@ -67,8 +79,22 @@ impl IntoResponse for AppError {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
             }
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.as_str()),
             AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             AppError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden"),
+            AppError::Mutation(
+                MutationError::Parse(_)
+                | MutationError::InvalidLine(_)
+                | MutationError::PositionNotFound { .. }
+                | MutationError::InvalidMutation { .. }
+                | MutationError::UnsupportedType(_)
+                | MutationError::NotApplied { .. }
+                | MutationError::NoTests,
+            ) => (StatusCode::UNPROCESSABLE_ENTITY, mutation_message.as_str()),
+            AppError::Mutation(err) => {
+                error!(error = %err, kind = "mutation", "Mutation error");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
         };
 
         let body = Json(json!({
@@ -1,17 +1,28 @@
 use axum::{
     extract::{Path, Query, State},
-    response::Json,
+    response::{
+        Json,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
-use serde::Deserialize;
+use dashmap::mapref::entry::Entry;
+use futures::{Stream, stream};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{info, instrument, warn};
 use uuid::Uuid;
 
 use crate::{
-    app::AppState,
+    app::{AppState, RunHandle},
     error::{AppError, AppResult},
-    models::{CreateMutationTestRequest, MutationTest, MutationTestWithResults},
-    services::mutation_service,
+    models::{
+        CreateMutationTestRequest, ListCursor, MutationResult, MutationScorePoint, MutationTest,
+        MutationTestOverview, MutationTestPage, MutationTestWithJob, MutationTestWithResults, RunEvent, TriageStatus, UpdateTriageRequest,
+    },
+    mutation::types::MutationReport,
+    services::{job_service, mutation_service},
 };
 
 #[derive(Debug, Deserialize)]
@@ -20,6 +31,29 @@ pub struct ListMutationsQuery {
     pub limit: Option<u32>,
     pub status: Option<String>,
     pub language: Option<String>,
+    /// `next_cursor` from the previous page; takes precedence over `page`.
+    /// An empty value asks for the first page in cursor form.
+    pub after: Option<String>,
+}
+
+/// The list endpoint's body: a bare array for `page` requests, as it has
+/// always been, and a [`MutationTestPage`] once `after` is given.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum MutationTestList {
+    Offset(Vec<MutationTest>),
+    Cursor(MutationTestPage),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResultsQuery {
+    pub triage_status: Option<TriageStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrendQuery {
+    pub name: String,
+    pub limit: Option<u32>,
 }
 
 #[instrument(skip(state))]
@@ -39,12 +73,31 @@ pub async fn create_mutation(
 pub async fn list_mutations(
     State(state): State<Arc<AppState>>,
     Query(params): Query<ListMutationsQuery>,
-) -> AppResult<Json<Vec<MutationTest>>> {
+) -> AppResult<Json<MutationTestList>> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
 
-    if limit > 100 {
-        return Err(AppError::Validation("Limit cannot exceed 100".to_string()));
+    if let Some(after) = &params.after {
+        let cursor = match after.as_str() {
+            "" => None,
+            after => Some(
+                ListCursor::decode(after)
+                    .ok_or_else(|| AppError::Validation(format!("Invalid cursor: {}", after)))?,
+            ),
+        };
+
+        info!("Listing mutation tests after cursor '{}', limit: {}", after, limit);
+
+        let page = mutation_service::list_mutation_tests_after(
+            &state.db,
+            cursor,
+            limit,
+            params.status,
+            params.language,
+        )
+        .await?;
+
+        return Ok(Json(MutationTestList::Cursor(page)));
     }
 
     info!(
@@ -61,20 +114,43 @@ pub async fn list_mutations(
     )
     .await?;
 
-    Ok(Json(mutation_tests))
+    Ok(Json(MutationTestList::Offset(mutation_tests)))
+}
+
+#[instrument(skip(state))]
+pub async fn get_mutation_trend(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TrendQuery>,
+) -> AppResult<Json<Vec<MutationScorePoint>>> {
+    let limit = params.limit.unwrap_or(10);
+
+    if limit == 0 || limit > 100 {
+        return Err(AppError::Validation(
+            "Limit must be between 1 and 100".to_string(),
+        ));
+    }
+
+    info!("Getting mutation score trend for '{}' over {} runs", params.name, limit);
+
+    let trend = mutation_service::get_mutation_score_trend(&state.db, &params.name, limit).await?;
+
+    Ok(Json(trend))
 }
 
 #[instrument(skip(state))]
 pub async fn get_mutation(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> AppResult<Json<MutationTest>> {
+) -> AppResult<Json<MutationTestWithJob>> {
     info!("Getting mutation test: {}", id);
 
     let mutation_test = mutation_service::get_mutation_test(&state.db, id).await?;
 
     match mutation_test {
-        Some(test) => Ok(Json(test)),
+        Some(test) => {
+            let job = job_service::get_latest_job(&state.db, id).await?;
+            Ok(Json(MutationTestWithJob { test, job }))
+        }
         None => {
             warn!("Mutation test not found: {}", id);
             Err(AppError::NotFound(format!(
@@ -85,15 +161,33 @@ pub async fn get_mutation(
     }
 }
 
+#[instrument(skip(state))]
+pub async fn get_mutation_report(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<MutationReport>> {
+    info!("Getting stored mutation report: {}", id);
+
+    match mutation_service::get_stored_report(&state.db, id).await? {
+        Some(report) => Ok(Json(report)),
+        None => Err(AppError::NotFound(format!(
+            "No completed report for mutation test {}",
+            id
+        ))),
+    }
+}
+
 #[instrument(skip(state))]
 pub async fn get_mutation_results(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    Query(params): Query<ResultsQuery>,
 ) -> AppResult<Json<MutationTestWithResults>> {
     info!("Getting mutation test results: {}", id);
 
     let mutation_test_with_results =
-        mutation_service::get_mutation_test_with_results(&state.db, id).await?;
+        mutation_service::get_mutation_test_with_results(&state.db, id, params.triage_status)
+            .await?;
 
     match mutation_test_with_results {
         Some(results) => Ok(Json(results)),
@@ -107,6 +201,49 @@ pub async fn get_mutation_results(
     }
 }
 
+#[instrument(skip(state))]
+pub async fn get_mutation_summary(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<MutationTestOverview>> {
+    info!("Getting mutation test summary: {}", id);
+
+    match mutation_service::get_mutation_summary(&state.db, id).await? {
+        Some(overview) => Ok(Json(overview)),
+        None => Err(AppError::NotFound(format!(
+            "Mutation test with ID {} not found",
+            id
+        ))),
+    }
+}
+
+#[instrument(skip(state))]
+pub async fn update_result_triage(
+    State(state): State<Arc<AppState>>,
+    Path((id, result_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateTriageRequest>,
+) -> AppResult<Json<MutationResult>> {
+    info!(
+        "Setting triage status of result {} in mutation test {} to {:?}",
+        result_id, id, request.triage_status
+    );
+
+    let result =
+        mutation_service::update_triage_status(&state.db, id, result_id, request.triage_status)
+            .await?;
+
+    match result {
+        Some(result) => Ok(Json(result)),
+        None => {
+            warn!("Mutation result {} not found in test {}", result_id, id);
+            Err(AppError::NotFound(format!(
+                "Mutation result with ID {} not found in mutation test {}",
+                result_id, id
+            )))
+        }
+    }
+}
+
 #[instrument(skip(state))]
 pub async fn start_mutation_testing(
     State(state): State<Arc<AppState>>,
@@ -114,31 +251,100 @@ pub async fn start_mutation_testing(
 ) -> AppResult<Json<MutationTest>> {
     info!("Starting mutation testing: {}", id);
 
+    let mutation_test = match mutation_service::get_mutation_test(&state.db, id).await? {
+        Some(test) => test,
+        None => {
+            warn!("Mutation test not found: {}", id);
+            return Err(AppError::NotFound(format!(
+                "Mutation test with ID {} not found",
+                id
+            )));
+        }
+    };
+
+    // A second start would replace the first run's handle, and the first
+    // run's cleanup would then unregister the second.
+    let handle = match state.running.entry(id) {
+        Entry::Occupied(_) => {
+            return Err(AppError::Conflict(format!(
+                "Mutation test {} is already running",
+                id
+            )));
+        }
+        Entry::Vacant(slot) => slot.insert(RunHandle::default()).clone(),
+    };
+
     let state_clone = state.clone();
     let mutation_test_id = id;
-
     tokio::spawn(async move {
-        if let Err(e) =
-            mutation_service::run_mutation_testing(&state_clone.db, mutation_test_id).await
-        {
+        if let Err(e) = job_service::run_mutation_job(&state_clone.db, mutation_test_id, &handle).await {
             tracing::error!("Mutation testing failed for {}: {}", mutation_test_id, e);
         }
+        state_clone.running.remove(&mutation_test_id);
     });
 
-    let mutation_test = mutation_service::get_mutation_test(&state.db, id).await?;
+    Ok(Json(mutation_test))
+}
 
-    match mutation_test {
-        Some(test) => Ok(Json(test)),
-        None => {
-            warn!("Mutation test not found: {}", id);
-            Err(AppError::NotFound(format!(
-                "Mutation test with ID {} not found",
-                id
-            )))
+/// Stops a run started through the API. The run winds down in the
+/// background and ends with the test marked `cancelled`.
+#[instrument(skip(state))]
+pub async fn cancel_mutation_testing(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<MutationTest>> {
+    info!("Cancelling mutation testing: {}", id);
+
+    let test = mutation_service::get_mutation_test(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Mutation test with ID {} not found", id)))?;
+
+    match state.running.get(&id) {
+        Some(run) => {
+            run.cancel.cancel();
+            Ok(Json(test))
         }
+        None => Err(AppError::BadRequest(format!(
+            "Mutation test {} is not running",
+            id
+        ))),
     }
 }
 
+/// Live progress of a run started through the API: a `mutation` event per
+/// finished mutant, then a `done` event, after which the stream ends.
+#[instrument(skip(state))]
+pub async fn stream_mutation_events(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+    let receiver = state
+        .running
+        .get(&id)
+        .map(|run| run.events.subscribe())
+        .ok_or_else(|| AppError::BadRequest(format!("Mutation test {} is not running", id)))?;
+
+    let events = stream::unfold(Some(receiver), move |receiver| async move {
+        let mut receiver = receiver?;
+        loop {
+            match receiver.recv().await {
+                Ok(RunEvent::Mutation(progress)) => {
+                    return Some((Event::default().event("mutation").json_data(progress), Some(receiver)));
+                }
+                Ok(RunEvent::Done(finished)) => {
+                    return Some((Event::default().event("done").json_data(finished), None));
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("Event stream for {} skipped {} update(s)", id, missed);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(15))))
+}
+
 #[instrument(skip(state))]
 pub async fn dry_run_mutation_testing(
     State(state): State<Arc<AppState>>,
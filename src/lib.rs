@@ -6,3 +6,4 @@ pub mod handlers;
 pub mod models;
 pub mod mutation;
 pub mod services;
+pub mod store;
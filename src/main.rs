@@ -1,22 +1,35 @@
 use crate::mutation::engine::MutationEngine;
-use crate::mutation::logger::MutationLogger;
-use crate::mutation::types::MutationTestConfig;
+use crate::mutation::config_loader::ConfigLoader;
+use crate::mutation::coverage::CoverageMap;
+use crate::mutation::explain::explain_survivor;
+use crate::mutation::files::resolve_files;
+use crate::mutation::gates::{ERROR_RATE_EXIT_CODE, FAIL_UNDER_EXIT_CODE, check_error_rate, check_fail_under};
+use crate::mutation::hooks::SurvivorHook;
+use crate::mutation::history::{DEFAULT_HISTORY_PATH, append_history, format_trend, read_history};
+use crate::mutation::logger::{MutationLogger, Verbosity};
+use crate::mutation::queue::{DEFAULT_MAX_RETRIES, MAX_PRIORITY, dead_letter_queue, declare_job_queue, retry_properties, write_job_report};
+use crate::mutation::sinks::{ReportSink, WEBHOOK_SECRET_ENV, emit_reports};
+use crate::mutation::reports::{FileCandidates, ReportGenerator};
+use crate::mutation::runner::MutationRunner;
+use crate::mutation::types::{CandidateFormat, MutationReport, MutationTestConfig, SampleStrategy, WebhookFormat};
 use crate::mutation::types::{MutationJob, MutationType};
+use crate::mutation::watch::{MutantKey, diff_runs, survivor_keys};
 use anyhow::Result;
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{get, patch, post},
 };
 use clap::{Parser, Subcommand};
+use futures::stream::FuturesOrdered;
 use futures_lite::stream::StreamExt;
 use lapin::{BasicProperties, Connection, ConnectionProperties, options::*, types::FieldTable};
-use reqwest;
-use reqwest::Client;
-use serde_json;
+use notify::Watcher;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
-use toml;
+use tokio::sync::Semaphore;
 use tower_http::{cors::CorsLayer, timeout::TimeoutLayer};
 use tracing::info;
 
@@ -28,6 +41,7 @@ mod handlers;
 mod models;
 mod mutation;
 mod services;
+mod store;
 
 use crate::app::AppState;
 use crate::config::AppConfig;
@@ -39,11 +53,18 @@ use std::env;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// Print only warnings, errors and each file's final summary
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Show per-mutation progress; repeat (-vv) for debug output
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Subcommand, Debug)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     TestFiles {
         #[arg(required = false)]
@@ -56,12 +77,88 @@ enum Commands {
         json: Option<String>,
         #[arg(long)]
         html: Option<String>,
+        /// Write every result to PATH as CSV, one row per mutant.
+        #[arg(long, value_name = "PATH")]
+        csv: Option<String>,
         #[arg(long)]
         filter_types: Option<Vec<MutationType>>,
         #[arg(long)]
         webhook: Option<String>,
+        /// Sign webhook bodies with HMAC-SHA256 in an `X-Signature-256` header.
+        /// Falls back to `MUTATION_WEBHOOK_SECRET`.
+        #[arg(long, value_name = "SECRET")]
+        webhook_secret: Option<String>,
+        /// `slack` posts a Block Kit message instead of the raw report JSON.
+        #[arg(long, value_enum, default_value_t = WebhookFormat::Raw)]
+        webhook_format: WebhookFormat,
         #[arg(long)]
         databaseless: bool,
+        /// Seed for the candidate order, and for choosing which candidates
+        /// to keep on lines over `max_mutations_per_line` or when sampling.
+        #[arg(long)]
+        shuffle_seed: Option<u64>,
+        /// Most mutants tested per file; larger files are sampled.
+        #[arg(long, value_name = "N")]
+        max_total_mutations: Option<usize>,
+        /// How to pick candidates when `max_total_mutations` is exceeded.
+        #[arg(long, value_enum)]
+        sample: Option<SampleStrategy>,
+        /// llvm-cov or tarpaulin JSON report; lines it marks as never
+        /// executed are not mutated.
+        #[arg(long, value_name = "FILE")]
+        coverage: Option<String>,
+        #[arg(long)]
+        list_files: bool,
+        #[arg(long, visible_alias = "sqlite-out", value_name = "PATH")]
+        sqlite_db: Option<String>,
+        #[arg(long)]
+        by_module: bool,
+        #[arg(long)]
+        allow_non_test_command: bool,
+        #[arg(long)]
+        threads: Option<usize>,
+        #[arg(long)]
+        debug_skips: bool,
+        #[arg(long, value_name = "PERCENT")]
+        max_error_rate: Option<f64>,
+        /// Exit non-zero when the overall mutation score is below this;
+        /// defaults to `min_coverage_percent` from the config.
+        #[arg(long, value_name = "PERCENT")]
+        fail_under: Option<f64>,
+        #[arg(long)]
+        cap_lints: bool,
+        /// Experimental: rebuild body-only mutants incrementally in one persistent project.
+        #[arg(long)]
+        incremental: bool,
+        /// Reuse the baseline's dependency build for every mutant.
+        #[arg(long)]
+        shared_target_dir: bool,
+        /// Shell command run once per survivor; `{file}`, `{line}`, `{type}` and
+        /// `{original}` are substituted.
+        #[arg(long, value_name = "COMMAND")]
+        on_survivor_command: Option<String>,
+        /// Run the on-survivor command as survivors are found, not after the run.
+        #[arg(long)]
+        on_survivor_inline: bool,
+        /// Write the merged config (file plus flags) to PATH as TOML or YAML.
+        #[arg(long, value_name = "PATH")]
+        save_config: Option<String>,
+        /// Overlay the named `[profiles.NAME]` section of the config file.
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+    },
+    /// Re-run mutation testing on the given files whenever they are saved,
+    /// printing only mutants that newly survived or were newly killed.
+    Watch {
+        #[arg(required = true)]
+        files: Vec<String>,
+        #[arg(long)]
+        config: Option<String>,
+        #[arg(long)]
+        filter_types: Option<Vec<MutationType>>,
+        /// Wait this long after a change for further saves before re-running.
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        debounce_ms: u64,
     },
     EnqueueJobs {
         #[arg(required = true)]
@@ -74,6 +171,9 @@ enum Commands {
         queue_name: String,
         #[arg(long)]
         filter_types: Option<Vec<MutationType>>,
+        /// Message priority for these jobs; higher runs first.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(0..=MAX_PRIORITY as i64))]
+        priority: Option<u8>,
     },
     QueueRunner {
         #[arg(long)]
@@ -82,6 +182,51 @@ enum Commands {
         queue_name: String,
         #[arg(long)]
         output_dir: Option<String>,
+        /// Times a failing job is retried before it goes to `<queue>_dead`.
+        #[arg(long, default_value_t = DEFAULT_MAX_RETRIES)]
+        max_retries: u32,
+    },
+    DryRun {
+        #[arg(required = true)]
+        files: Vec<String>,
+        #[arg(long)]
+        config: Option<String>,
+        #[arg(long, value_enum, default_value_t = CandidateFormat::Text)]
+        format: CandidateFormat,
+        #[arg(long)]
+        debug_skips: bool,
+    },
+    /// Find mutation candidates without testing them and count them per
+    /// file and mutation type.
+    #[command(alias = "analyze")]
+    ListCandidates {
+        #[arg(required = true)]
+        files: Vec<String>,
+        #[arg(long)]
+        config: Option<String>,
+        /// Write the candidates, grouped by file, to PATH as JSON.
+        #[arg(long, value_name = "PATH")]
+        json: Option<String>,
+    },
+    Lsp {
+        #[arg(long)]
+        config: Option<String>,
+    },
+    History {
+        #[arg(long, default_value = DEFAULT_HISTORY_PATH)]
+        path: String,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Explain one survived mutant, identified by the fingerprint shown in reports.
+    ExplainSurvivor {
+        fingerprint: String,
+        file: String,
+        #[arg(long)]
+        config: Option<String>,
+        /// JSON report of the run that found the survivor, for covering tests.
+        #[arg(long, value_name = "PATH")]
+        report: Option<String>,
     },
     Wizard,
 }
@@ -95,6 +240,7 @@ async fn main() -> Result<()> {
         println!("Running in {env} mode");
     }
     let cli = Cli::parse();
+    MutationLogger::set_verbosity(Verbosity::from_flags(cli.quiet, cli.verbose));
 
     match &cli.command {
         Some(Commands::TestFiles {
@@ -102,167 +248,208 @@ async fn main() -> Result<()> {
             config,
             file_list,
             json,
-            html: _,
-            filter_types: _,
+            html,
+            csv,
+            filter_types,
             webhook,
+            webhook_secret,
+            webhook_format,
             databaseless,
+            shuffle_seed,
+            max_total_mutations,
+            sample,
+            coverage,
+            list_files,
+            sqlite_db,
+            by_module,
+            allow_non_test_command,
+            threads,
+            debug_skips,
+            max_error_rate,
+            fail_under,
+            cap_lints,
+            incremental,
+            shared_target_dir,
+            on_survivor_command,
+            on_survivor_inline,
+            save_config,
+            profile,
         }) => {
-            let test_config = if let Some(cfg_path) = config {
-                let cfg_str = fs::read_to_string(cfg_path)?;
-                toml::from_str::<MutationTestConfig>(&cfg_str)?
-            } else {
-                MutationTestConfig::default()
-            };
+            // Checked before any mutant runs rather than once they're done.
+            if sqlite_db.is_some() && !cfg!(feature = "sqlite") {
+                anyhow::bail!(SQLITE_FEATURE_REQUIRED);
+            }
+            // An explicitly named config has to load; only auto-discovered
+            // ones fall back to the defaults with a warning.
+            let mut test_config = ConfigLoader::new()
+                .with_profile(profile.clone())
+                .load_cli_config(Path::new("."), config.as_deref())
+                .map_err(anyhow::Error::msg)?;
+            if shuffle_seed.is_some() {
+                test_config.shuffle_seed = *shuffle_seed;
+            }
+            if max_total_mutations.is_some() {
+                test_config.max_total_mutations = *max_total_mutations;
+            }
+            if let Some(strategy) = sample {
+                test_config.sample_strategy = *strategy;
+            }
+            if coverage.is_some() {
+                test_config.coverage_file = coverage.clone();
+            }
+            if *by_module {
+                test_config.group_by_module = true;
+            }
+            if *allow_non_test_command {
+                test_config.allow_non_test_command = true;
+            }
+            if threads.is_some() {
+                test_config.parallel_jobs = *threads;
+            }
+            if *debug_skips {
+                test_config.debug_skips = true;
+                MutationLogger::set_verbosity(MutationLogger::verbosity().max(Verbosity::VeryVerbose));
+            }
+            if max_error_rate.is_some() {
+                test_config.max_error_rate = *max_error_rate;
+            }
+            if fail_under.is_some() {
+                test_config.min_coverage_percent = *fail_under;
+            }
+            if *cap_lints {
+                test_config.cap_lints = true;
+            }
+            if *incremental {
+                test_config.incremental_workspace = true;
+            }
+            if *shared_target_dir {
+                test_config.shared_target_dir = true;
+            }
+            if on_survivor_command.is_some() {
+                test_config.on_survivor_command = on_survivor_command.clone();
+            }
+            if *on_survivor_inline {
+                test_config.on_survivor_inline = true;
+            }
+            test_config.apply_filter_types(filter_types.as_deref());
+            if let Some(path) = save_config {
+                match ConfigLoader::new().save_config(&test_config, path) {
+                    Ok(()) => MutationLogger::info_file(path, "Saved effective config to"),
+                    Err(e) => MutationLogger::error_file(path, &e),
+                }
+            }
+            MutationLogger::info(&format!("Using {} thread(s) for mutation testing", test_config.effective_threads()));
+            if let Some(warning) = MutationRunner::test_command_warning(
+                &test_config.test_command,
+                test_config.allow_non_test_command,
+            ) {
+                MutationLogger::warn(&warning);
+            }
 
-            let mut all_files = files.clone();
-            if let Some(list_path) = file_list {
-                let list_content = fs::read_to_string(list_path)?;
-                for line in list_content.lines() {
-                    let trimmed = line.trim();
-                    if !trimmed.is_empty() {
-                        all_files.push(trimmed.to_string());
-                    }
+            let selection = resolve_files(files, file_list.as_deref(), &test_config)
+                .map_err(anyhow::Error::msg)?;
+            for skipped in &selection.skipped {
+                MutationLogger::warn_file(&skipped.path, &format!("skipped: {}", skipped.reason));
+            }
+            if *list_files {
+                for file in &selection.files {
+                    println!("{}", file);
                 }
+                return Ok(());
             }
+
+            let all_files = selection.files;
             if all_files.is_empty() {
                 MutationLogger::error("No files provided for mutation testing.");
                 return Ok(());
             }
 
-            let engine = MutationEngine::new(test_config.clone());
+            // Up to `parallel_jobs` files are tested at once; `FuturesOrdered`
+            // keeps the reports in the order the files were given. The files'
+            // engines share `slots`, so no more than `parallel_jobs` mutants
+            // run at once across all of them.
+            let slots = Arc::new(Semaphore::new(test_config.effective_threads()));
+            let coverage = load_coverage(&test_config)?;
+            let mut pending = all_files.into_iter();
+            let mut in_flight = FuturesOrdered::new();
+            for file in pending.by_ref().take(test_config.effective_threads()) {
+                in_flight.push_back(test_file(&test_config, coverage.as_ref(), &slots, file));
+            }
             let mut all_reports = Vec::new();
-            for file in all_files {
-                MutationLogger::info_file(&file, &format!("=== Mutation Testing ==="));
-                let code = fs::read_to_string(&file)?;
-                MutationLogger::step("Analyzing source code for mutation candidates...");
-                match engine.run_mutation_testing(&code).await {
-                    Ok(report) => {
-                        all_reports.push((file.clone(), report.clone()));
-                        MutationLogger::info_file(
-                            &file,
-                            &format!("Total mutations: {}", report.total_mutations),
-                        );
-                        MutationLogger::info_file(
-                            &file,
-                            &format!(
-                                "Killed: {} | Survived: {} | Timeouts: {} | Errors: {} | Skipped: {}",
-                                report.killed_mutations,
-                                report.survived_mutations,
-                                report.timeout_mutations,
-                                report.error_mutations,
-                                report.skipped_mutations
-                            ),
-                        );
-                        MutationLogger::info_file(
-                            &file,
-                            &format!("Mutation Score: {:.1}%", report.mutation_score),
-                        );
-                        MutationLogger::info_file(
-                            &file,
-                            &format!("Execution Time: {:.2}s", report.execution_time_seconds),
-                        );
-                        if report.survived_mutations > 0 {
-                            MutationLogger::warn(
-                                "Some mutations survived. Consider improving your tests to catch these cases.",
-                            );
-                            MutationLogger::fix(
-                                "Review survived mutations and add assertions or edge case tests.",
-                            );
-                        }
-                        if report.error_mutations > 0 {
-                            MutationLogger::error(
-                                "Some mutations caused errors. Check for panics or unhandled cases in your code.",
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        MutationLogger::error_file(
-                            &file,
-                            &format!("Error running mutation testing for {}: {}", file, e),
-                        );
-                        MutationLogger::fix(
-                            "Ensure the file compiles and contains valid Rust code with tests.",
-                        );
-                    }
+            while let Some(tested) = in_flight.next().await {
+                if let Some(file_report) = tested? {
+                    all_reports.push(file_report);
+                }
+                if let Some(file) = pending.next() {
+                    in_flight.push_back(test_file(&test_config, coverage.as_ref(), &slots, file));
                 }
             }
 
-            if let Some(json_path) = json {
-                if all_reports.len() == 1 {
-                    MutationLogger::info_file(&json_path, "Exported JSON report to");
-                } else {
-                    use std::collections::BTreeMap;
-                    let mut map = BTreeMap::new();
-                    for (file, report) in &all_reports {
-                        map.insert(file, report);
-                    }
-                    let json = serde_json::to_string_pretty(&map)
-                        .expect("Failed to serialize multi-file report");
-                    std::fs::write(json_path, json)?;
-                    MutationLogger::info_file(&json_path, "Exported JSON report to");
+            if let Some(command) = &test_config.on_survivor_command
+                && !test_config.on_survivor_inline
+            {
+                for (file, report) in &all_reports {
+                    SurvivorHook::new(command, file).notify_all(&report.results);
                 }
             }
 
-            if let Some(webhook_url) = webhook {
-                if all_reports.len() == 1 {
-                    let json = serde_json::to_string_pretty(&all_reports[0].1)?;
-                    let client = Client::new();
-                    match client
-                        .post(webhook_url)
-                        .header("Content-Type", "application/json")
-                        .body(json)
-                        .send()
-                        .await
-                    {
-                        Ok(r) if r.status().is_success() => MutationLogger::info_file(
-                            &webhook_url,
-                            &format!("Posted results to webhook: {}", webhook_url),
-                        ),
-                        Ok(r) => MutationLogger::error_file(
-                            &webhook_url,
-                            &format!("Webhook POST failed: {}", r.status()),
-                        ),
-                        Err(e) => MutationLogger::error_file(
-                            &webhook_url,
-                            &format!("Webhook POST error: {}", e),
-                        ),
-                    }
-                } else {
-                    let json = serde_json::to_string_pretty(&all_reports)?;
-                    let client = Client::new();
-                    match client
-                        .post(webhook_url)
-                        .header("Content-Type", "application/json")
-                        .body(json)
-                        .send()
-                        .await
-                    {
-                        Ok(r) if r.status().is_success() => MutationLogger::info_file(
-                            &webhook_url,
-                            &format!("Posted results to webhook: {}", webhook_url),
-                        ),
-                        Ok(r) => MutationLogger::error_file(
-                            &webhook_url,
-                            &format!("Webhook POST failed: {}", r.status()),
-                        ),
-                        Err(e) => MutationLogger::error_file(
-                            &webhook_url,
-                            &format!("Webhook POST error: {}", e),
-                        ),
-                    }
+            let webhook_secret = webhook_secret.clone().or_else(|| env::var(WEBHOOK_SECRET_ENV).ok());
+            let sinks = ReportSink::from_options(
+                &test_config,
+                json.as_deref(),
+                html.as_deref(),
+                csv.as_deref(),
+                webhook.as_deref(),
+                webhook_secret.as_deref(),
+                *webhook_format,
+            );
+            emit_reports(&sinks, &all_reports).await;
+            if *databaseless {
+                let path = Path::new(DEFAULT_HISTORY_PATH);
+                match append_history(path, &all_reports) {
+                    Ok(count) => MutationLogger::info_file(
+                        DEFAULT_HISTORY_PATH,
+                        &format!("Databaseless mode: appended {} run(s) to", count),
+                    ),
+                    Err(e) => MutationLogger::error_file(DEFAULT_HISTORY_PATH, &e),
                 }
             }
-            if *databaseless {
-                MutationLogger::info("Databaseless mode: skipping DB writes.");
+            if let Some(path) = sqlite_db {
+                save_to_sqlite(path, &all_reports).await?;
+            }
+            if let Some(max_error_rate) = test_config.max_error_rate
+                && let Err(message) = check_error_rate(&all_reports, max_error_rate)
+            {
+                MutationLogger::error(&message);
+                std::process::exit(ERROR_RATE_EXIT_CODE);
+            }
+            if let Some(fail_under) = test_config.min_coverage_percent
+                && let Err(messages) = check_fail_under(&all_reports, fail_under)
+            {
+                for message in &messages {
+                    MutationLogger::error(message);
+                }
+                std::process::exit(FAIL_UNDER_EXIT_CODE);
             }
             Ok(())
         }
+        Some(Commands::Watch {
+            files,
+            config,
+            filter_types,
+            debounce_ms,
+        }) => {
+            let mut test_config = load_cli_config(config.as_deref())?;
+            test_config.apply_filter_types(filter_types.as_deref());
+            run_watch(files, test_config, Duration::from_millis(*debounce_ms)).await
+        }
         Some(Commands::EnqueueJobs {
             files,
             config,
             queue_url,
             queue_name,
             filter_types,
+            priority,
         }) => {
             enqueue_jobs(
                 files.clone(),
@@ -270,6 +457,7 @@ async fn main() -> Result<()> {
                 queue_url,
                 queue_name,
                 filter_types.clone(),
+                *priority,
             )
             .await?;
             Ok(())
@@ -278,8 +466,67 @@ async fn main() -> Result<()> {
             queue_url,
             queue_name,
             output_dir,
+            max_retries,
         }) => {
-            run_queue_runner(queue_url, queue_name, output_dir.clone()).await?;
+            run_queue_runner(queue_url, queue_name, output_dir.clone(), *max_retries).await?;
+            Ok(())
+        }
+        Some(Commands::DryRun {
+            files,
+            config,
+            format,
+            debug_skips,
+        }) => {
+            let mut test_config = load_cli_config(config.as_deref())?;
+            if *debug_skips {
+                test_config.debug_skips = true;
+                MutationLogger::set_verbosity(MutationLogger::verbosity().max(Verbosity::VeryVerbose));
+            }
+            let generator = ReportGenerator::new();
+            for listing in find_candidates(files, test_config, *format == CandidateFormat::Text).await? {
+                print!("{}", generator.generate_candidate_listing(&listing.file, &listing.candidates, *format));
+            }
+            Ok(())
+        }
+        Some(Commands::ListCandidates { files, config, json }) => {
+            let test_config = load_cli_config(config.as_deref())?;
+            let listings = find_candidates(files, test_config, true).await?;
+            print!("{}", ReportGenerator::new().generate_candidate_summary(&listings));
+            if let Some(path) = json {
+                fs::write(path, serde_json::to_string_pretty(&listings)?)?;
+                MutationLogger::info_file(path, "Wrote candidates to");
+            }
+            Ok(())
+        }
+        Some(Commands::Lsp { config }) => {
+            let test_config = load_cli_config(config.as_deref())?;
+            crate::mutation::lsp::run_stdio(test_config).await;
+            Ok(())
+        }
+        Some(Commands::History { path, file }) => {
+            let entries = read_history(Path::new(path)).map_err(anyhow::Error::msg)?;
+            if entries.is_empty() {
+                MutationLogger::info_file(path, "No mutation history recorded in");
+            } else {
+                print!("{}", format_trend(&entries, file.as_deref()));
+            }
+            Ok(())
+        }
+        Some(Commands::ExplainSurvivor {
+            fingerprint,
+            file,
+            config,
+            report,
+        }) => {
+            let test_config = load_cli_config(config.as_deref())?;
+            let prior = match report {
+                Some(path) => Some(read_prior_report(path, file)?),
+                None => None,
+            };
+            let code = fs::read_to_string(file)?;
+            let explanation =
+                explain_survivor(&code, fingerprint, &test_config, prior.as_ref()).map_err(anyhow::Error::msg)?;
+            print!("{}", explanation);
             Ok(())
         }
         Some(Commands::Wizard) => {
@@ -330,9 +577,13 @@ async fn main() -> Result<()> {
 
             database::run_migrations(&db).await?;
 
+            let stale_after = services::job_service::HEARTBEAT_INTERVAL * 6;
+            tokio::spawn(services::job_service::reconcile_stale_jobs_every(db.clone(), stale_after, stale_after));
+
             let state = Arc::new(AppState {
                 db,
                 config: config.clone(),
+                running: Default::default(),
             });
 
             let app = create_router(state);
@@ -347,21 +598,237 @@ async fn main() -> Result<()> {
     }
 }
 
+/// The config for a subcommand: the `--config` file, which has to load, or
+/// else an auto-discovered one with defaults for anything it leaves out.
+fn load_cli_config(config: Option<&str>) -> Result<MutationTestConfig> {
+    ConfigLoader::new().load_cli_config(Path::new("."), config).map_err(anyhow::Error::msg)
+}
+
+/// The candidates of each of `files` the config selects, without testing
+/// them. Files the config skips are logged when `log_skips` is set.
+async fn find_candidates(files: &[String], test_config: MutationTestConfig, log_skips: bool) -> Result<Vec<FileCandidates>> {
+    let selection = resolve_files(files, None, &test_config).map_err(anyhow::Error::msg)?;
+    if log_skips {
+        for skipped in &selection.skipped {
+            MutationLogger::warn_file(&skipped.path, &format!("skipped: {}", skipped.reason));
+        }
+    }
+
+    let engine = MutationEngine::new(test_config);
+    let mut listings = Vec::new();
+    for file in &selection.files {
+        let code = fs::read_to_string(file)?;
+        listings.push(FileCandidates::new(file, engine.dry_run(&code).await?));
+    }
+    Ok(listings)
+}
+
+/// The `coverage_file` report, read once for all files of a run.
+fn load_coverage(test_config: &MutationTestConfig) -> Result<Option<CoverageMap>> {
+    match &test_config.coverage_file {
+        Some(path) => Ok(Some(CoverageMap::load(Path::new(path)).map_err(anyhow::Error::msg)?)),
+        None => Ok(None),
+    }
+}
+
+/// Runs mutation testing on one file of a `TestFiles` run and logs its
+/// summary, attributing each line to the file since files run concurrently.
+async fn test_file(
+    test_config: &MutationTestConfig,
+    coverage: Option<&CoverageMap>,
+    slots: &Arc<Semaphore>,
+    file: String,
+) -> Result<Option<(String, MutationReport)>> {
+    MutationLogger::info_file(&file, "=== Mutation Testing ===");
+    // One unreadable file, e.g. one that isn't UTF-8, is reported as failed
+    // rather than ending the whole run.
+    let code = match fs::read_to_string(&file) {
+        Ok(code) => code,
+        Err(e) => {
+            let message = format!("Failed to read source: {}", e);
+            MutationLogger::error_file(&file, &message);
+            let report = MutationReport {
+                source_file: Some(file.clone()),
+                file_error: Some(message),
+                ..MutationReport::new()
+            };
+            return Ok(Some((file, report)));
+        }
+    };
+    MutationLogger::info_file(&file, "Analyzing source code for mutation candidates...");
+    // Each file gets its own engine, so its progress and timings aren't
+    // mixed up with those of the files running alongside it.
+    let mut engine = MutationEngine::for_file_with_coverage(test_config.clone(), Path::new(&file), coverage)
+        .map_err(anyhow::Error::msg)?
+        .with_mutant_slots(slots.clone());
+    if let Some(command) = test_config.on_survivor_command.as_deref()
+        && test_config.on_survivor_inline
+    {
+        engine = engine.with_survivor_hook(SurvivorHook::new(command, &file));
+    }
+    match engine.run_mutation_testing(&code).await {
+        Ok(mut report) => {
+            report.source_file = Some(file.clone());
+
+            MutationLogger::summary_file(
+                &file,
+                &format!("Total mutations: {}", report.total_mutations),
+            );
+            MutationLogger::summary_file(
+                &file,
+                &format!(
+                    "Killed: {} | Survived: {} | Timeouts: {} | Errors: {} | Skipped: {}",
+                    report.killed_mutations,
+                    report.survived_mutations,
+                    report.timeout_mutations,
+                    report.error_mutations,
+                    report.skipped_mutations
+                ),
+            );
+            if report.uncovered_mutations > 0 {
+                MutationLogger::summary_file(
+                    &file,
+                    &format!("Uncovered: {} (not tested)", report.uncovered_mutations),
+                );
+            }
+            if report.capped_mutations > 0 {
+                MutationLogger::summary_file(
+                    &file,
+                    &format!("Over max_mutations_per_line: {} (not tested)", report.capped_mutations),
+                );
+            }
+            MutationLogger::summary_file(
+                &file,
+                &format!("Mutation Score: {:.1}%", report.mutation_score),
+            );
+            MutationLogger::summary_file(
+                &file,
+                &format!(
+                    "Wall Time: {:.2}s, CPU Time: {:.2}s",
+                    report.total_wall_time_seconds, report.total_cpu_time_seconds
+                ),
+            );
+            if test_config.group_by_module {
+                print!("{}", ReportGenerator::new().generate_module_summary(&report));
+            }
+            if report.survived_mutations > 0 {
+                MutationLogger::warn_file(
+                    &file,
+                    "Some mutations survived. Consider improving your tests to catch these cases.",
+                );
+                MutationLogger::fix(
+                    "Review survived mutations and add assertions or edge case tests.",
+                );
+            }
+            if report.error_mutations > 0 {
+                MutationLogger::error_file(
+                    &file,
+                    "Some mutations caused errors. Check for panics or unhandled cases in your code.",
+                );
+            }
+            Ok(Some((file, report)))
+        }
+        Err(e) => {
+            MutationLogger::error_file(
+                &file,
+                &format!("Error running mutation testing for {}: {}", file, e),
+            );
+            MutationLogger::fix(
+                "Ensure the file compiles and contains valid Rust code with tests.",
+            );
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+async fn save_to_sqlite(path: &str, reports: &[(String, MutationReport)]) -> anyhow::Result<()> {
+    use crate::models::CreateMutationTestRequest;
+    use crate::store::{record_run, sqlite::SqliteStore};
+
+    let store = SqliteStore::connect(path).await?;
+    for (file, report) in reports {
+        // A file that couldn't be read has no results to store, and a file
+        // that changed since can't fail the run after its reports are out.
+        if report.file_error.is_some() {
+            MutationLogger::warn_file(file, "Not saved to SQLite: the file could not be read");
+            continue;
+        }
+        let source_code = match fs::read_to_string(file) {
+            Ok(source_code) => source_code,
+            Err(e) => {
+                MutationLogger::error_file(file, &format!("Not saved to SQLite: failed to read source: {}", e));
+                continue;
+            }
+        };
+        let request = CreateMutationTestRequest {
+            name: file.clone(),
+            description: None,
+            source_code,
+            language: Some("rust".to_string()),
+        };
+        let mutation_test = record_run(&store, request, report).await?;
+        MutationLogger::info_file(path, &format!("Saved run {} for {} to", mutation_test.id, file));
+    }
+    Ok(())
+}
+
+const SQLITE_FEATURE_REQUIRED: &str = "--sqlite-db requires building with the `sqlite` feature";
+
+#[cfg(not(feature = "sqlite"))]
+async fn save_to_sqlite(_path: &str, _reports: &[(String, MutationReport)]) -> anyhow::Result<()> {
+    anyhow::bail!(SQLITE_FEATURE_REQUIRED)
+}
+
+/// Reads a JSON report written by `--json`, either a single report or the
+/// multi-file map keyed by path.
+fn read_prior_report(path: &str, file: &str) -> anyhow::Result<MutationReport> {
+    let content = fs::read_to_string(path)?;
+    if let Ok(report) = serde_json::from_str::<MutationReport>(&content) {
+        return Ok(report);
+    }
+    let mut reports: std::collections::BTreeMap<String, MutationReport> = serde_json::from_str(&content)?;
+    reports
+        .remove(file)
+        .ok_or_else(|| anyhow::anyhow!("{} has no results for {}", path, file))
+}
+
 fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/health", get(health::health_check))
         .route("/ready", get(health::readiness_check))
         .route("/api/v1/mutations", post(mutations::create_mutation))
         .route("/api/v1/mutations", get(mutations::list_mutations))
+        .route("/api/v1/mutations/trend", get(mutations::get_mutation_trend))
         .route("/api/v1/mutations/:id", get(mutations::get_mutation))
         .route(
             "/api/v1/mutations/:id/results",
             get(mutations::get_mutation_results),
         )
+        .route(
+            "/api/v1/mutations/:id/summary",
+            get(mutations::get_mutation_summary),
+        )
+        .route(
+            "/api/v1/mutations/:id/report",
+            get(mutations::get_mutation_report),
+        )
+        .route(
+            "/api/v1/mutations/:id/results/:result_id",
+            patch(mutations::update_result_triage),
+        )
         .route(
             "/api/v1/mutations/:id/start",
             post(mutations::start_mutation_testing),
         )
+        .route(
+            "/api/v1/mutations/:id/events",
+            get(mutations::stream_mutation_events),
+        )
+        .route(
+            "/api/v1/mutations/:id/cancel",
+            post(mutations::cancel_mutation_testing),
+        )
         .route(
             "/api/v1/mutations/:id/dry-run",
             get(mutations::dry_run_mutation_testing),
@@ -371,48 +838,137 @@ fn create_router(state: Arc<AppState>) -> Router {
         .layer(TimeoutLayer::new(Duration::from_secs(30)))
 }
 
+async fn run_watch(files: &[String], test_config: MutationTestConfig, debounce: Duration) -> anyhow::Result<()> {
+    // Editors often save by replacing the file, so the parent directories
+    // are watched and events are matched against the files' full paths.
+    let mut watched = HashMap::new();
+    for file in files {
+        watched.insert(fs::canonicalize(file)?, file.clone());
+    }
+    let (sender, mut changes) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && matches!(event.kind, notify::EventKind::Create(_) | notify::EventKind::Modify(_))
+        {
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+        }
+    })?;
+    let directories: HashSet<&Path> = watched.keys().filter_map(|path| path.parent()).collect();
+    for directory in directories {
+        watcher.watch(directory, notify::RecursiveMode::NonRecursive)?;
+    }
+
+    let coverage = load_coverage(&test_config)?;
+    let mut engines = HashMap::new();
+    for file in files {
+        let engine = MutationEngine::for_file_with_coverage(test_config.clone(), Path::new(file), coverage.as_ref())
+            .map_err(anyhow::Error::msg)?;
+        engines.insert(file, engine);
+    }
+    let mut survivors = HashMap::new();
+    for file in files {
+        watch_run(&engines[file], file, &mut survivors).await;
+    }
+    MutationLogger::info(&format!("Watching {} file(s) for changes", files.len()));
+
+    while let Some(path) = changes.recv().await {
+        let mut changed = HashSet::from([path]);
+        // A single save can fire several events; wait until they settle.
+        while let Ok(Some(path)) = tokio::time::timeout(debounce, changes.recv()).await {
+            changed.insert(path);
+        }
+        for file in files.iter().filter(|file| changed.iter().any(|path| watched.get(path) == Some(*file))) {
+            watch_run(&engines[file], file, &mut survivors).await;
+        }
+    }
+    Ok(())
+}
+
+/// Tests `file` and prints how its survivors changed since the last run.
+async fn watch_run(engine: &MutationEngine, file: &str, survivors: &mut HashMap<String, HashSet<MutantKey>>) {
+    let code = match fs::read_to_string(file) {
+        Ok(code) => code,
+        Err(e) => return MutationLogger::error_file(file, &format!("Failed to read: {}", e)),
+    };
+    let report = match engine.run_mutation_testing(&code).await {
+        Ok(report) => report,
+        Err(e) => return MutationLogger::error_file(file, &format!("Mutation testing failed: {}", e)),
+    };
+
+    let delta = diff_runs(survivors.get(file).unwrap_or(&HashSet::new()), &report);
+    if delta.is_empty() && survivors.contains_key(file) {
+        MutationLogger::info_file(file, &format!("No mutant changed outcome (score {:.1}%)", report.mutation_score));
+        return;
+    }
+    MutationLogger::info_file(
+        file,
+        &format!(
+            "{} newly surviving, {} newly killed (score {:.1}%)",
+            delta.newly_survived.len(),
+            delta.newly_killed.len(),
+            report.mutation_score
+        ),
+    );
+    for (label, results) in [("survived", &delta.newly_survived), ("killed", &delta.newly_killed)] {
+        for result in results {
+            let candidate = &result.candidate;
+            let message = format!(
+                "{} {}:{} {:?} '{}'",
+                label, candidate.line, candidate.column, candidate.mutation_type, candidate.original_code
+            );
+            if label == "survived" {
+                MutationLogger::warn_file(file, &message);
+            } else {
+                MutationLogger::info_file(file, &message);
+            }
+        }
+    }
+    survivors.insert(file.to_string(), survivor_keys(&report));
+}
+
 async fn enqueue_jobs(
     files: Vec<String>,
     config: Option<String>,
     queue_url: &str,
     queue_name: &str,
     filter_types: Option<Vec<MutationType>>,
+    priority: Option<u8>,
 ) -> anyhow::Result<()> {
-    let test_config = if let Some(cfg_path) = config {
-        let cfg_str = std::fs::read_to_string(cfg_path)?;
-        Some(toml::from_str::<MutationTestConfig>(&cfg_str)?)
-    } else {
-        None
+    // Without --config the runner's defaults apply, not this machine's
+    // auto-discovered config.
+    let test_config = match config {
+        Some(path) => Some(load_cli_config(Some(&path))?),
+        None => None,
     };
     let conn = Connection::connect(queue_url, ConnectionProperties::default()).await?;
-    let channel = conn.create_channel().await?;
-    channel
-        .queue_declare(
-            queue_name,
-            QueueDeclareOptions::default(),
-            FieldTable::default(),
-        )
-        .await?;
+    let channel = declare_job_queue(&conn, queue_name).await?;
     for file in &files {
         let job = MutationJob {
             file: file.clone(),
             config: test_config.clone(),
             filter_types: filter_types.clone(),
+            priority,
         };
         let payload = serde_json::to_vec(&job)?;
+        let properties = match job.priority {
+            Some(priority) => BasicProperties::default().with_priority(priority),
+            None => BasicProperties::default(),
+        };
         channel
             .basic_publish(
                 "",
                 queue_name,
                 BasicPublishOptions::default(),
                 &payload,
-                BasicProperties::default(),
+                properties,
             )
             .await?
             .await?;
     }
     MutationLogger::info_file(
-        &queue_name,
+        queue_name,
         &format!("Enqueued {} jobs to queue {}", files.len(), queue_name),
     );
     Ok(())
@@ -422,16 +978,13 @@ async fn run_queue_runner(
     queue_url: &str,
     queue_name: &str,
     output_dir: Option<String>,
+    max_retries: u32,
 ) -> anyhow::Result<()> {
-    let _ = output_dir;
     let conn = Connection::connect(queue_url, ConnectionProperties::default()).await?;
-    let channel = conn.create_channel().await?;
+    let channel = declare_job_queue(&conn, queue_name).await?;
+    let dead_queue = dead_letter_queue(queue_name);
     channel
-        .queue_declare(
-            queue_name,
-            QueueDeclareOptions::default(),
-            FieldTable::default(),
-        )
+        .queue_declare(&dead_queue, QueueDeclareOptions::default(), FieldTable::default())
         .await?;
     let mut consumer = channel
         .basic_consume(
@@ -443,31 +996,27 @@ async fn run_queue_runner(
         .await?;
     while let Some(delivery) = consumer.next().await {
         let delivery = delivery?;
-        let job: MutationJob = serde_json::from_slice(&delivery.data)?;
-        MutationLogger::info_file(
-            &job.file,
-            &format!("Runner picked up job for file: {}", job.file),
-        );
-        let code = std::fs::read_to_string(&job.file)?;
-        let config = job
-            .config
-            .clone()
-            .unwrap_or_else(MutationTestConfig::default);
-        let mut config = config;
-        if let Some(filter) = &job.filter_types {
-            config.mutation_types = filter.clone();
-        }
-        let engine = MutationEngine::new(config);
-        let start = std::time::Instant::now();
-        let report = engine.run_mutation_testing(&code).await;
-        let elapsed = start.elapsed().as_secs_f64();
-        MutationLogger::info_file(&job.file, &format!("Job completed in {:.2}s", elapsed));
-        if let Ok(report) = &report {
-            if report.survived_mutations > 0 {
-                MutationLogger::warn(
-                    "[Notify] Some mutations survived. Consider improving your tests.",
-                );
-            }
+        let outcome = match serde_json::from_slice::<MutationJob>(&delivery.data) {
+            Ok(job) => run_queued_job(&job, output_dir.as_deref()).await.map_err(|e| (job.file, e, true)),
+            Err(e) => Err((queue_name.to_string(), format!("Invalid job payload: {}", e), false)),
+        };
+        if let Err((label, error, retryable)) = outcome {
+            // A nack can't change the message's headers, so a retry is
+            // published again with the count bumped and the original acked.
+            let (queue, properties) = match retry_properties(&delivery.properties, retryable, max_retries) {
+                Some(properties) => {
+                    MutationLogger::warn_file(&label, &format!("{}; retrying", error));
+                    (queue_name, properties)
+                }
+                None => {
+                    MutationLogger::error_file(&label, &format!("{}; moving job to {}", error, dead_queue));
+                    (dead_queue.as_str(), delivery.properties.clone())
+                }
+            };
+            channel
+                .basic_publish("", queue, BasicPublishOptions::default(), &delivery.data, properties)
+                .await?
+                .await?;
         }
         channel
             .basic_ack(delivery.delivery_tag, BasicAckOptions::default())
@@ -475,3 +1024,29 @@ async fn run_queue_runner(
     }
     Ok(())
 }
+
+async fn run_queued_job(job: &MutationJob, output_dir: Option<&str>) -> Result<(), String> {
+    MutationLogger::info_file(
+        &job.file,
+        &format!("Runner picked up job for file: {}", job.file),
+    );
+    let code = std::fs::read_to_string(&job.file).map_err(|e| format!("Failed to read {}: {}", job.file, e))?;
+    let mut config = job.config.clone().unwrap_or_default();
+    config.apply_filter_types(job.filter_types.as_deref());
+    let engine = MutationEngine::for_file(config, Path::new(&job.file))?;
+    let start = std::time::Instant::now();
+    let report = engine
+        .run_mutation_testing(&code)
+        .await
+        .map_err(|e| format!("Mutation testing failed: {}", e))?;
+    let elapsed = start.elapsed().as_secs_f64();
+    MutationLogger::info_file(&job.file, &format!("Job completed in {:.2}s", elapsed));
+    if report.survived_mutations > 0 {
+        MutationLogger::warn("[Notify] Some mutations survived. Consider improving your tests.");
+    }
+    if let Some(output_dir) = output_dir {
+        let path = write_job_report(Path::new(output_dir), &job.file, &report)?;
+        MutationLogger::info_file(&job.file, &format!("Wrote report to {}", path.display()));
+    }
+    Ok(())
+}
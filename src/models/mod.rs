@@ -47,10 +47,28 @@ pub struct MutationResult {
     pub test_result: TestResult,
     pub execution_time_ms: Option<i64>,
     pub error_message: Option<String>,
+    pub triage_status: TriageStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// A reviewer's decision about a result, typically a survivor.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "triage_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TriageStatus {
+    #[default]
+    Untriaged,
+    Accepted,
+    NeedsTest,
+    FalsePositive,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateTriageRequest {
+    pub triage_status: TriageStatus,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "test_result", rename_all = "lowercase")]
 pub enum TestResult {
@@ -62,6 +80,35 @@ pub enum TestResult {
     Skipped,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MutationTestJob {
+    pub id: Uuid,
+    pub mutation_test_id: Uuid,
+    pub status: JobStatus,
+    pub total_mutations: Option<i32>,
+    pub completed_mutations: i32,
+    pub error_message: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub heartbeat_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "mutation_job_status", rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationTestWithJob {
+    #[serde(flatten)]
+    pub test: MutationTest,
+    pub job: Option<MutationTestJob>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MutationTestSummary {
     pub total_mutations: i64,
@@ -73,6 +120,90 @@ pub struct MutationTestSummary {
     pub mutation_score: f64,
 }
 
+/// Sent as a `mutation` event on `/mutations/:id/events` each time a mutant
+/// finishes, with the run's tallies so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationProgressEvent {
+    pub line: usize,
+    pub column: usize,
+    pub mutation_type: String,
+    pub test_result: TestResult,
+    pub completed: usize,
+    pub killed: usize,
+    pub survived: usize,
+}
+
+/// The final `done` event of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunFinishedEvent {
+    pub status: MutationTestStatus,
+    pub total_mutations: usize,
+    pub killed_mutations: usize,
+    pub survived_mutations: usize,
+    pub mutation_score: f64,
+}
+
+#[derive(Debug, Clone)]
+pub enum RunEvent {
+    Mutation(MutationProgressEvent),
+    Done(RunFinishedEvent),
+}
+
+/// Position in the mutation test list, newest first. Encoded as an opaque
+/// hex string for the `after` query parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl ListCursor {
+    pub fn after(test: &MutationTest) -> Self {
+        Self { created_at: test.created_at, id: test.id }
+    }
+
+    pub fn encode(&self) -> String {
+        hex::encode(format!("{}/{}", self.created_at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+        let (micros, id) = decoded.split_once('/')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+/// A page of mutation tests. `next_cursor` is set when the page is full and
+/// more tests may follow it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationTestPage {
+    pub items: Vec<MutationTest>,
+    pub next_cursor: Option<String>,
+}
+
+impl MutationTestPage {
+    pub fn new(items: Vec<MutationTest>, limit: u32) -> Self {
+        let next_cursor = match items.last() {
+            Some(last) if items.len() == limit as usize => Some(ListCursor::after(last).encode()),
+            _ => None,
+        };
+        Self { items, next_cursor }
+    }
+}
+
+/// One completed run in a named test's score trend.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MutationScorePoint {
+    pub mutation_test_id: Uuid,
+    pub completed_at: DateTime<Utc>,
+    pub total_mutations: i64,
+    pub killed_mutations: i64,
+    pub mutation_score: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MutationTestWithResults {
     #[serde(flatten)]
@@ -81,6 +212,19 @@ pub struct MutationTestWithResults {
     pub summary: MutationTestSummary,
 }
 
+/// A test's metadata and result counts, without its source or results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationTestOverview {
+    pub id: Uuid,
+    pub name: String,
+    pub status: MutationTestStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub summary: MutationTestSummary,
+}
+
 impl MutationTestSummary {
     pub fn calculate(results: &[MutationResult]) -> Self {
         let total = results.len() as i64;
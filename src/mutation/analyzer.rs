@@ -1,51 +1,301 @@
-use crate::mutation::types::{MutationCandidate, MutationTestConfig, MutationType};
+use crate::mutation::ast_mutator::{
+    break_continue_candidates, closure_predicate_candidates, field_swap_candidates, optional_unwrap_candidates,
+    overflow_method_candidates, return_value_candidates,
+    statement_deletion_candidates, switch_case_candidates, trait_impl_line_ranges,
+};
+use crate::mutation::error::MutationError;
+use crate::mutation::logger::MutationLogger;
+use crate::mutation::types::{MutationCandidate, MutationTestConfig, MutationType, NumericMutationKind};
+use regex::Regex;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::ops::Range;
 use tracing::{debug, instrument};
 
+/// The rule that made the analyzer pass over a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    ExcludedPattern,
+    MutationIgnore,
+    Empty,
+    Comment,
+    Attribute,
+    FnSignature,
+    LetBinding,
+    Const,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SkipReason::ExcludedPattern => "excluded_pattern",
+            SkipReason::MutationIgnore => "mutation-ignore",
+            SkipReason::Empty => "empty",
+            SkipReason::Comment => "comment",
+            SkipReason::Attribute => "attribute",
+            SkipReason::FnSignature => "fn_signature",
+            SkipReason::LetBinding => "let_binding",
+            SkipReason::Const => "const",
+        };
+        f.write_str(name)
+    }
+}
+
+/// An `excluded_patterns` entry: a substring, or a regex when written with a
+/// `regex:` prefix.
+enum LinePattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl LinePattern {
+    /// Invalid regexes are reported and ignored rather than failing the run.
+    fn parse(pattern: &str) -> Option<Self> {
+        let Some(expression) = pattern.strip_prefix("regex:") else {
+            return Some(LinePattern::Substring(pattern.to_string()));
+        };
+        match Regex::new(expression) {
+            Ok(regex) => Some(LinePattern::Regex(regex)),
+            Err(e) => {
+                MutationLogger::warn(&format!("Ignoring invalid excluded_patterns regex '{}': {}", expression, e));
+                None
+            }
+        }
+    }
+
+    fn matches(&self, line: &str) -> bool {
+        match self {
+            LinePattern::Substring(pattern) => line.contains(pattern.as_str()),
+            LinePattern::Regex(regex) => regex.is_match(line),
+        }
+    }
+}
+
 pub struct CodeAnalyzer {
     config: MutationTestConfig,
+    excluded_patterns: Vec<LinePattern>,
+    uncovered_lines: BTreeSet<usize>,
 }
 
 impl CodeAnalyzer {
     pub fn new(config: MutationTestConfig) -> Self {
-        Self { config }
+        let excluded_patterns = config.excluded_patterns.iter().filter_map(|pattern| LinePattern::parse(pattern)).collect();
+        Self { config, excluded_patterns, uncovered_lines: BTreeSet::new() }
+    }
+
+    /// Lines that no test executes, from a coverage report; see
+    /// [`Self::drop_uncovered`].
+    pub fn with_uncovered_lines(mut self, lines: BTreeSet<usize>) -> Self {
+        self.uncovered_lines = lines;
+        self
+    }
+
+    /// Drops the candidates on uncovered lines, whose mutants could only
+    /// survive, and returns how many mutants they would have made.
+    pub fn drop_uncovered(&self, candidates: &mut Vec<MutationCandidate>) -> usize {
+        let mut uncovered = 0;
+        candidates.retain(|candidate| {
+            if !self.uncovered_lines.contains(&candidate.line) {
+                return true;
+            }
+            debug!("Skipping {:?} candidate at line {}: not covered by any test", candidate.mutation_type, candidate.line);
+            uncovered += candidate.suggested_mutations.len();
+            false
+        });
+        uncovered
     }
 
     #[instrument(skip(self, source_code))]
     pub fn find_mutation_candidates(&self, source_code: &str) -> Vec<MutationCandidate> {
         let mut candidates = Vec::new();
         let lines: Vec<&str> = source_code.lines().collect();
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source_code.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let mut scope = ScopeTracker::default();
+        let mut line_modules = Vec::with_capacity(lines.len());
+
+        let mut excluded_lines = Vec::with_capacity(lines.len());
 
         for (line_number, line) in lines.iter().enumerate() {
-            if self.should_skip_line(line) {
-                continue;
+            line_modules.push(scope.module_path());
+            excluded_lines.push(scope.within_function(line, &self.config.excluded_functions));
+            let skip = self.should_skip_line(line);
+            if self.config.debug_skips {
+                match skip {
+                    Some(reason) => MutationLogger::debug(&format!("line {}: skipped ({})", line_number + 1, reason)),
+                    None => MutationLogger::debug(&format!("line {}: analyzed", line_number + 1)),
+                }
+            }
+            if skip.is_none() {
+                let module_path = &line_modules[line_number];
+                candidates.extend(self.analyze_line(line, line_number + 1).into_iter().map(|mut candidate| {
+                    candidate.module_path = module_path.clone();
+                    locate_in_source(line, line_starts[line_number], &mut candidate);
+                    candidate
+                }));
             }
+            scope.advance(line);
+        }
 
-            candidates.extend(self.analyze_line(line, line_number + 1));
+        if self.config.mutation_types.contains(&MutationType::FieldSwap) {
+            self.add_ast_candidates(field_swap_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::OverflowMethod) {
+            self.add_ast_candidates(overflow_method_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::ClosurePredicate) {
+            self.add_ast_candidates(closure_predicate_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::ReturnValue) {
+            self.add_ast_candidates(return_value_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::OptionalUnwrap) {
+            self.add_ast_candidates(optional_unwrap_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::BreakContinueReplacement) {
+            self.add_ast_candidates(break_continue_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::SwitchCase) {
+            self.add_ast_candidates(switch_case_candidates(source_code), &lines, &line_modules, &mut candidates);
+        }
+        if self.config.mutation_types.contains(&MutationType::StatementDeletion) {
+            self.add_ast_candidates(statement_deletion_candidates(source_code), &lines, &line_modules, &mut candidates);
         }
 
+        let candidates = self.finish_candidates(source_code, &excluded_lines, candidates);
         debug!("Found {} mutation candidates", candidates.len());
         candidates
     }
 
-    fn should_skip_line(&self, line: &str) -> bool {
-        for pattern in &self.config.excluded_patterns {
-            if line.contains(pattern) {
-                return true;
-            }
+    /// Puts candidates found elsewhere, such as the engine's full syntax-tree
+    /// pass, through the analyzer's own opt-outs: unconfigured types,
+    /// excluded patterns and functions, skipped trait impls and excluded
+    /// mutations.
+    pub fn filter_extra_candidates(&self, source_code: &str, found: Vec<MutationCandidate>) -> Vec<MutationCandidate> {
+        let lines: Vec<&str> = source_code.lines().collect();
+        let mut scope = ScopeTracker::default();
+        let mut line_modules = Vec::with_capacity(lines.len());
+        let mut excluded_lines = Vec::with_capacity(lines.len());
+        for line in &lines {
+            line_modules.push(scope.module_path());
+            excluded_lines.push(scope.within_function(line, &self.config.excluded_functions));
+            scope.advance(line);
+        }
+
+        let found = found
+            .into_iter()
+            .filter(|candidate| {
+                candidate.mutation_type.is_implemented() && self.config.mutation_types.contains(&candidate.mutation_type)
+            })
+            .collect();
+        let mut candidates = Vec::new();
+        self.add_ast_candidates(Ok(found), &lines, &line_modules, &mut candidates);
+        self.finish_candidates(source_code, &excluded_lines, candidates)
+    }
+
+    fn finish_candidates(
+        &self,
+        source_code: &str,
+        excluded_lines: &[bool],
+        mut candidates: Vec<MutationCandidate>,
+    ) -> Vec<MutationCandidate> {
+        if excluded_lines.contains(&true) {
+            candidates.retain(|candidate| {
+                let excluded = excluded_lines[candidate.line - 1];
+                if excluded {
+                    debug!(
+                        "Skipping {:?} candidate at line {}: inside an excluded function",
+                        candidate.mutation_type, candidate.line
+                    );
+                }
+                !excluded
+            });
+        }
+
+        drop_degenerate_candidates(&mut candidates);
+        // Exclusions go first, so a mutant shared by two types stays with the
+        // one that is kept.
+        if self.config.skip_trait_impls {
+            self.drop_trait_impl_candidates(source_code, &mut candidates);
+        }
+        if !self.config.excluded_mutations.is_empty() {
+            candidates.retain(|candidate| !self.config.excluded_mutations.contains(&candidate.mutation_type));
+        }
+        candidates.sort_by(|a, b| (a.line, a.column, &a.mutation_type).cmp(&(b.line, b.column, &b.mutation_type)));
+        drop_duplicate_mutants(&mut candidates);
+        candidates
+    }
+
+    // Field swaps, overflow methods, closure predicates, return values and
+    // statement deletions come from the syntax tree rather than line
+    // scanning, so only the opt-out rules (excluded patterns,
+    // mutation-ignore) apply to them.
+    fn add_ast_candidates(
+        &self,
+        found: Result<Vec<MutationCandidate>, MutationError>,
+        lines: &[&str],
+        line_modules: &[String],
+        candidates: &mut Vec<MutationCandidate>,
+    ) {
+        match found {
+            Ok(swaps) => candidates.extend(
+                swaps
+                    .into_iter()
+                    .filter(|candidate| {
+                        !matches!(
+                            self.should_skip_line(lines[candidate.line - 1]),
+                            Some(SkipReason::ExcludedPattern | SkipReason::MutationIgnore)
+                        )
+                    })
+                    .map(|mut candidate| {
+                        candidate.module_path = line_modules[candidate.line - 1].clone();
+                        candidate
+                    }),
+            ),
+            Err(e) => debug!("Not looking for syntax-tree candidates: {}", e),
+        }
+    }
+
+    fn drop_trait_impl_candidates(&self, source_code: &str, candidates: &mut Vec<MutationCandidate>) {
+        match trait_impl_line_ranges(source_code, &self.config.skipped_traits) {
+            Ok(ranges) => candidates.retain(|candidate| {
+                let inside = ranges.iter().any(|range| range.contains(&candidate.line));
+                if inside {
+                    debug!(
+                        "Skipping {:?} candidate at line {}: inside a skipped trait impl",
+                        candidate.mutation_type, candidate.line
+                    );
+                }
+                !inside
+            }),
+            Err(e) => debug!("Not skipping trait impls: {}", e),
+        }
+    }
+
+    fn should_skip_line(&self, line: &str) -> Option<SkipReason> {
+        if self.excluded_patterns.iter().any(|pattern| pattern.matches(line)) {
+            return Some(SkipReason::ExcludedPattern);
         }
         if line.contains("// mutation-ignore") || line.contains("#[mutation_ignore]") {
-            return true;
+            return Some(SkipReason::MutationIgnore);
         }
         let trimmed = line.trim();
-        trimmed.is_empty()
-            || trimmed.starts_with("//")
-            || trimmed.starts_with("#")
-            || trimmed.starts_with("/*")
-            || trimmed.ends_with("*/")
-            || trimmed.starts_with("fn ")
-            || trimmed.starts_with("pub fn ")
-            || trimmed.starts_with("let ")
-            || trimmed.starts_with("const ")
+        if trimmed.is_empty() {
+            Some(SkipReason::Empty)
+        } else if trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.ends_with("*/") {
+            Some(SkipReason::Comment)
+        } else if trimmed.starts_with("#") {
+            Some(SkipReason::Attribute)
+        } else if trimmed.starts_with("fn ") || trimmed.starts_with("pub fn ") {
+            Some(SkipReason::FnSignature)
+        } else if trimmed.starts_with("let ") {
+            Some(SkipReason::LetBinding)
+        } else if trimmed.starts_with("const ") {
+            Some(SkipReason::Const)
+        } else {
+            None
+        }
     }
 
     fn analyze_line(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
@@ -75,6 +325,22 @@ impl CodeAnalyzer {
             candidates.extend(self.find_logical_operators(line, line_number));
         }
 
+        if self
+            .config
+            .mutation_types
+            .contains(&MutationType::AssignmentOperator)
+        {
+            candidates.extend(self.find_assignment_operators(line, line_number));
+        }
+
+        if self
+            .config
+            .mutation_types
+            .contains(&MutationType::IncrementDecrement)
+        {
+            candidates.extend(self.find_increment_decrement(line, line_number));
+        }
+
         if self
             .config
             .mutation_types
@@ -91,6 +357,14 @@ impl CodeAnalyzer {
             candidates.extend(self.find_numeric_literals(line, line_number));
         }
 
+        if self
+            .config
+            .mutation_types
+            .contains(&MutationType::StringLiteral)
+        {
+            candidates.extend(self.find_string_literals(line, line_number));
+        }
+
         if self
             .config
             .mutation_types
@@ -99,6 +373,40 @@ impl CodeAnalyzer {
             candidates.extend(self.find_conditional_boundaries(line, line_number));
         }
 
+        if self
+            .config
+            .mutation_types
+            .contains(&MutationType::PanicMacro)
+        {
+            candidates.extend(self.find_panic_macros(line, line_number));
+        }
+
+        if self
+            .config
+            .mutation_types
+            .contains(&MutationType::DefaultValue)
+        {
+            candidates.extend(self.find_default_values(line, line_number));
+        }
+
+        let masked = literal_and_comment_ranges(line);
+        if !masked.is_empty() {
+            candidates.retain(|candidate| {
+                if candidate.mutation_type == MutationType::StringLiteral {
+                    return true;
+                }
+                let offset = candidate.column - 1;
+                let inside = masked.iter().any(|range| range.contains(&offset));
+                if inside {
+                    debug!(
+                        "Dropping {:?} candidate at line {}, col {}: inside a literal or comment",
+                        candidate.mutation_type, candidate.line, candidate.column
+                    );
+                }
+                !inside
+            });
+        }
+
         candidates
     }
 
@@ -112,13 +420,23 @@ impl CodeAnalyzer {
                 let actual_pos = start + pos;
 
                 if self.is_standalone_operator(line, actual_pos, op) {
-                    let mutations = self.get_arithmetic_mutations(op);
+                    let divisor = if *op == "%" { literal_divisor(&line[actual_pos + 1..]) } else { None };
+                    let (original_code, mutations) = match divisor {
+                        Some((len, divisor)) => {
+                            let original = &line[actual_pos..actual_pos + 1 + len];
+                            (original.to_string(), self.get_modulo_mutations(original, divisor))
+                        }
+                        None => (op.to_string(), self.get_arithmetic_mutations(op)),
+                    };
                     candidates.push(MutationCandidate {
                         line: line_number,
                         column: actual_pos + 1,
-                        original_code: op.to_string(),
+                        original_code,
                         mutation_type: MutationType::ArithmeticOperator,
                         suggested_mutations: mutations,
+                        module_path: String::new(),
+                        byte_start: None,
+                        byte_end: None,
                     });
                 }
                 start = actual_pos + 1;
@@ -128,6 +446,80 @@ impl CodeAnalyzer {
         candidates
     }
 
+    /// Compound assignments (`+=`, `-=`, `*=`, `/=`, `%=`), swapped within the
+    /// arithmetic family. The operator must not continue an `<<=`/`>>=` or
+    /// another operator, and the `=` must not start an `==`.
+    fn find_assignment_operators(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
+        let bytes = line.as_bytes();
+        let mut candidates = Vec::new();
+
+        for (pos, _) in line.match_indices('=') {
+            if pos == 0 || !matches!(bytes[pos - 1], b'+' | b'-' | b'*' | b'/' | b'%') {
+                continue;
+            }
+            let start = pos - 1;
+            let preceded_by_operator = start > 0 && b"=!<>+-*/%&|^".contains(&bytes[start - 1]);
+            if preceded_by_operator || bytes.get(pos + 1) == Some(&b'=') {
+                continue;
+            }
+            let original = &line[start..=pos];
+            candidates.push(MutationCandidate {
+                line: line_number,
+                column: start + 1,
+                original_code: original.to_string(),
+                mutation_type: MutationType::AssignmentOperator,
+                suggested_mutations: self
+                    .get_arithmetic_mutations(&original[..1])
+                    .into_iter()
+                    .map(|op| format!("{}=", op))
+                    .collect(),
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            });
+        }
+
+        candidates
+    }
+
+    /// Steps of one, which Rust writes as `x += 1`/`x -= 1` or `n + 1`/`n - 1`
+    /// for lack of `++`/`--`; the direction of the step is flipped. A plain
+    /// `+`/`-` must follow an identifier or a closing bracket, so unary minus
+    /// and `return -1` are left alone, and the `1` must be a whole unsuffixed
+    /// literal.
+    fn find_increment_decrement(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
+        let bytes = line.as_bytes();
+        let mut candidates = Vec::new();
+
+        for (pos, op) in line.match_indices(['+', '-']) {
+            if pos > 0 && b"=!<>+-*/%&|^".contains(&bytes[pos - 1]) {
+                continue;
+            }
+            let compound = bytes.get(pos + 1) == Some(&b'=');
+            if !compound && !follows_operand(&line[..pos]) {
+                continue;
+            }
+            let operand_start = pos + 1 + usize::from(compound);
+            let Some((len, 1)) = literal_divisor(&line[operand_start..]) else {
+                continue;
+            };
+            let original = &line[pos..operand_start + len];
+            let flipped = if op == "+" { "-" } else { "+" };
+            candidates.push(MutationCandidate {
+                line: line_number,
+                column: pos + 1,
+                original_code: original.to_string(),
+                mutation_type: MutationType::IncrementDecrement,
+                suggested_mutations: vec![format!("{}{}", flipped, &original[1..])],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            });
+        }
+
+        candidates
+    }
+
     fn find_relational_operators(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
         let mut candidates = Vec::new();
         let operators = ["==", "!=", "<", ">", "<=", ">="];
@@ -143,6 +535,9 @@ impl CodeAnalyzer {
                     original_code: op.to_string(),
                     mutation_type: MutationType::RelationalOperator,
                     suggested_mutations: mutations,
+                    module_path: String::new(),
+                    byte_start: None,
+                    byte_end: None,
                 });
                 start = actual_pos + op.len();
             }
@@ -165,6 +560,9 @@ impl CodeAnalyzer {
                     original_code: op.to_string(),
                     mutation_type: MutationType::LogicalOperator,
                     suggested_mutations: mutations,
+                    module_path: String::new(),
+                    byte_start: None,
+                    byte_end: None,
                 });
                 start = actual_pos + op.len();
             }
@@ -187,6 +585,9 @@ impl CodeAnalyzer {
                         original_code: literal.to_string(),
                         mutation_type: MutationType::BooleanLiteral,
                         suggested_mutations: vec![mutation.to_string()],
+                        module_path: String::new(),
+                        byte_start: None,
+                        byte_end: None,
                     });
                 }
                 start = actual_pos + literal.len();
@@ -194,24 +595,76 @@ impl CodeAnalyzer {
         }
         candidates
     }
+    // Byte strings are left alone since a `&str` replacement wouldn't type-check,
+    // as are strings with braces, which are most likely format strings whose
+    // placeholders must match the arguments.
+    fn find_string_literals(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
+        let bytes = line.as_bytes();
+        let mut candidates = Vec::new();
+
+        for range in literal_and_comment_ranges(line) {
+            if bytes[range.start] != b'"' {
+                continue;
+            }
+            let hashes = bytes[..range.start].iter().rev().take_while(|&&b| b == b'#').count();
+            let raw = range.start > hashes && bytes[range.start - hashes - 1] == b'r';
+            let start = if raw { range.start - hashes - 1 } else { range.start };
+            let fence = if raw { "#".repeat(hashes) } else { String::new() };
+            // A string still open at the end of the line continues on the next.
+            let Some(body) = line[range.start + 1..range.end].strip_suffix(&format!("\"{}", fence)) else {
+                continue;
+            };
+            if start > 0 && bytes[start - 1] == b'b' || body.contains(['{', '}']) {
+                continue;
+            }
+
+            let reversed: String = body.chars().rev().collect();
+            let mut mutations = vec!["\"\"".to_string(), "\"MUTATED\"".to_string()];
+            if raw {
+                mutations.push(format!("r{}\"{}\"{}", fence, reversed, fence));
+            } else if !body.contains('\\') {
+                mutations.push(format!("\"{}\"", reversed));
+            }
+
+            candidates.push(MutationCandidate {
+                line: line_number,
+                column: start + 1,
+                original_code: line[start..range.end].to_string(),
+                mutation_type: MutationType::StringLiteral,
+                suggested_mutations: mutations,
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            });
+        }
+
+        candidates
+    }
+
     fn find_numeric_literals(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
         let mut candidates = Vec::new();
-        let chars: Vec<char> = line.chars().collect();
+        let bytes = line.as_bytes();
         let mut i = 0;
 
-        while i < chars.len() {
-            if chars[i].is_ascii_digit() {
+        while i < bytes.len() {
+            if bytes[i].is_ascii_digit() {
                 let start = i;
-                while i < chars.len() && chars[i].is_ascii_digit() {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
                     i += 1;
                 }
-                let literal: String = chars[start..i].iter().collect();
+                let literal = &line[start..i];
+                let suffix_len = bytes[i..].iter().take_while(|b| b.is_ascii_alphanumeric()).count();
+                let suffix = &line[i..i + suffix_len];
+                i += suffix_len;
                 candidates.push(MutationCandidate {
                     line: line_number,
                     column: start + 1,
-                    original_code: literal.clone(),
+                    original_code: literal.to_string(),
                     mutation_type: MutationType::NumericLiteral,
-                    suggested_mutations: self.get_numeric_mutations(&literal),
+                    suggested_mutations: self.get_numeric_mutations(literal, suffix),
+                    module_path: String::new(),
+                    byte_start: None,
+                    byte_end: None,
                 });
             } else {
                 i += 1;
@@ -220,44 +673,258 @@ impl CodeAnalyzer {
         candidates
     }
 
-    fn find_conditional_boundaries(
-        &self,
-        _line: &str,
-        _line_number: usize,
-    ) -> Vec<MutationCandidate> {
-        Vec::new()
+    /// Comparisons in an `if` or `while` condition, flipped to the
+    /// neighbouring boundary (`<` and `<=`, `>` and `>=`). Only operators with
+    /// whitespace on both sides count, which leaves out generics and shifts.
+    fn find_conditional_boundaries(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
+        let Some(condition) = condition_range(line) else {
+            return Vec::new();
+        };
+        let bytes = line.as_bytes();
+        let mut candidates = Vec::new();
+
+        let mut i = condition.start;
+        while i < condition.end {
+            if !matches!(bytes[i], b'<' | b'>') || i == 0 || bytes[i - 1] != b' ' {
+                i += 1;
+                continue;
+            }
+            let len = if bytes.get(i + 1) == Some(&b'=') { 2 } else { 1 };
+            if bytes.get(i + len) != Some(&b' ') {
+                i += len;
+                continue;
+            }
+            let original = &line[i..i + len];
+            let flipped = match original {
+                "<" => "<=",
+                "<=" => "<",
+                ">" => ">=",
+                _ => ">",
+            };
+            candidates.push(MutationCandidate {
+                line: line_number,
+                column: i + 1,
+                original_code: original.to_string(),
+                mutation_type: MutationType::ConditionalBoundary,
+                suggested_mutations: vec![flipped.to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            });
+            i += len;
+        }
+
+        candidates
     }
 
-    fn is_standalone_operator(&self, line: &str, pos: usize, op: &str) -> bool {
-        let chars: Vec<char> = line.chars().collect();
+    fn find_panic_macros(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
+        let mut candidates = Vec::new();
+        let macros = ["unreachable!", "todo!", "unimplemented!", "panic!"];
 
-        if pos > 0 {
-            let prev_char = chars[pos - 1];
-            if "=!<>+-*/".contains(prev_char) {
-                return false;
+        for name in &macros {
+            let mut start = 0;
+            while let Some(pos) = line[start..].find(name) {
+                let actual_pos = start + pos;
+                start = actual_pos + name.len();
+
+                if !self.is_complete_word(line, actual_pos, &name[..name.len() - 1]) {
+                    continue;
+                }
+                let Some(end) = Self::find_macro_invocation_end(line, actual_pos + name.len()) else {
+                    continue;
+                };
+                let Some(replacement) = Self::panic_macro_replacement(&line[..actual_pos], &line[end..]) else {
+                    continue;
+                };
+
+                candidates.push(MutationCandidate {
+                    line: line_number,
+                    column: actual_pos + 1,
+                    original_code: line[actual_pos..end].to_string(),
+                    mutation_type: MutationType::PanicMacro,
+                    suggested_mutations: vec![replacement.to_string()],
+                    module_path: String::new(),
+                    byte_start: None,
+                    byte_end: None,
+                });
+                start = end;
+            }
+        }
+
+        candidates
+    }
+
+    // The whole call (`unwrap_or(0)`, `map_or(d, f)`, ...) is the original
+    // code so the affected fallback shows up in reports.
+    fn find_default_values(&self, line: &str, line_number: usize) -> Vec<MutationCandidate> {
+        let mut candidates = Vec::new();
+
+        for method in [".unwrap_or(", ".unwrap_or_default(", ".map_or("] {
+            let mut start = 0;
+            while let Some(pos) = line[start..].find(method) {
+                let call_start = start + pos + 1;
+                let args_start = start + pos + method.len() - 1;
+                start = args_start;
+                let Some(end) = Self::find_macro_invocation_end(line, args_start) else {
+                    continue;
+                };
+                let call = &line[call_start..end];
+                let args = line[args_start + 1..end - 1].trim();
+
+                let suggested_mutations = match method {
+                    ".unwrap_or(" => Self::get_default_literal_mutations(args)
+                        .into_iter()
+                        .map(|value| format!("unwrap_or({})", value))
+                        .collect(),
+                    // Without type information no sentinel value is guaranteed
+                    // to compile, so make reaching the default fatal instead.
+                    ".unwrap_or_default(" => vec!["unwrap_or_else(|| unreachable!(\"default value reached\"))".to_string()],
+                    // The default is used twice, which only compiles for a
+                    // value that can be copied, so only literals qualify.
+                    _ => match Self::split_top_level_comma(args) {
+                        Some((default, _)) if Self::is_literal(default) => {
+                            vec![format!("map_or({}, |_| {})", default, default)]
+                        }
+                        _ => Vec::new(),
+                    },
+                };
+
+                candidates.push(MutationCandidate {
+                    line: line_number,
+                    column: call_start + 1,
+                    original_code: call.to_string(),
+                    mutation_type: MutationType::DefaultValue,
+                    suggested_mutations,
+                    module_path: String::new(),
+                    byte_start: None,
+                    byte_end: None,
+                });
+                start = end;
+            }
+        }
+
+        candidates
+    }
+
+    fn get_default_literal_mutations(value: &str) -> Vec<String> {
+        match value {
+            "true" => vec!["false".to_string()],
+            "false" => vec!["true".to_string()],
+            "\"\"" => vec!["\"mutated\"".to_string()],
+            _ if value.starts_with('"') => vec!["\"\"".to_string()],
+            _ if value.parse::<i64>().is_ok() => match value.parse::<i64>().unwrap() {
+                0 => vec!["1".to_string()],
+                n => std::iter::once("0".to_string()).chain(n.checked_add(1).map(|next| next.to_string())).collect(),
+            },
+            _ if value.parse::<f64>().is_ok() => match value.parse::<f64>().unwrap() {
+                0.0 => vec!["1.0".to_string()],
+                _ => vec!["0.0".to_string()],
+            },
+            _ => vec!["Default::default()".to_string()],
+        }
+    }
+
+    fn is_literal(value: &str) -> bool {
+        matches!(value, "true" | "false")
+            || value.len() >= 2 && (value.starts_with('"') && value.ends_with('"') || value.starts_with('\'') && value.ends_with('\''))
+            || value.parse::<i64>().is_ok()
+            || value.parse::<f64>().is_ok()
+    }
+
+    fn split_top_level_comma(args: &str) -> Option<(&str, &str)> {
+        let mut depth = 0i32;
+        let mut in_string = false;
+        for (i, ch) in args.char_indices() {
+            match ch {
+                '"' => in_string = !in_string,
+                '(' | '[' | '{' if !in_string => depth += 1,
+                ')' | ']' | '}' if !in_string => depth -= 1,
+                ',' if !in_string && depth == 0 => return Some((args[..i].trim(), args[i + 1..].trim())),
+                _ => {}
             }
         }
+        None
+    }
 
-        let op_end = pos + op.len();
-        if op_end < chars.len() {
-            let next_char = chars[op_end];
-            if "=!<>+-*/".contains(next_char) {
-                return false;
+    // The replacement has to have the type the macro stood in for: `()` for a
+    // statement (`panic!(..);`), `Default::default()` for a block's tail or a
+    // match arm. Anywhere else, such as `let x = todo!();`, neither is sure
+    // to compile, so the macro is left alone.
+    fn panic_macro_replacement(before: &str, after: &str) -> Option<&'static str> {
+        let before = before.trim_end();
+        let after = after.trim_start();
+        let starts_block = before.is_empty() || before.ends_with('{');
+
+        if after.starts_with(';') && (starts_block || before.ends_with(';') || before.ends_with('}')) {
+            Some("()")
+        } else if (starts_block && (after.is_empty() || after.starts_with('}')))
+            || (before.ends_with("=>") && (after.is_empty() || after.starts_with(',') || after.starts_with('}')))
+        {
+            Some("Default::default()")
+        } else {
+            None
+        }
+    }
+
+    // Returns the byte offset just past the delimiter closing the macro
+    // arguments that start at `args_start`, if they close on this line.
+    fn find_macro_invocation_end(line: &str, args_start: usize) -> Option<usize> {
+        let rest = &line[args_start..];
+        let offset = rest.len() - rest.trim_start().len();
+        let (open, close) = match rest.trim_start().chars().next()? {
+            '(' => ('(', ')'),
+            '[' => ('[', ']'),
+            '{' => ('{', '}'),
+            _ => return None,
+        };
+
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        for (i, ch) in line[args_start + offset..].char_indices() {
+            if in_string {
+                match ch {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => in_string = false,
+                    _ => escaped = false,
+                }
+                continue;
             }
+            if ch == '"' {
+                in_string = true;
+            } else if ch == open {
+                depth += 1;
+            } else if ch == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(args_start + offset + i + ch.len_utf8());
+                }
+            }
+        }
+        None
+    }
+
+    // `pos` is a byte offset, like every position the line scanners use.
+    fn is_standalone_operator(&self, line: &str, pos: usize, op: &str) -> bool {
+        let is_operator = |c: char| "=!<>+-*/".contains(c);
+
+        if line[..pos].chars().next_back().is_some_and(is_operator) {
+            return false;
+        }
+        if line[pos + op.len()..].chars().next().is_some_and(is_operator) {
+            return false;
         }
 
         true
     }
 
     fn is_complete_word(&self, line: &str, pos: usize, word: &str) -> bool {
-        let chars: Vec<char> = line.chars().collect();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
 
-        if pos > 0 && (chars[pos - 1].is_alphanumeric() || chars[pos - 1] == '_') {
+        if line[..pos].chars().next_back().is_some_and(is_word) {
             return false;
         }
-
-        let word_end = pos + word.len();
-        if word_end < chars.len() && (chars[word_end].is_alphanumeric() || chars[word_end] == '_') {
+        if line[pos + word.len()..].chars().next().is_some_and(is_word) {
             return false;
         }
 
@@ -275,6 +942,17 @@ impl CodeAnalyzer {
         }
     }
 
+    // `% n` -> `% (n + 1)` and `% 1`: swapping `%` for `/` or `*` mostly
+    // yields trivially killed or divide-by-zero mutants, while a shifted
+    // divisor checks that the boundary is actually tested.
+    fn get_modulo_mutations(&self, original: &str, divisor: u64) -> Vec<String> {
+        let prefix = original.trim_end_matches(|c: char| c.is_ascii_digit() || c == '_');
+        vec![
+            format!("{}{}", prefix, divisor.saturating_add(1)),
+            format!("{}1", prefix),
+        ]
+    }
+
     fn get_relational_mutations(&self, operator: &str) -> Vec<String> {
         match operator {
             "==" => vec!["!=".to_string(), "<".to_string(), ">".to_string()],
@@ -296,25 +974,901 @@ impl CodeAnalyzer {
         }
     }
 
-    fn get_numeric_mutations(&self, number: &str) -> Vec<String> {
-        if let Ok(num) = number.parse::<i32>() {
-            vec![
-                (num + 1).to_string(),
-                (num - 1).to_string(),
-                (num * -1).to_string(),
-                "0".to_string(),
-                "1".to_string(),
-            ]
-        } else if let Ok(num) = number.parse::<f64>() {
+    /// The configured `numeric_mutations` of an integer literal whose type
+    /// suffix (possibly empty) is `suffix`.
+    fn get_numeric_mutations(&self, number: &str, suffix: &str) -> Vec<String> {
+        let Ok(num) = number.parse::<i128>() else {
+            return vec!["0".to_string(), "1".to_string()];
+        };
+        let bounds = integer_bounds(suffix).filter(|(min, max)| (*min..=*max).contains(&num));
+
+        let mut mutations: Vec<String> = Vec::new();
+        for kind in &self.config.numeric_mutations {
+            let mutation = match kind {
+                NumericMutationKind::PlusOne => num.checked_add(1),
+                NumericMutationKind::MinusOne => Some(num - 1),
+                NumericMutationKind::Negate => Some(-num),
+                NumericMutationKind::Zero => Some(0),
+                NumericMutationKind::One => Some(1),
+                NumericMutationKind::MaxValue => bounds.map(|(_, max)| max),
+                NumericMutationKind::MinValue => bounds.map(|(min, _)| min),
+                NumericMutationKind::FlipBits => bounds.map(|(min, max)| if min < 0 { !num } else { max ^ num }),
+            };
+            if let Some(mutation) = mutation.map(|m| m.to_string())
+                && !mutations.contains(&mutation)
+            {
+                mutations.push(mutation);
+            }
+        }
+        mutations
+    }
+}
+
+/// Smallest and largest value of the integer type named by a literal suffix,
+/// `i32` when there is none. `u128` doesn't fit and yields `None`.
+fn integer_bounds(suffix: &str) -> Option<(i128, i128)> {
+    let bounds = match suffix {
+        "" | "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i64" | "isize" => (i64::MIN as i128, i64::MAX as i128),
+        "i128" => (i128::MIN, i128::MAX),
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" | "usize" => (0, u64::MAX as i128),
+        _ => return None,
+    };
+    Some(bounds)
+}
+
+/// Tracks inline `mod name { ... }` nesting line by line so candidates can be
+/// attributed to the module they were found in.
+#[derive(Debug, Default)]
+struct ScopeTracker {
+    depth: usize,
+    modules: Vec<(String, usize)>,
+    functions: Vec<(String, usize)>,
+    pending_function: Option<String>,
+}
+
+impl ScopeTracker {
+    fn module_path(&self) -> String {
+        self.modules.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join("::")
+    }
+
+    /// Whether `line` sits inside, or declares, a function named in `names`.
+    /// Nested functions and closures inherit their enclosing function's match.
+    fn within_function(&self, line: &str, names: &[String]) -> bool {
+        let is_named = |name: &str| names.iter().any(|n| n == name);
+        self.functions.iter().any(|(name, _)| is_named(name))
+            || self.pending_function.as_deref().is_some_and(is_named)
+            || Self::function_name(line).as_deref().is_some_and(is_named)
+    }
+
+    fn advance(&mut self, line: &str) {
+        let mut pending_module = Self::inline_module_name(line);
+        if let Some(name) = Self::function_name(line) {
+            self.pending_function = Some(name);
+        }
+        let mut in_string = false;
+        let mut brackets = 0usize;
+        let mut chars = line.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' if in_string => {
+                    chars.next();
+                }
+                '"' => in_string = !in_string,
+                '/' if !in_string && chars.peek() == Some(&'/') => break,
+                '{' if !in_string => {
+                    self.depth += 1;
+                    if let Some(name) = pending_module.take() {
+                        self.modules.push((name, self.depth));
+                    }
+                    if let Some(name) = self.pending_function.take() {
+                        self.functions.push((name, self.depth));
+                    }
+                }
+                '[' if !in_string => brackets += 1,
+                ']' if !in_string => brackets = brackets.saturating_sub(1),
+                // A bodiless declaration, as in a trait; `[T; N]` types don't count.
+                ';' if !in_string && brackets == 0 => self.pending_function = None,
+                '}' if !in_string => {
+                    self.depth = self.depth.saturating_sub(1);
+                    while self.modules.last().is_some_and(|(_, depth)| *depth > self.depth) {
+                        self.modules.pop();
+                    }
+                    while self.functions.last().is_some_and(|(_, depth)| *depth > self.depth) {
+                        self.functions.pop();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Name from a `fn NAME(` or `fn NAME<` declaration on the line.
+    fn function_name(line: &str) -> Option<String> {
+        let code = line.split("//").next().unwrap_or(line);
+        code.match_indices("fn ").find_map(|(pos, _)| {
+            if code[..pos].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                return None;
+            }
+            let rest = code[pos + 3..].trim_start();
+            let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            let after = rest[name.len()..].trim_start();
+            (!name.is_empty() && (after.starts_with('(') || after.starts_with('<'))).then_some(name)
+        })
+    }
+
+    fn inline_module_name(line: &str) -> Option<String> {
+        let mut rest = line.trim_start();
+        if let Some(after_pub) = rest.strip_prefix("pub") {
+            rest = match after_pub.strip_prefix('(') {
+                Some(restricted) => restricted.split_once(')')?.1,
+                None => after_pub,
+            }
+            .trim_start();
+        }
+        let rest = rest.strip_prefix("mod ")?;
+        let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+        if name.is_empty() || !rest[name.len()..].trim_start().starts_with('{') {
+            return None;
+        }
+        Some(name)
+    }
+}
+
+/// Name of the innermost function enclosing `line` (1-based), using the same
+/// brace scan as `excluded_functions`.
+pub fn enclosing_function(source_code: &str, line: usize) -> Option<String> {
+    let mut scope = ScopeTracker::default();
+    for (index, text) in source_code.lines().enumerate() {
+        if index + 1 == line {
+            return ScopeTracker::function_name(text).or_else(|| scope.functions.last().map(|(name, _)| name.clone()));
+        }
+        scope.advance(text);
+    }
+    None
+}
+
+/// Line scanners work in byte offsets within `line`; this records the
+/// candidate's byte range in the whole source and turns its column into the
+/// character column that syntax-tree candidates, the mutator and reports use.
+fn locate_in_source(line: &str, line_start: usize, candidate: &mut MutationCandidate) {
+    let offset = candidate.column - 1;
+    candidate.byte_start = Some(line_start + offset);
+    candidate.byte_end = Some(line_start + offset + candidate.original_code.len());
+    candidate.column = line[..offset].chars().count() + 1;
+}
+
+/// Byte ranges of string and char literals (quotes included) and comments on a
+/// single line. A string left open at the end of the line is masked to the end,
+/// and `'a` without a closing quote is read as a lifetime.
+fn literal_and_comment_ranges(line: &str) -> Vec<Range<usize>> {
+    let bytes = line.as_bytes();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                ranges.push(i..line.len());
+                break;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = line[i + 2..].find("*/").map_or(line.len(), |pos| i + 2 + pos + 2);
+                ranges.push(i..end);
+                i = end;
+            }
+            b'"' => {
+                // Raw strings: `r"..."`, `r#"..."#`, optionally with a `b` prefix.
+                let hashes = bytes[..i].iter().rev().take_while(|&&b| b == b'#').count();
+                let is_raw = i > hashes
+                    && bytes[i - hashes - 1] == b'r'
+                    && (i == hashes + 1 || !is_ident_byte(bytes[i - hashes - 2]) || bytes[i - hashes - 2] == b'b');
+                let end = if is_raw {
+                    let closing = format!("\"{}", "#".repeat(hashes));
+                    line[i + 1..].find(&closing).map_or(line.len(), |pos| i + 1 + pos + closing.len())
+                } else {
+                    let mut j = i + 1;
+                    while j < bytes.len() && bytes[j] != b'"' {
+                        j += if bytes[j] == b'\\' { 2 } else { 1 };
+                    }
+                    (j + 1).min(line.len())
+                };
+                ranges.push(i..end);
+                i = end;
+            }
+            b'\'' => {
+                let end = if bytes.get(i + 1) == Some(&b'\\') {
+                    line[i + 2..].find('\'').map(|pos| i + 2 + pos + 1)
+                } else {
+                    line[i + 1..]
+                        .chars()
+                        .next()
+                        .map(|c| i + 1 + c.len_utf8())
+                        .filter(|&close| bytes.get(close) == Some(&b'\''))
+                        .map(|close| close + 1)
+                };
+                match end {
+                    Some(end) => {
+                        ranges.push(i..end);
+                        i = end;
+                    }
+                    None => i += 1,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    ranges
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Whether `before` ends in an operand that a binary `+`/`-` could follow:
+/// an identifier other than a keyword, or a closing parenthesis or bracket.
+fn follows_operand(before: &str) -> bool {
+    let before = before.trim_end();
+    if before.ends_with([')', ']']) {
+        return true;
+    }
+    let ident_start = before.bytes().rposition(|byte| !is_ident_byte(byte)).map_or(0, |pos| pos + 1);
+    let ident = &before[ident_start..];
+    !ident.is_empty()
+        && !ident.starts_with(|c: char| c.is_ascii_digit())
+        && !matches!(ident, "return" | "break" | "in" | "if" | "else" | "match" | "while")
+}
+
+/// Length and value of a decimal integer literal right after a `%`, including
+/// the whitespace before it. Suffixed, float or identifier operands yield `None`.
+fn literal_divisor(rest: &str) -> Option<(usize, u64)> {
+    let digits_start = rest.len() - rest.trim_start().len();
+    let digits_len = rest[digits_start..]
+        .find(|c: char| !c.is_ascii_digit() && c != '_')
+        .unwrap_or(rest.len() - digits_start);
+    let end = digits_start + digits_len;
+    if digits_len == 0 || rest[end..].starts_with(|c: char| c.is_alphanumeric() || c == '.') {
+        return None;
+    }
+    let divisor = rest[digits_start..end].replace('_', "").parse().ok()?;
+    Some((end, divisor))
+}
+
+/// Byte range of the condition of an `if` or `while` on this line, from the
+/// keyword to the opening brace, or to the end of the line when the condition
+/// continues on the next one.
+fn condition_range(line: &str) -> Option<Range<usize>> {
+    let start = ["if ", "while "]
+        .iter()
+        .filter_map(|keyword| {
+            line.match_indices(keyword)
+                .map(|(pos, _)| pos)
+                .find(|&pos| pos == 0 || !line.as_bytes()[pos - 1].is_ascii_alphanumeric() && line.as_bytes()[pos - 1] != b'_')
+                .map(|pos| pos + keyword.len())
+        })
+        .min()?;
+    let end = line[start..].find(" {").map_or(line.len(), |brace| start + brace);
+    Some(start..end)
+}
+
+/// Removes suggestions identical to the original code and drops candidates left
+/// with nothing to try, since those would only produce no-op runs.
+fn drop_degenerate_candidates(candidates: &mut Vec<MutationCandidate>) {
+    candidates.retain_mut(|candidate| {
+        let original = candidate.original_code.trim();
+        candidate
+            .suggested_mutations
+            .retain(|suggestion| suggestion.trim() != original);
+        if candidate.suggested_mutations.is_empty() {
+            debug!(
+                "Dropping {:?} candidate at line {}, col {}: no suggestion differs from '{}'",
+                candidate.mutation_type, candidate.line, candidate.column, candidate.original_code
+            );
+            return false;
+        }
+        true
+    });
+}
+
+/// Keeps each mutant once, so overlapping types such as a condition's `<` to
+/// `<=` (both a boundary flip and a relational swap), or `+= 1` to `-= 1`
+/// (both an assignment swap and a step flip), aren't tested and counted
+/// twice. The candidate with fewer suggestions, the more specific one, keeps
+/// the mutant.
+pub fn drop_duplicate_mutants(candidates: &mut Vec<MutationCandidate>) {
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.sort_by_key(|&index| candidates[index].suggested_mutations.len());
+    let mut seen = HashSet::new();
+    for index in order {
+        let candidate = &mut candidates[index];
+        let original = candidate.original_code.clone();
+        let (line, column) = (candidate.line, candidate.column);
+        let span = candidate
+            .byte_start
+            .zip(candidate.byte_end)
+            .filter(|(start, end)| end.checked_sub(*start) == Some(original.len()));
+        candidate.suggested_mutations.retain(|suggestion| {
+            let key = match span {
+                Some((start, end)) => {
+                    let (start, end, replacement) = minimal_edit(start, end, &original, suggestion);
+                    MutantIdentity::Edit(start, end, replacement)
+                }
+                None => MutantIdentity::Position(line, column, suggestion.trim().to_string()),
+            };
+            seen.insert(key)
+        });
+    }
+    candidates.retain(|candidate| !candidate.suggested_mutations.is_empty());
+}
+
+/// What a mutant changes: the edit it makes to the source when its byte
+/// range is known, and otherwise its position and replacement.
+#[derive(PartialEq, Eq, Hash)]
+enum MutantIdentity {
+    Edit(usize, usize, String),
+    Position(usize, usize, String),
+}
+
+/// Replacing `start..end` (holding `original`) with `replacement`, narrowed
+/// to the bytes that actually change. Two candidates producing the same
+/// source, like `+=` to `-=` and `+= 1` to `-= 1`, narrow to the same edit.
+fn minimal_edit(start: usize, end: usize, original: &str, replacement: &str) -> (usize, usize, String) {
+    let prefix: usize = original
+        .chars()
+        .zip(replacement.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let (original, replacement) = (&original[prefix..], &replacement[prefix..]);
+    let suffix: usize = original
+        .chars()
+        .rev()
+        .zip(replacement.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    (
+        start + prefix,
+        end - suffix,
+        replacement[..replacement.len() - suffix].to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyzer_for(mutation_types: Vec<MutationType>) -> CodeAnalyzer {
+        CodeAnalyzer::new(MutationTestConfig {
+            mutation_types,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_candidates_come_out_in_the_same_order_every_time() {
+        let source = "pub fn f(a: i32, b: i32) -> bool {\n    a + b > 1 && a - 1 < b || !(a == b)\n}\n";
+        let analyzer = CodeAnalyzer::new(MutationTestConfig::default());
+        let key = |candidates: Vec<MutationCandidate>| -> Vec<String> {
+            candidates.iter().map(|c| serde_json::to_string(c).unwrap()).collect()
+        };
+
+        let first = analyzer.find_mutation_candidates(source);
+        assert!(first.len() > 1);
+        assert!(first.windows(2).all(|w| (w[0].line, w[0].column, &w[0].mutation_type) <= (w[1].line, w[1].column, &w[1].mutation_type)));
+        assert_eq!(key(first), key(analyzer.find_mutation_candidates(source)));
+    }
+
+    #[test]
+    fn test_numeric_mutations_follow_the_configured_kinds() {
+        let analyzer = CodeAnalyzer::new(MutationTestConfig {
+            mutation_types: vec![MutationType::NumericLiteral],
+            numeric_mutations: vec![NumericMutationKind::Zero, NumericMutationKind::MaxValue],
+            ..Default::default()
+        });
+
+        let candidates = analyzer.find_mutation_candidates("    count * 7\n    mask & 3u8\n");
+
+        let suggestions: Vec<&[String]> = candidates.iter().map(|c| c.suggested_mutations.as_slice()).collect();
+        assert_eq!(suggestions, vec![&["0".to_string(), "2147483647".to_string()][..], &["0".to_string(), "255".to_string()][..]]);
+
+        let defaults = analyzer_for(vec![MutationType::NumericLiteral]).find_mutation_candidates("    count * 7\n");
+        assert_eq!(defaults[0].suggested_mutations, vec!["8", "6", "-7", "0", "1"]);
+    }
+
+    #[test]
+    fn test_candidates_on_uncovered_lines_are_dropped() {
+        use crate::mutation::coverage::CoverageMap;
+        use std::path::Path;
+
+        let coverage = CoverageMap::parse(
+            r#"{"files": [{"path": ["src", "lib.rs"], "traces": [
+                {"line": 2, "stats": {"Line": 4}},
+                {"line": 6, "stats": {"Line": 0}}
+            ]}]}"#,
+        )
+        .unwrap();
+        let source = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\npub fn sub(a: i32, b: i32) -> i32 {\n    a - b\n}\n";
+        let analyzer = analyzer_for(vec![MutationType::ArithmeticOperator])
+            .with_uncovered_lines(coverage.uncovered_lines(Path::new("src/lib.rs")));
+
+        let mut candidates = analyzer.find_mutation_candidates(source);
+        assert_eq!(candidates.iter().map(|c| c.line).collect::<Vec<_>>(), vec![2, 6]);
+        let uncovered = candidates[1].suggested_mutations.len();
+
+        assert_eq!(analyzer.drop_uncovered(&mut candidates), uncovered);
+        assert_eq!(candidates.iter().map(|c| c.line).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_degenerate_candidates_are_filtered_out() {
+        let candidate = |original: &str, suggestions: &[&str]| MutationCandidate {
+            line: 1,
+            column: 1,
+            original_code: original.to_string(),
+            mutation_type: MutationType::LogicalOperator,
+            suggested_mutations: suggestions.iter().map(|s| s.to_string()).collect(),
+            module_path: String::new(),
+            byte_start: None,
+            byte_end: None,
+        };
+        let mut candidates = vec![
+            candidate("&&", &[]),
+            candidate("&&", &["&&"]),
+            candidate("!", &[""]),
+            candidate("||", &["||", "&&"]),
+        ];
+
+        drop_degenerate_candidates(&mut candidates);
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].suggested_mutations, vec!["".to_string()]);
+        assert_eq!(candidates[1].suggested_mutations, vec!["&&".to_string()]);
+
+        let literals = analyzer_for(vec![MutationType::NumericLiteral]).find_mutation_candidates("    total += 1;\n");
+        assert_eq!(literals.len(), 1);
+        assert!(!literals[0].suggested_mutations.contains(&"1".to_string()));
+    }
+
+    #[test]
+    fn test_candidates_carry_module_path() {
+        let source = r#"
+pub fn top(a: i32) -> i32 {
+    a + 1
+}
+
+pub mod parser {
+    pub fn parse(a: i32) -> i32 {
+        a * 2
+    }
+
+    pub(crate) mod lexer {
+        pub fn lex(a: i32) -> i32 {
+            a - 3
+        }
+    }
+
+    pub fn after(a: i32) -> i32 {
+        a / 4
+    }
+}
+
+mod util {
+    pub fn helper(a: i32) -> i32 {
+        a % 5
+    }
+}
+"#;
+        let candidates = analyzer_for(vec![MutationType::ArithmeticOperator]).find_mutation_candidates(source);
+        let module_of = |op: &str| candidates.iter().find(|c| c.original_code == op).unwrap().module_path.clone();
+
+        assert_eq!(module_of("+"), "");
+        assert_eq!(module_of("*"), "parser");
+        assert_eq!(module_of("-"), "parser::lexer");
+        assert_eq!(module_of("/"), "parser");
+        assert_eq!(module_of("% 5"), "util");
+    }
+
+    #[test]
+    fn test_excluded_mutations_are_filtered_out() {
+        let analyzer = CodeAnalyzer::new(MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator, MutationType::NumericLiteral],
+            excluded_mutations: vec![MutationType::NumericLiteral],
+            ..Default::default()
+        });
+        let candidates = analyzer.find_mutation_candidates("pub fn next(a: i32) -> i32 {\n    a + 1\n}\n");
+
+        assert!(candidates.iter().any(|c| c.mutation_type == MutationType::ArithmeticOperator));
+        assert!(candidates.iter().all(|c| c.mutation_type != MutationType::NumericLiteral));
+    }
+
+    #[test]
+    fn test_overflow_methods_swap_family_and_plain_arithmetic() {
+        let source = "pub fn total(a: u8, b: u8) -> u8 {\n    a.saturating_add(b)\n}\n\npub fn next(a: u8) -> Option<u8> {\n    a.checked_sub(1)\n}\n";
+        let candidates = analyzer_for(vec![MutationType::OverflowMethod]).find_mutation_candidates(source);
+
+        let saturating = candidates.iter().find(|c| c.original_code == "a.saturating_add(b)").unwrap();
+        assert_eq!(saturating.mutation_type, MutationType::OverflowMethod);
+        assert_eq!(saturating.suggested_mutations, vec!["a.wrapping_add(b)", "(a + b)"]);
+        assert_eq!((saturating.line, saturating.column), (2, 5));
+
+        let checked = candidates.iter().find(|c| c.original_code == "a.checked_sub(1)").unwrap();
+        assert_eq!(checked.suggested_mutations, vec!["Some(a.saturating_sub(1))", "Some(a.wrapping_sub(1))", "Some(a - 1)"]);
+
+        let mutated = crate::mutation::mutators::CodeMutator::new()
+            .apply_mutation(source, saturating, "(a + b)")
+            .unwrap();
+        assert!(mutated.contains("    (a + b)\n"));
+    }
+
+    #[test]
+    fn test_filter_closures_get_operator_and_predicate_candidates() {
+        let source = "pub fn positives(v: &[i32]) -> usize {\n    v.iter().filter(|x| **x > 0).count()\n}\n";
+        let candidates = analyzer_for(vec![MutationType::RelationalOperator, MutationType::ClosurePredicate])
+            .find_mutation_candidates(source);
+
+        let relational = candidates.iter().find(|c| c.mutation_type == MutationType::RelationalOperator).unwrap();
+        assert_eq!((relational.original_code.as_str(), relational.line, relational.column), (">", 2, 29));
+
+        let predicate = candidates.iter().find(|c| c.mutation_type == MutationType::ClosurePredicate).unwrap();
+        assert_eq!(predicate.original_code, "**x > 0");
+        assert_eq!((predicate.line, predicate.column), (2, 25));
+        assert_eq!(predicate.suggested_mutations, vec!["true", "false"]);
+
+        let mutated = crate::mutation::mutators::CodeMutator::new()
+            .apply_mutation(source, predicate, "false")
+            .unwrap();
+        assert!(mutated.contains(".filter(|x| false).count()"));
+    }
+
+    #[test]
+    fn test_excluded_functions_are_skipped() {
+        let source = r#"
+pub fn hot_path(a: i32, b: i32) -> i32 {
+    if a > b {
+        return a - b;
+    }
+    a * b
+}
+
+pub fn checked(a: i32, b: i32) -> i32 {
+    a + b
+}
+"#;
+        let analyzer = CodeAnalyzer::new(MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator, MutationType::RelationalOperator],
+            excluded_functions: vec!["hot_path".to_string()],
+            ..Default::default()
+        });
+        let candidates = analyzer.find_mutation_candidates(source);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].original_code, "+");
+        assert_eq!(candidates[0].line, 10);
+    }
+
+    #[test]
+    fn test_modulo_by_literal_shifts_the_divisor() {
+        let source = "pub fn bucket(x: u32, n: u32) -> u32 {\n    x % 3 + x % n\n}\n";
+        let candidates = analyzer_for(vec![MutationType::ArithmeticOperator]).find_mutation_candidates(source);
+
+        let literal = candidates.iter().find(|c| c.original_code == "% 3").unwrap();
+        assert_eq!(literal.suggested_mutations, vec!["% 4", "% 1"]);
+        assert_eq!(literal.column, 7);
+        let start = source.find("% 3").unwrap();
+        assert_eq!((literal.byte_start, literal.byte_end), (Some(start), Some(start + 3)));
+
+        let variable = candidates.iter().find(|c| c.original_code == "%").unwrap();
+        assert_eq!(variable.suggested_mutations, vec!["/", "*"]);
+
+        let mutated = crate::mutation::mutators::CodeMutator::new().apply_mutation(source, literal, "% 1").unwrap();
+        assert!(mutated.contains("x % 1 + x % n"));
+    }
+
+    #[test]
+    fn test_comparisons_in_partial_eq_impl_are_skipped() {
+        let source = r#"
+pub struct Version {
+    major: u32,
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.major == other.major
+    }
+}
+
+impl Version {
+    pub fn is_newer(&self, other: &Version) -> bool {
+        self.major > other.major
+    }
+}
+"#;
+        let mut analyzer = analyzer_for(vec![MutationType::RelationalOperator]);
+        let lines = |analyzer: &CodeAnalyzer| {
+            analyzer.find_mutation_candidates(source).iter().map(|c| c.line).collect::<Vec<_>>()
+        };
+        assert!(lines(&analyzer).contains(&8));
+
+        analyzer.config.skip_trait_impls = true;
+        let remaining = lines(&analyzer);
+        assert!(!remaining.contains(&8));
+        assert!(remaining.contains(&14));
+    }
+
+    #[test]
+    fn test_unreachable_arm_yields_replacement_candidate() {
+        let source = r#"
+pub fn sign(n: i32) -> i32 {
+    match n.signum() {
+        -1 | 0 | 1 => n.signum(),
+        _ => unreachable!("signum is always -1, 0 or 1"),
+    }
+}
+"#;
+        let candidates = analyzer_for(vec![MutationType::PanicMacro]).find_mutation_candidates(source);
+
+        assert_eq!(candidates.len(), 1);
+        let candidate = &candidates[0];
+        assert_eq!(candidate.line, 5);
+        assert_eq!(candidate.original_code, r#"unreachable!("signum is always -1, 0 or 1")"#);
+        assert_eq!(candidate.suggested_mutations, vec!["Default::default()".to_string()]);
+    }
+
+    #[test]
+    fn test_unwrap_or_default_argument_is_mutated() {
+        let source = "pub fn count(opt: Option<i32>) -> i32 {\n    opt.unwrap_or(0)\n}\n";
+        let candidates = analyzer_for(vec![MutationType::DefaultValue]).find_mutation_candidates(source);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].mutation_type, MutationType::DefaultValue);
+        assert_eq!(candidates[0].original_code, "unwrap_or(0)");
+        assert_eq!(candidates[0].column, 9);
+        assert_eq!(candidates[0].suggested_mutations, vec!["unwrap_or(1)".to_string()]);
+
+        let source = "pub fn f(a: Option<String>, b: Option<u8>) -> usize {\n    a.unwrap_or_default().len() + b.map_or(0, |n| n as usize)\n}\n";
+        let candidates = analyzer_for(vec![MutationType::DefaultValue]).find_mutation_candidates(source);
+        let mutated: Vec<&str> = candidates.iter().map(|c| c.suggested_mutations[0].as_str()).collect();
+        assert!(mutated[0].starts_with("unwrap_or_else(||"));
+        assert_eq!(mutated[1], "map_or(0, |_| 0)");
+
+        // A non-literal default would be moved twice by `|_| fallback`.
+        let source = "pub fn f(a: Option<u8>, fallback: String) -> String {\n    a.map_or(fallback, |n| n.to_string())\n}\n";
+        assert!(analyzer_for(vec![MutationType::DefaultValue]).find_mutation_candidates(source).is_empty());
+
+        let source = "pub fn f(opt: Option<i64>) -> i64 {\n    opt.unwrap_or(9223372036854775807)\n}\n";
+        let candidates = analyzer_for(vec![MutationType::DefaultValue]).find_mutation_candidates(source);
+        assert_eq!(candidates[0].suggested_mutations, vec!["unwrap_or(0)".to_string()]);
+    }
+
+    #[test]
+    fn test_skip_reasons() {
+        let analyzer = analyzer_for(vec![MutationType::ArithmeticOperator]);
+
+        assert_eq!(analyzer.should_skip_line("    // a + b"), Some(SkipReason::Comment));
+        assert_eq!(analyzer.should_skip_line("    a + b // mutation-ignore"), Some(SkipReason::MutationIgnore));
+        assert_eq!(analyzer.should_skip_line("   "), Some(SkipReason::Empty));
+        assert_eq!(analyzer.should_skip_line("#[test]"), Some(SkipReason::ExcludedPattern));
+        assert_eq!(analyzer.should_skip_line("    a + b"), None);
+        assert_eq!(SkipReason::Comment.to_string(), "comment");
+    }
+
+    #[test]
+    fn test_regex_excluded_pattern_skips_matching_lines() {
+        let analyzer = CodeAnalyzer::new(MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator],
+            excluded_patterns: vec![r"regex:^\s*log_\w+!".to_string(), "regex:(".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(analyzer.should_skip_line("    log_debug!(\"{}\", a + b);"), Some(SkipReason::ExcludedPattern));
+        assert_eq!(analyzer.should_skip_line("    dialog_debug(a + b);"), None);
+        assert_eq!(analyzer.should_skip_line("    regex:(a + b)"), None);
+        let candidates = analyzer.find_mutation_candidates("fn f(a: i32, b: i32) -> i32 {\n    log_info!(a + b);\n    a - b\n}\n");
+        assert!(candidates.iter().all(|candidate| candidate.line == 3));
+    }
+
+    #[test]
+    fn test_if_comparison_gets_boundary_flip() {
+        let analyzer = analyzer_for(vec![MutationType::ConditionalBoundary]);
+        let source = "fn f(n: i32) -> bool {\n    if n < 10 {\n        return true;\n    }\n    while n >= 0 && Vec::<u8>::new().is_empty() {}\n    n > 3\n}\n";
+
+        let candidates = analyzer.find_mutation_candidates(source);
+
+        let found: Vec<(usize, usize, &str, &str)> = candidates
+            .iter()
+            .map(|c| (c.line, c.column, c.original_code.as_str(), c.suggested_mutations[0].as_str()))
+            .collect();
+        assert_eq!(found, vec![(2, 10, "<", "<="), (5, 13, ">=", ">")]);
+        assert!(candidates.iter().all(|c| c.mutation_type == MutationType::ConditionalBoundary));
+
+        let mutated = crate::mutation::mutators::CodeMutator::new().apply_mutation(source, &candidates[0], "<=").unwrap();
+        assert!(mutated.contains("    if n <= 10 {\n"));
+
+        let analyzer = analyzer_for(vec![MutationType::RelationalOperator, MutationType::ConditionalBoundary]);
+        let candidates = analyzer.find_mutation_candidates(source);
+        let on_line_2: Vec<(&MutationType, &[String])> = candidates
+            .iter()
+            .filter(|c| c.line == 2)
+            .map(|c| (&c.mutation_type, c.suggested_mutations.as_slice()))
+            .collect();
+        assert_eq!(
+            on_line_2,
             vec![
-                (num + 1.0).to_string(),
-                (num - 1.0).to_string(),
-                (num * -1.0).to_string(),
-                "0.0".to_string(),
-                "1.0".to_string(),
+                (&MutationType::RelationalOperator, &[">".to_string(), "==".to_string()][..]),
+                (&MutationType::ConditionalBoundary, &["<=".to_string()][..]),
             ]
-        } else {
-            vec!["0".to_string(), "1".to_string()]
+        );
+
+        // With the boundary flips excluded, the relational swap keeps `<=`.
+        let analyzer = CodeAnalyzer::new(MutationTestConfig {
+            mutation_types: vec![MutationType::RelationalOperator, MutationType::ConditionalBoundary],
+            excluded_mutations: vec![MutationType::ConditionalBoundary],
+            ..Default::default()
+        });
+        let candidates = analyzer.find_mutation_candidates(source);
+        let on_line_2: Vec<(&MutationType, &[String])> = candidates
+            .iter()
+            .filter(|c| c.line == 2)
+            .map(|c| (&c.mutation_type, c.suggested_mutations.as_slice()))
+            .collect();
+        assert_eq!(
+            on_line_2,
+            vec![(&MutationType::RelationalOperator, &["<=".to_string(), ">".to_string(), "==".to_string()][..])]
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment_is_swapped() {
+        let analyzer = analyzer_for(vec![MutationType::AssignmentOperator]);
+        let source = "fn f(mut x: i32) -> i32 {\n    x += 1;\n    if x == 2 || x <= 3 || x != 4 { x <<= 1; }\n    x\n}\n";
+
+        let candidates = analyzer.find_mutation_candidates(source);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!((candidates[0].line, candidates[0].column), (2, 7));
+        assert_eq!(candidates[0].original_code, "+=");
+        assert_eq!(candidates[0].suggested_mutations, vec!["-=".to_string(), "*=".to_string()]);
+
+        let mutated = crate::mutation::mutators::CodeMutator::new().apply_mutation(source, &candidates[0], "-=").unwrap();
+        assert!(mutated.contains("    x -= 1;\n"));
+    }
+
+    #[test]
+    fn test_steps_of_one_change_direction() {
+        let analyzer = analyzer_for(vec![MutationType::IncrementDecrement]);
+        let source = "fn f(mut i: usize, n: usize) -> usize {\n    i += 1;\n    if i > 10 { return -1 + n; }\n    n - 1 + i + 10 - 1.5\n}\n";
+        let mutator = crate::mutation::mutators::CodeMutator::new();
+
+        let candidates = analyzer.find_mutation_candidates(source);
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!((candidates[0].line, candidates[0].original_code.as_str()), (2, "+= 1"));
+        assert_eq!(candidates[0].suggested_mutations, vec!["-= 1".to_string()]);
+        let mutated = mutator.apply_mutation(source, &candidates[0], "-= 1").unwrap();
+        assert!(mutated.contains("    i -= 1;\n"));
+
+        assert_eq!((candidates[1].line, candidates[1].original_code.as_str()), (4, "- 1"));
+        let mutated = mutator.apply_mutation(source, &candidates[1], "+ 1").unwrap();
+        assert!(mutated.contains("    n + 1 + i + 10 - 1.5\n"));
+    }
+
+    #[test]
+    fn test_overlapping_operator_and_step_mutants_are_tested_once() {
+        let analyzer = analyzer_for(vec![
+            MutationType::ArithmeticOperator,
+            MutationType::AssignmentOperator,
+            MutationType::IncrementDecrement,
+        ]);
+        let source = "fn f(mut x: usize, n: usize) -> usize {\n    x += 1;\n    x * (n - 1)\n}\n";
+        let mutator = &crate::mutation::mutators::CodeMutator::new();
+
+        let candidates = analyzer.find_mutation_candidates(source);
+        let mutants: Vec<String> = candidates
+            .iter()
+            .flat_map(|c| c.suggested_mutations.iter().map(move |s| mutator.apply_mutation(source, c, s).unwrap()))
+            .collect();
+
+        let unique: HashSet<&String> = mutants.iter().collect();
+        assert_eq!(unique.len(), mutants.len(), "{:#?}", candidates);
+        assert_eq!(mutants.iter().filter(|m| m.contains("    x -= 1;\n")).count(), 1);
+        assert_eq!(mutants.iter().filter(|m| m.contains("x * (n + 1)")).count(), 1);
+    }
+
+    #[test]
+    fn test_panic_guard_is_deleted() {
+        let source = "    if divisor == 0 { panic!(\"division by zero\"); }";
+        let candidates = analyzer_for(vec![MutationType::PanicMacro]).find_mutation_candidates(source);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].original_code, "panic!(\"division by zero\")");
+        assert_eq!(candidates[0].suggested_mutations, vec!["()".to_string()]);
+    }
+
+    #[test]
+    fn test_panic_macro_replacement_follows_its_position() {
+        let analyzer = analyzer_for(vec![MutationType::PanicMacro]);
+        let replacements = |line: &str| -> Vec<String> {
+            analyzer
+                .find_mutation_candidates(line)
+                .into_iter()
+                .flat_map(|c| c.suggested_mutations)
+                .collect()
+        };
+
+        assert_eq!(replacements("    todo!();"), vec!["()"]);
+        assert_eq!(replacements("    panic!(\"no value\")"), vec!["Default::default()"]);
+        assert_eq!(replacements("    None => panic!(\"no value\"),"), vec!["Default::default()"]);
+        assert_eq!(replacements("    } else { unimplemented!() }"), vec!["Default::default()"]);
+        assert!(replacements("    let x: u8 = todo!();").is_empty());
+        assert!(replacements("    opt.unwrap_or_else(|| panic!(\"no value\"));").is_empty());
+    }
+
+    #[test]
+    fn test_operators_inside_literals_and_comments_are_ignored() {
+        let analyzer = analyzer_for(vec![
+            MutationType::ArithmeticOperator,
+            MutationType::RelationalOperator,
+            MutationType::LogicalOperator,
+        ]);
+
+        for line in [
+            "    call(\"a+b == c\");",
+            "    call('+', '\\'', '=');",
+            "    call(x); // a + b",
+            "    call(r#\"a \" + b\"#);",
+        ] {
+            let candidates = analyzer.find_mutation_candidates(line);
+            assert!(candidates.is_empty(), "{:?} produced {:?}", line, candidates);
         }
+
+        let candidates = analyzer.find_mutation_candidates("    total(\"+\") + 1 // +");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].column, 16);
+    }
+
+    #[test]
+    fn test_string_literals_are_mutated() {
+        let analyzer = analyzer_for(vec![MutationType::StringLiteral]);
+
+        let candidates = analyzer.find_mutation_candidates("    greet(\"hello\", b\"raw\") // \"note\"\n");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].original_code, "\"hello\"");
+        assert_eq!(candidates[0].column, 11);
+        assert_eq!(candidates[0].suggested_mutations, vec!["\"\"", "\"MUTATED\"", "\"olleh\""]);
+
+        let candidates = analyzer.find_mutation_candidates("    emit(r#\"say \"hi\"\"#);\n");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].original_code, "r#\"say \"hi\"\"#");
+        assert_eq!(candidates[0].suggested_mutations[2], "r#\"\"ih\" yas\"#");
+
+        let mutated = crate::mutation::mutators::CodeMutator::new()
+            .apply_mutation("    emit(r#\"say \"hi\"\"#);", &candidates[0], "\"MUTATED\"")
+            .unwrap();
+        assert_eq!(mutated, "    emit(\"MUTATED\");");
+    }
+
+    #[test]
+    fn test_columns_count_characters_after_tabs_and_multibyte_text() {
+        let analyzer = analyzer_for(vec![MutationType::ArithmeticOperator]);
+        let source = "fn f(a: u8) -> u8 {\n\tg(\"héllo\") + a\n}\n";
+
+        let candidates = analyzer.find_mutation_candidates(source);
+        assert_eq!(candidates.len(), 1);
+        let plus = &candidates[0];
+        assert_eq!((plus.line, plus.column), (2, 13));
+        assert_eq!(&source[plus.byte_start.unwrap()..plus.byte_end.unwrap()], "+");
+
+        // The line/column path, used when the byte range is missing, agrees.
+        let by_column = MutationCandidate { byte_start: None, byte_end: None, ..plus.clone() };
+        let mutated = crate::mutation::mutators::CodeMutator::new().apply_mutation(source, &by_column, "-").unwrap();
+        assert!(mutated.contains("\tg(\"héllo\") - a"), "{}", mutated);
     }
 }
@@ -1,12 +1,18 @@
 #![allow(dead_code)]
 
+use crate::mutation::error::MutationError;
 use crate::mutation::types::{MutationCandidate, MutationType};
+use std::fmt;
 use std::str::FromStr;
-use syn::{parse_file, visit_mut::VisitMut, Expr, ExprLit, Lit, ExprIf, ExprBinary, BinOp, UnOp, ExprUnary};
+use std::ops::{Range, RangeInclusive};
+use syn::spanned::Spanned;
+use syn::{parse_file, visit_mut::VisitMut, Expr, ExprLit, Lit, ExprIf, ExprBinary, BinOp, UnOp, ExprUnary, Item, Member};
+use syn::{Attribute, Block, Stmt};
 use quote::ToTokens;
 use tracing::{debug};
 
 #[allow(dead_code)]
+#[derive(Default)]
 pub struct AstMutator;
 
 #[allow(dead_code)]
@@ -15,9 +21,8 @@ impl AstMutator {
         Self
     }
 
-    pub fn find_ast_mutations(&self, source_code: &str) -> Result<Vec<MutationCandidate>, String> {
-        let ast = parse_file(source_code)
-            .map_err(|e| format!("Failed to parse code as Rust AST: {}", e))?;
+    pub fn find_ast_mutations(&self, source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+        let ast = parse_file(source_code)?;
 
         let mut visitor = MutationVisitor::new();
         visitor.visit_file_mut(&mut ast.clone());
@@ -30,20 +35,22 @@ impl AstMutator {
         source_code: &str,
         candidate: &MutationCandidate,
         mutation: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, MutationError> {
         // Parse the source code into a syntax tree
-        let mut ast = parse_file(source_code)
-            .map_err(|e| format!("Failed to parse code as Rust AST: {}", e))?;
+        let mut ast = parse_file(source_code)?;
 
         // Apply the mutation to the AST
         let mut mutator = AstMutationApplier::new(candidate, mutation);
         mutator.visit_file_mut(&mut ast);
 
+        if let Some(error) = mutator.error {
+            return Err(error);
+        }
         if !mutator.mutation_applied {
-            return Err(format!(
-                "Failed to apply AST mutation at line {}, column {}",
-                candidate.line, candidate.column
-            ));
+            return Err(MutationError::NotApplied {
+                line: candidate.line,
+                column: candidate.column,
+            });
         }
 
         // Convert the modified AST back to source code
@@ -66,7 +73,7 @@ impl MutationVisitor {
         }
     }
     
-    fn add_candidate(&mut self, line: usize, column: usize, original_code: String, 
+    fn add_candidate(&mut self, line: usize, column: usize, bytes: Range<usize>, original_code: String, 
                     mutation_type: MutationType, suggested_mutations: Vec<String>) {
         self.candidates.push(MutationCandidate {
             line,
@@ -74,15 +81,907 @@ impl MutationVisitor {
             original_code,
             mutation_type,
             suggested_mutations,
+            module_path: String::new(),
+            byte_start: Some(bytes.start),
+            byte_end: Some(bytes.end),
         });
     }
     
-    fn get_location(&self, expr: &impl ToTokens) -> Option<(usize, usize)> {
-        let _tokens = expr.to_token_stream();
-        // This is a simplified implementation. In a real scenario, 
-        // you would use proc_macro2::Span information to get accurate line/column
-        // For now, we'll return a placeholder
-        Some((1, 1))
+    fn get_location(&self, node: &impl Spanned) -> Option<(usize, usize, Range<usize>)> {
+        let (line, column) = span_location(node)?;
+        Some((line, column, node.span().byte_range()))
+    }
+}
+
+/// 1-based line and column where `node` starts, matching `MutationCandidate`.
+fn span_location(node: &impl Spanned) -> Option<(usize, usize)> {
+    let start = node.span().start();
+    (start.line > 0).then_some((start.line, start.column + 1))
+}
+
+/// Line ranges (1-based, inclusive) of `impl Trait for T` blocks whose trait
+/// name is in `traits`, including impls nested in inline modules.
+pub fn trait_impl_line_ranges(source_code: &str, traits: &[String]) -> Result<Vec<RangeInclusive<usize>>, MutationError> {
+    let file = parse_file(source_code)?;
+    let mut ranges = Vec::new();
+    collect_trait_impls(&file.items, traits, &mut ranges);
+    Ok(ranges)
+}
+
+fn collect_trait_impls(items: &[Item], traits: &[String], ranges: &mut Vec<RangeInclusive<usize>>) {
+    for item in items {
+        match item {
+            Item::Impl(item_impl) => {
+                let implements_skipped = item_impl
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .is_some_and(|segment| traits.iter().any(|t| segment.ident == t));
+                if implements_skipped {
+                    let span = item_impl.span();
+                    ranges.push(span.start().line..=span.end().line);
+                }
+            }
+            Item::Mod(item_mod) => {
+                if let Some((_, nested)) = &item_mod.content {
+                    collect_trait_impls(nested, traits, ranges);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `==`/`!=` comparisons whose left side (or right, if the left isn't one) is
+/// a tuple or struct literal, with suggestions swapping two adjacent fields.
+/// Only fields of the same apparent kind are swapped so the mutant still
+/// type-checks, and only sides written on a single line are considered.
+pub fn field_swap_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = FieldSwapVisitor { lines: source_code.lines().collect(), candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+struct FieldSwapVisitor<'a> {
+    lines: Vec<&'a str>,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl FieldSwapVisitor<'_> {
+    fn source_text(&self, node: &impl Spanned) -> Option<String> {
+        single_line_text(&self.lines, node)
+    }
+
+    fn swapped_sides(&self, side: &Expr) -> Vec<String> {
+        let mut swaps = Vec::new();
+        match side {
+            Expr::Tuple(tuple) => {
+                let elems: Option<Vec<String>> = tuple.elems.iter().map(|e| self.source_text(e)).collect();
+                let Some(elems) = elems else { return swaps };
+                for i in 1..elems.len() {
+                    if elems[i - 1] != elems[i] && expr_kind(&tuple.elems[i - 1]) == expr_kind(&tuple.elems[i]) {
+                        let mut swapped = elems.clone();
+                        swapped.swap(i - 1, i);
+                        swaps.push(format!("({})", swapped.join(", ")));
+                    }
+                }
+            }
+            Expr::Struct(literal) => {
+                let fields: Vec<&syn::FieldValue> = literal.fields.iter().collect();
+                let values: Option<Vec<String>> = fields.iter().map(|f| self.source_text(&f.expr)).collect();
+                let (Some(values), Some(path)) = (values, self.source_text(&literal.path)) else {
+                    return swaps;
+                };
+                let rest = match &literal.rest {
+                    Some(rest) => match self.source_text(rest) {
+                        Some(rest) => format!(", ..{}", rest),
+                        None => return swaps,
+                    },
+                    None => String::new(),
+                };
+                for i in 1..fields.len() {
+                    if values[i - 1] != values[i] && expr_kind(&fields[i - 1].expr) == expr_kind(&fields[i].expr) {
+                        let mut swapped = values.clone();
+                        swapped.swap(i - 1, i);
+                        let body: Vec<String> = fields
+                            .iter()
+                            .zip(&swapped)
+                            .map(|(field, value)| format!("{}: {}", member_name(&field.member), value))
+                            .collect();
+                        swaps.push(format!("{} {{ {}{} }}", path, body.join(", "), rest));
+                    }
+                }
+            }
+            _ => {}
+        }
+        swaps
+    }
+}
+
+impl VisitMut for FieldSwapVisitor<'_> {
+    fn visit_expr_binary_mut(&mut self, node: &mut ExprBinary) {
+        if matches!(node.op, BinOp::Eq(_) | BinOp::Ne(_)) {
+            for side in [&*node.left, &*node.right] {
+                let swaps = self.swapped_sides(side);
+                if swaps.is_empty() {
+                    continue;
+                }
+                if let Some(original_code) = self.source_text(side) {
+                    let start = side.span().start();
+                    self.candidates.push(MutationCandidate {
+                        line: start.line,
+                        column: start.column + 1,
+                        original_code,
+                        mutation_type: MutationType::FieldSwap,
+                        suggested_mutations: swaps,
+                        module_path: String::new(),
+                        byte_start: Some(side.span().byte_range().start),
+                        byte_end: Some(side.span().byte_range().end),
+                    });
+                    break;
+                }
+            }
+        }
+        syn::visit_mut::visit_expr_binary_mut(self, node);
+    }
+}
+
+// Source text of a node that starts and ends on the same line.
+fn single_line_text(lines: &[&str], node: &impl Spanned) -> Option<String> {
+    let span = node.span();
+    let (start, end) = (span.start(), span.end());
+    if start.line != end.line {
+        return None;
+    }
+    let line = lines.get(start.line.checked_sub(1)?)?;
+    Some(line.chars().skip(start.column).take(end.column - start.column).collect())
+}
+
+/// `saturating_*`, `checked_*` and `wrapping_*` calls for add/sub/mul, each
+/// with the other overflow behaviours and plain arithmetic as suggestions.
+/// `checked_*` returns an `Option`, so its replacements are wrapped in `Some`.
+pub fn overflow_method_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = OverflowMethodVisitor { lines: source_code.lines().collect(), candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+struct OverflowMethodVisitor<'a> {
+    lines: Vec<&'a str>,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl VisitMut for OverflowMethodVisitor<'_> {
+    fn visit_expr_method_call_mut(&mut self, node: &mut syn::ExprMethodCall) {
+        if let Some(candidate) = self.candidate(node) {
+            self.candidates.push(candidate);
+        }
+        syn::visit_mut::visit_expr_method_call_mut(self, node);
+    }
+}
+
+impl OverflowMethodVisitor<'_> {
+    fn candidate(&self, call: &syn::ExprMethodCall) -> Option<MutationCandidate> {
+        let method = call.method.to_string();
+        let (family, operation) = method.split_once('_')?;
+        let operator = match operation {
+            "add" => "+",
+            "sub" => "-",
+            "mul" => "*",
+            _ => return None,
+        };
+        if !matches!(family, "saturating" | "checked" | "wrapping") || call.args.len() != 1 {
+            return None;
+        }
+
+        let original_code = single_line_text(&self.lines, call)?;
+        let receiver = single_line_text(&self.lines, &*call.receiver)?;
+        let arg = &call.args[0];
+        let arg_text = single_line_text(&self.lines, arg)?;
+        let operand = match arg {
+            Expr::Path(_) | Expr::Lit(_) | Expr::Field(_) | Expr::MethodCall(_) | Expr::Call(_) | Expr::Paren(_) | Expr::Index(_) => arg_text.clone(),
+            _ => format!("({})", arg_text),
+        };
+
+        let mut replacements: Vec<String> = ["saturating", "wrapping"]
+            .iter()
+            .filter(|other| **other != family)
+            .map(|other| format!("{}.{}_{}({})", receiver, other, operation, arg_text))
+            .collect();
+        let suggested_mutations = if family == "checked" {
+            replacements.push(format!("{} {} {}", receiver, operator, operand));
+            replacements.into_iter().map(|r| format!("Some({})", r)).collect()
+        } else {
+            replacements.push(format!("({} {} {})", receiver, operator, operand));
+            replacements
+        };
+
+        let start = call.span().start();
+        let bytes = call.span().byte_range();
+        Some(MutationCandidate {
+            line: start.line,
+            column: start.column + 1,
+            original_code,
+            mutation_type: MutationType::OverflowMethod,
+            suggested_mutations,
+            module_path: String::new(),
+            byte_start: Some(bytes.start),
+            byte_end: Some(bytes.end),
+        })
+    }
+}
+
+/// Iterator adaptors whose closure argument is a `bool` predicate.
+const PREDICATE_ADAPTORS: &[&str] = &["filter", "take_while", "skip_while", "any", "all", "find", "position"];
+
+/// Bodies of predicate closures passed to [`PREDICATE_ADAPTORS`], e.g. the
+/// `*x > 0` in `.filter(|x| *x > 0)`, replaced by constant `true`/`false`.
+/// The candidate points at the closure body, which may span several lines.
+pub fn closure_predicate_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = ClosurePredicateVisitor { source: source_code, candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+struct ClosurePredicateVisitor<'a> {
+    source: &'a str,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl VisitMut for ClosurePredicateVisitor<'_> {
+    fn visit_expr_method_call_mut(&mut self, node: &mut syn::ExprMethodCall) {
+        let is_predicate = PREDICATE_ADAPTORS.iter().any(|name| node.method == name) && node.args.len() == 1;
+        if is_predicate
+            && let Some(Expr::Closure(closure)) = node.args.first()
+            && let Some((line, column)) = span_location(&*closure.body)
+        {
+            let bytes = closure.body.span().byte_range();
+            if let Some(body) = self.source.get(bytes.clone())
+                && body != "true"
+                && body != "false"
+            {
+                self.candidates.push(MutationCandidate {
+                    line,
+                    column,
+                    original_code: body.to_string(),
+                    mutation_type: MutationType::ClosurePredicate,
+                    suggested_mutations: vec!["true".to_string(), "false".to_string()],
+                    module_path: String::new(),
+                    byte_start: Some(bytes.start),
+                    byte_end: Some(bytes.end),
+                });
+            }
+        }
+        syn::visit_mut::visit_expr_method_call_mut(self, node);
+    }
+}
+
+/// Statements that could be deleted without touching the block's value:
+/// expression, macro and `let` statements other than the tail expression.
+/// Test functions and `#[cfg(test)]` modules are left alone.
+pub fn statement_deletion_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = StatementDeletionVisitor { source: source_code, candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+struct StatementDeletionVisitor<'a> {
+    source: &'a str,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl VisitMut for StatementDeletionVisitor<'_> {
+    fn visit_item_fn_mut(&mut self, node: &mut syn::ItemFn) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_fn_mut(self, node);
+        }
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut syn::ItemMod) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_mod_mut(self, node);
+        }
+    }
+
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        let body_len = match node.stmts.last() {
+            Some(Stmt::Expr(_, None)) => node.stmts.len() - 1,
+            _ => node.stmts.len(),
+        };
+        for (index, stmt) in node.stmts[..body_len].iter().enumerate() {
+            if !is_deletable(stmt, &node.stmts[index + 1..]) {
+                continue;
+            }
+            let bytes = stmt.span().byte_range();
+            if let Some((line, column)) = span_location(stmt)
+                && let Some(text) = self.source.get(bytes.clone())
+            {
+                self.candidates.push(MutationCandidate {
+                    line,
+                    column,
+                    original_code: text.to_string(),
+                    mutation_type: MutationType::StatementDeletion,
+                    suggested_mutations: vec![String::new()],
+                    module_path: String::new(),
+                    byte_start: Some(bytes.start),
+                    byte_end: Some(bytes.end),
+                });
+            }
+        }
+        syn::visit_mut::visit_block_mut(self, node);
+    }
+}
+
+/// Values returned with `return EXPR` or as a function's tail expression,
+/// replaced according to the declared return type: a flipped `bool`, zero
+/// for numbers, `None` for `Option` and `Ok(Default::default())` for
+/// `Result` (unless the value already is `Ok(())`). Other return types,
+/// closures and test code are left alone.
+pub fn return_value_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = ReturnValueVisitor { source: source_code, kind: None, candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReturnKind {
+    Bool,
+    Integer,
+    Float,
+    Option,
+    Result,
+}
+
+impl ReturnKind {
+    fn of(output: &syn::ReturnType) -> Option<Self> {
+        let syn::ReturnType::Type(_, ty) = output else {
+            return None;
+        };
+        let syn::Type::Path(path) = &**ty else {
+            return None;
+        };
+        let name = path.path.segments.last()?.ident.to_string();
+        match name.as_str() {
+            "bool" => Some(ReturnKind::Bool),
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => {
+                Some(ReturnKind::Integer)
+            }
+            "f32" | "f64" => Some(ReturnKind::Float),
+            "Option" => Some(ReturnKind::Option),
+            "Result" => Some(ReturnKind::Result),
+            _ => None,
+        }
+    }
+
+    fn replacement(self, original: &str) -> Option<String> {
+        let replacement = match (self, original) {
+            (ReturnKind::Bool, "true") => "false".to_string(),
+            (ReturnKind::Bool, "false") => "true".to_string(),
+            (ReturnKind::Bool, _) => format!("!({})", original),
+            (ReturnKind::Integer, _) => "0".to_string(),
+            (ReturnKind::Float, _) => "0.0".to_string(),
+            (ReturnKind::Option, _) => "None".to_string(),
+            // `Ok(Default::default())` is `Ok(())` again, so the mutant could never be killed.
+            (ReturnKind::Result, _) if original.split_whitespace().collect::<String>() == "Ok(())" => return None,
+            (ReturnKind::Result, _) => "Ok(Default::default())".to_string(),
+        };
+        (replacement != original).then_some(replacement)
+    }
+}
+
+struct ReturnValueVisitor<'a> {
+    source: &'a str,
+    kind: Option<ReturnKind>,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl ReturnValueVisitor<'_> {
+    fn visit_fn(&mut self, attrs: &[Attribute], sig: &syn::Signature, block: &mut Block) {
+        if is_test_only(attrs) {
+            return;
+        }
+        let outer = std::mem::replace(&mut self.kind, ReturnKind::of(&sig.output));
+        if let Some(Stmt::Expr(tail, None)) = block.stmts.last()
+            && !matches!(tail, Expr::Return(_))
+        {
+            self.add(tail);
+        }
+        self.visit_block_mut(block);
+        self.kind = outer;
+    }
+
+    fn add(&mut self, expr: &Expr) {
+        let Some(kind) = self.kind else {
+            return;
+        };
+        let bytes = expr.span().byte_range();
+        if let Some((line, column)) = span_location(expr)
+            && let Some(original) = self.source.get(bytes.clone())
+            && let Some(replacement) = kind.replacement(original)
+        {
+            self.candidates.push(MutationCandidate {
+                line,
+                column,
+                original_code: original.to_string(),
+                mutation_type: MutationType::ReturnValue,
+                suggested_mutations: vec![replacement],
+                module_path: String::new(),
+                byte_start: Some(bytes.start),
+                byte_end: Some(bytes.end),
+            });
+        }
+    }
+}
+
+impl VisitMut for ReturnValueVisitor<'_> {
+    fn visit_item_fn_mut(&mut self, node: &mut syn::ItemFn) {
+        self.visit_fn(&node.attrs, &node.sig, &mut node.block);
+    }
+
+    fn visit_impl_item_fn_mut(&mut self, node: &mut syn::ImplItemFn) {
+        self.visit_fn(&node.attrs, &node.sig, &mut node.block);
+    }
+
+    fn visit_trait_item_fn_mut(&mut self, node: &mut syn::TraitItemFn) {
+        if let Some(block) = &mut node.default {
+            self.visit_fn(&node.attrs, &node.sig, block);
+        }
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut syn::ItemMod) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_mod_mut(self, node);
+        }
+    }
+
+    fn visit_expr_return_mut(&mut self, node: &mut syn::ExprReturn) {
+        if let Some(expr) = &node.expr {
+            self.add(expr);
+        }
+        syn::visit_mut::visit_expr_return_mut(self, node);
+    }
+
+    // `return` inside a closure or async block leaves only that closure or
+    // block, whose type the signature says nothing about.
+    fn visit_expr_closure_mut(&mut self, node: &mut syn::ExprClosure) {
+        let outer = self.kind.take();
+        syn::visit_mut::visit_expr_closure_mut(self, node);
+        self.kind = outer;
+    }
+
+    fn visit_expr_async_mut(&mut self, node: &mut syn::ExprAsync) {
+        let outer = self.kind.take();
+        syn::visit_mut::visit_expr_async_mut(self, node);
+        self.kind = outer;
+    }
+}
+
+/// `.unwrap()` and `.expect(..)` calls, replaced by `.unwrap_or_default()`,
+/// and `?` operators, replaced by `.unwrap()`. The candidate covers just the
+/// method call or the `?`, so the receiver is left as written.
+pub fn optional_unwrap_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = OptionalUnwrapVisitor { source: source_code, closures: 0, candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+struct OptionalUnwrapVisitor<'a> {
+    source: &'a str,
+    /// Closures and async blocks around the current node; `?` inside them
+    /// returns from them rather than the function.
+    closures: usize,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl OptionalUnwrapVisitor<'_> {
+    fn add(&mut self, start: &impl Spanned, bytes: Range<usize>, replacement: &str) {
+        if let Some((line, column)) = span_location(start)
+            && let Some(original) = self.source.get(bytes.clone())
+        {
+            self.candidates.push(MutationCandidate {
+                line,
+                column,
+                original_code: original.to_string(),
+                mutation_type: MutationType::OptionalUnwrap,
+                suggested_mutations: vec![replacement.to_string()],
+                module_path: String::new(),
+                byte_start: Some(bytes.start),
+                byte_end: Some(bytes.end),
+            });
+        }
+    }
+}
+
+impl VisitMut for OptionalUnwrapVisitor<'_> {
+    fn visit_item_fn_mut(&mut self, node: &mut syn::ItemFn) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_fn_mut(self, node);
+        }
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut syn::ItemMod) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_mod_mut(self, node);
+        }
+    }
+
+    fn visit_expr_method_call_mut(&mut self, node: &mut syn::ExprMethodCall) {
+        let unwraps = (node.method == "unwrap" && node.args.is_empty()) || (node.method == "expect" && node.args.len() == 1);
+        if unwraps && node.turbofish.is_none() {
+            let bytes = node.method.span().byte_range().start..node.span().byte_range().end;
+            self.add(&node.method, bytes, "unwrap_or_default()");
+        }
+        syn::visit_mut::visit_expr_method_call_mut(self, node);
+    }
+
+    fn visit_expr_try_mut(&mut self, node: &mut syn::ExprTry) {
+        if self.closures == 0 {
+            self.add(&node.question_token, node.question_token.span().byte_range(), ".unwrap()");
+        }
+        syn::visit_mut::visit_expr_try_mut(self, node);
+    }
+
+    fn visit_expr_closure_mut(&mut self, node: &mut syn::ExprClosure) {
+        self.closures += 1;
+        syn::visit_mut::visit_expr_closure_mut(self, node);
+        self.closures -= 1;
+    }
+
+    fn visit_expr_async_mut(&mut self, node: &mut syn::ExprAsync) {
+        self.closures += 1;
+        syn::visit_mut::visit_expr_async_mut(self, node);
+        self.closures -= 1;
+    }
+}
+
+/// `break` and `continue` inside loops, each swapped for the other with the
+/// same label. `break` with a value, or out of a labeled block rather than a
+/// loop, has no `continue` counterpart and is skipped.
+pub fn break_continue_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = BreakContinueVisitor { source: source_code, targets: Vec::new(), candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+/// A loop or labeled block that `break` can leave.
+struct BreakTarget {
+    label: Option<String>,
+    is_loop: bool,
+}
+
+struct BreakContinueVisitor<'a> {
+    source: &'a str,
+    targets: Vec<BreakTarget>,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl BreakContinueVisitor<'_> {
+    fn within(&mut self, label: Option<&syn::Label>, is_loop: bool, visit: impl FnOnce(&mut Self)) {
+        self.targets.push(BreakTarget { label: label.map(|l| l.name.ident.to_string()), is_loop });
+        visit(self);
+        self.targets.pop();
+    }
+
+    // Closures, async blocks and nested fns can't break out of the loops
+    // around them.
+    fn isolated(&mut self, visit: impl FnOnce(&mut Self)) {
+        let outer = std::mem::take(&mut self.targets);
+        visit(self);
+        self.targets = outer;
+    }
+
+    fn leaves_loop(&self, label: Option<&syn::Lifetime>) -> bool {
+        let target = match label {
+            Some(label) => self.targets.iter().rev().find(|t| t.label.as_ref().is_some_and(|name| label.ident == name)),
+            None => self.targets.iter().rev().find(|t| t.is_loop),
+        };
+        target.is_some_and(|t| t.is_loop)
+    }
+
+    fn add(&mut self, node: &impl Spanned, replacement: &str, label: Option<&syn::Lifetime>) {
+        let bytes = node.span().byte_range();
+        if let Some((line, column)) = span_location(node)
+            && let Some(original) = self.source.get(bytes.clone())
+        {
+            let replacement = match label {
+                Some(label) => format!("{} {}", replacement, label),
+                None => replacement.to_string(),
+            };
+            self.candidates.push(MutationCandidate {
+                line,
+                column,
+                original_code: original.to_string(),
+                mutation_type: MutationType::BreakContinueReplacement,
+                suggested_mutations: vec![replacement],
+                module_path: String::new(),
+                byte_start: Some(bytes.start),
+                byte_end: Some(bytes.end),
+            });
+        }
+    }
+}
+
+impl VisitMut for BreakContinueVisitor<'_> {
+    fn visit_item_fn_mut(&mut self, node: &mut syn::ItemFn) {
+        if !is_test_only(&node.attrs) {
+            self.isolated(|v| syn::visit_mut::visit_item_fn_mut(v, node));
+        }
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut syn::ItemMod) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_mod_mut(self, node);
+        }
+    }
+
+    fn visit_expr_closure_mut(&mut self, node: &mut syn::ExprClosure) {
+        self.isolated(|v| syn::visit_mut::visit_expr_closure_mut(v, node));
+    }
+
+    fn visit_expr_async_mut(&mut self, node: &mut syn::ExprAsync) {
+        self.isolated(|v| syn::visit_mut::visit_expr_async_mut(v, node));
+    }
+
+    fn visit_expr_loop_mut(&mut self, node: &mut syn::ExprLoop) {
+        let label = node.label.clone();
+        self.within(label.as_ref(), true, |v| syn::visit_mut::visit_expr_loop_mut(v, node));
+    }
+
+    fn visit_expr_while_mut(&mut self, node: &mut syn::ExprWhile) {
+        let label = node.label.clone();
+        self.within(label.as_ref(), true, |v| syn::visit_mut::visit_expr_while_mut(v, node));
+    }
+
+    fn visit_expr_for_loop_mut(&mut self, node: &mut syn::ExprForLoop) {
+        let label = node.label.clone();
+        self.within(label.as_ref(), true, |v| syn::visit_mut::visit_expr_for_loop_mut(v, node));
+    }
+
+    fn visit_expr_block_mut(&mut self, node: &mut syn::ExprBlock) {
+        match node.label.clone() {
+            Some(label) => self.within(Some(&label), false, |v| syn::visit_mut::visit_expr_block_mut(v, node)),
+            None => syn::visit_mut::visit_expr_block_mut(self, node),
+        }
+    }
+
+    fn visit_expr_break_mut(&mut self, node: &mut syn::ExprBreak) {
+        if node.expr.is_none() && self.leaves_loop(node.label.as_ref()) {
+            self.add(node, "continue", node.label.as_ref());
+        }
+        syn::visit_mut::visit_expr_break_mut(self, node);
+    }
+
+    fn visit_expr_continue_mut(&mut self, node: &mut syn::ExprContinue) {
+        if self.leaves_loop(node.label.as_ref()) {
+            self.add(node, "break", node.label.as_ref());
+        }
+    }
+}
+
+/// `match` expressions, with adjacent arms' bodies swapped, arms that a
+/// catch-all arm below them would absorb deleted, and guards replaced by
+/// `false`. A body that names its own pattern's bindings stays put, since it
+/// can't compile under another pattern.
+pub fn switch_case_candidates(source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
+    let mut file = parse_file(source_code)?;
+    let mut visitor = SwitchCaseVisitor { source: source_code, candidates: Vec::new() };
+    visitor.visit_file_mut(&mut file);
+    Ok(visitor.candidates)
+}
+
+/// One rewrite of a `match`'s arms. Candidates carry these as their
+/// suggestions, with arms numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArmMutation {
+    Swap(usize, usize),
+    Delete(usize),
+    FalseGuard(usize),
+}
+
+impl fmt::Display for ArmMutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArmMutation::Swap(a, b) => write!(f, "swap arms {} and {}", a + 1, b + 1),
+            ArmMutation::Delete(arm) => write!(f, "delete arm {}", arm + 1),
+            ArmMutation::FalseGuard(arm) => write!(f, "replace guard of arm {} with false", arm + 1),
+        }
+    }
+}
+
+impl FromStr for ArmMutation {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let arm = |n: &str| n.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).ok_or(());
+        if let Some(rest) = s.strip_prefix("swap arms ") {
+            let (a, b) = rest.split_once(" and ").ok_or(())?;
+            Ok(ArmMutation::Swap(arm(a)?, arm(b)?))
+        } else if let Some(rest) = s.strip_prefix("delete arm ") {
+            Ok(ArmMutation::Delete(arm(rest)?))
+        } else if let Some(rest) = s.strip_prefix("replace guard of arm ").and_then(|r| r.strip_suffix(" with false")) {
+            Ok(ArmMutation::FalseGuard(arm(rest)?))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl ArmMutation {
+    fn apply(self, arms: &mut Vec<syn::Arm>) -> bool {
+        match self {
+            ArmMutation::Swap(a, b) if a < arms.len() && b < arms.len() && a != b => {
+                let body = arms[a].body.clone();
+                arms[a].body = arms[b].body.clone();
+                arms[b].body = body;
+                // A block body needs no comma, but the expression swapped in
+                // might.
+                arms[a].comma = Some(Default::default());
+                arms[b].comma = Some(Default::default());
+                true
+            }
+            ArmMutation::Delete(arm) if arm < arms.len() => {
+                arms.remove(arm);
+                true
+            }
+            ArmMutation::FalseGuard(arm) if arms.get(arm).is_some_and(|a| a.guard.is_some()) => {
+                let guard = arms[arm].guard.as_mut().unwrap();
+                *guard.1 = syn::parse_quote!(false);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// An unguarded `_` or plain binding matches whatever reaches it.
+fn is_catch_all(arm: &syn::Arm) -> bool {
+    arm.guard.is_none()
+        && match &arm.pat {
+            syn::Pat::Wild(_) => true,
+            syn::Pat::Ident(ident) => {
+                ident.subpat.is_none() && ident.ident.to_string().starts_with(|c: char| c.is_lowercase() || c == '_')
+            }
+            _ => false,
+        }
+}
+
+struct SwitchCaseVisitor<'a> {
+    source: &'a str,
+    candidates: Vec<MutationCandidate>,
+}
+
+impl SwitchCaseVisitor<'_> {
+    fn arm_mutations(arms: &[syn::Arm]) -> Vec<ArmMutation> {
+        let movable: Vec<bool> = arms
+            .iter()
+            .map(|arm| {
+                let mut names = BindingNames::default();
+                names.visit_pat_mut(&mut arm.pat.clone());
+                !mentions_any(arm.body.to_token_stream(), &names.0)
+            })
+            .collect();
+
+        let mut mutations = Vec::new();
+        for i in 0..arms.len().saturating_sub(1) {
+            let differ = arms[i].body.to_token_stream().to_string() != arms[i + 1].body.to_token_stream().to_string();
+            if differ && movable[i] && movable[i + 1] {
+                mutations.push(ArmMutation::Swap(i, i + 1));
+            }
+        }
+        for (i, arm) in arms.iter().enumerate() {
+            if !is_catch_all(arm) && arms[i + 1..].iter().any(is_catch_all) {
+                mutations.push(ArmMutation::Delete(i));
+            }
+            if arm.guard.is_some() {
+                mutations.push(ArmMutation::FalseGuard(i));
+            }
+        }
+        mutations
+    }
+}
+
+impl VisitMut for SwitchCaseVisitor<'_> {
+    fn visit_item_fn_mut(&mut self, node: &mut syn::ItemFn) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_fn_mut(self, node);
+        }
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut syn::ItemMod) {
+        if !is_test_only(&node.attrs) {
+            syn::visit_mut::visit_item_mod_mut(self, node);
+        }
+    }
+
+    fn visit_expr_match_mut(&mut self, node: &mut syn::ExprMatch) {
+        let mutations = Self::arm_mutations(&node.arms);
+        let bytes = node.match_token.span().byte_range().start..node.expr.span().byte_range().end;
+        if !mutations.is_empty()
+            && let Some((line, column)) = span_location(&node.match_token)
+            && let Some(original) = self.source.get(bytes.clone())
+        {
+            self.candidates.push(MutationCandidate {
+                line,
+                column,
+                original_code: original.to_string(),
+                mutation_type: MutationType::SwitchCase,
+                suggested_mutations: mutations.iter().map(ToString::to_string).collect(),
+                module_path: String::new(),
+                byte_start: Some(bytes.start),
+                byte_end: Some(bytes.end),
+            });
+        }
+        syn::visit_mut::visit_expr_match_mut(self, node);
+    }
+}
+
+fn is_test_only(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("test")
+            || attr.path().is_ident("cfg") && attr.parse_args::<syn::Ident>().is_ok_and(|arg| arg == "test")
+    })
+}
+
+// Control flow can't go without changing the block's type, and a binding
+// that a later statement names would only leave a mutant that can't compile.
+fn is_deletable(stmt: &Stmt, later: &[Stmt]) -> bool {
+    match stmt {
+        Stmt::Expr(Expr::Return(_) | Expr::Break(_) | Expr::Continue(_), _) | Stmt::Item(_) => false,
+        Stmt::Expr(..) | Stmt::Macro(_) => true,
+        Stmt::Local(local) => {
+            let mut names = BindingNames::default();
+            names.visit_pat_mut(&mut local.pat.clone());
+            !later.iter().any(|stmt| mentions_any(stmt.to_token_stream(), &names.0))
+        }
+    }
+}
+
+#[derive(Default)]
+struct BindingNames(Vec<String>);
+
+impl VisitMut for BindingNames {
+    fn visit_pat_ident_mut(&mut self, node: &mut syn::PatIdent) {
+        self.0.push(node.ident.to_string());
+        syn::visit_mut::visit_pat_ident_mut(self, node);
+    }
+}
+
+fn mentions_any(tokens: proc_macro2::TokenStream, names: &[String]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => names.iter().any(|name| ident == name),
+        proc_macro2::TokenTree::Group(group) => mentions_any(group.stream(), names),
+        _ => false,
+    })
+}
+
+// A coarse stand-in for the field's type: literals by literal kind, plain
+// paths together, anything else only with itself.
+fn expr_kind(expr: &Expr) -> String {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => format!("int{}", int.suffix()),
+            Lit::Float(float) => format!("float{}", float.suffix()),
+            Lit::Str(_) => "str".to_string(),
+            Lit::Bool(_) => "bool".to_string(),
+            Lit::Char(_) => "char".to_string(),
+            _ => "lit".to_string(),
+        },
+        Expr::Path(_) | Expr::Field(_) => "path".to_string(),
+        Expr::Reference(reference) => format!("&{}", expr_kind(&reference.expr)),
+        other => other.to_token_stream().to_string(),
+    }
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
     }
 }
 
@@ -92,25 +991,25 @@ impl VisitMut for MutationVisitor {
     fn visit_expr_lit_mut(&mut self, node: &mut ExprLit) {
         if let Lit::Int(ref lit_int) = node.lit {
             let value = lit_int.base10_parse::<i64>().ok();
-            if let Some(val) = value {
-                if let Some((line, col)) = self.get_location(&node) {
-                    let original = val.to_string();
-                    let mutations = vec![
-                        "0".to_string(),
-                        "1".to_string(),
-                        (-val).to_string(),
-                        (val + 1).to_string(),
-                        (val - 1).to_string(),
-                    ];
-                    self.add_candidate(line, col, original, MutationType::ConstantReplacement, mutations);
-                }
+            if let Some(val) = value
+                && let Some((line, col, bytes)) = self.get_location(&node)
+            {
+                let original = val.to_string();
+                let mutations = vec![
+                    "0".to_string(),
+                    "1".to_string(),
+                    (-val).to_string(),
+                    (val + 1).to_string(),
+                    (val - 1).to_string(),
+                ];
+                self.add_candidate(line, col, bytes, original, MutationType::ConstantReplacement, mutations);
             }
         } else if let Lit::Bool(ref lit_bool) = node.lit {
             let value = lit_bool.value;
-            if let Some((line, col)) = self.get_location(&node) {
+            if let Some((line, col, bytes)) = self.get_location(&node) {
                 let original = value.to_string();
                 let mutations = vec![(!value).to_string()];
-                self.add_candidate(line, col, original, MutationType::ConstantReplacement, mutations);
+                self.add_candidate(line, col, bytes, original, MutationType::ConstantReplacement, mutations);
             }
         }
         
@@ -120,31 +1019,31 @@ impl VisitMut for MutationVisitor {
     
     // Visit if statements for conditional boundary mutations
     fn visit_expr_if_mut(&mut self, node: &mut ExprIf) {
-        if let Expr::Binary(ref binary) = *node.cond {
-            if let Some((line, col)) = self.get_location(&binary) {
-                match binary.op {
-                    BinOp::Lt(_) => {
-                        let original = "<".to_string();
-                        let mutations = vec!["<=".to_string()];
-                        self.add_candidate(line, col, original, MutationType::ConditionalBoundary, mutations);
-                    }
-                    BinOp::Le(_) => {
-                        let original = "<=".to_string();
-                        let mutations = vec!["<".to_string()];
-                        self.add_candidate(line, col, original, MutationType::ConditionalBoundary, mutations);
-                    }
-                    BinOp::Gt(_) => {
-                        let original = ">".to_string();
-                        let mutations = vec![">=".to_string()];
-                        self.add_candidate(line, col, original, MutationType::ConditionalBoundary, mutations);
-                    }
-                    BinOp::Ge(_) => {
-                        let original = ">=".to_string();
-                        let mutations = vec![">".to_string()];
-                        self.add_candidate(line, col, original, MutationType::ConditionalBoundary, mutations);
-                    }
-                    _ => {}
+        if let Expr::Binary(ref binary) = *node.cond
+            && let Some((line, col, bytes)) = self.get_location(&binary.op)
+        {
+            match binary.op {
+                BinOp::Lt(_) => {
+                    let original = "<".to_string();
+                    let mutations = vec!["<=".to_string()];
+                    self.add_candidate(line, col, bytes, original, MutationType::ConditionalBoundary, mutations);
+                }
+                BinOp::Le(_) => {
+                    let original = "<=".to_string();
+                    let mutations = vec!["<".to_string()];
+                    self.add_candidate(line, col, bytes, original, MutationType::ConditionalBoundary, mutations);
+                }
+                BinOp::Gt(_) => {
+                    let original = ">".to_string();
+                    let mutations = vec![">=".to_string()];
+                    self.add_candidate(line, col, bytes, original, MutationType::ConditionalBoundary, mutations);
+                }
+                BinOp::Ge(_) => {
+                    let original = ">=".to_string();
+                    let mutations = vec![">".to_string()];
+                    self.add_candidate(line, col, bytes, original, MutationType::ConditionalBoundary, mutations);
                 }
+                _ => {}
             }
         }
         
@@ -154,73 +1053,73 @@ impl VisitMut for MutationVisitor {
     
     // Visit binary operations for operator mutations
     fn visit_expr_binary_mut(&mut self, node: &mut ExprBinary) {
-        if let Some((line, col)) = self.get_location(&node) {
+        if let Some((line, col, bytes)) = self.get_location(&node.op) {
             match node.op {
                 // Arithmetic operators
                 BinOp::Add(_) => {
-                    self.add_candidate(line, col, "+".to_string(), MutationType::ArithmeticOperator, 
+                    self.add_candidate(line, col, bytes, "+".to_string(), MutationType::ArithmeticOperator, 
                                       vec!["-".to_string(), "*".to_string()]);
                 }
                 BinOp::Sub(_) => {
-                    self.add_candidate(line, col, "-".to_string(), MutationType::ArithmeticOperator, 
+                    self.add_candidate(line, col, bytes, "-".to_string(), MutationType::ArithmeticOperator, 
                                       vec!["+".to_string(), "*".to_string()]);
                 }
                 BinOp::Mul(_) => {
-                    self.add_candidate(line, col, "*".to_string(), MutationType::ArithmeticOperator, 
+                    self.add_candidate(line, col, bytes, "*".to_string(), MutationType::ArithmeticOperator, 
                                       vec!["/".to_string(), "+".to_string()]);
                 }
                 BinOp::Div(_) => {
-                    self.add_candidate(line, col, "/".to_string(), MutationType::ArithmeticOperator, 
+                    self.add_candidate(line, col, bytes, "/".to_string(), MutationType::ArithmeticOperator, 
                                       vec!["*".to_string(), "%".to_string()]);
                 }
                 
                 // Logical operators
                 BinOp::And(_) => {
-                    self.add_candidate(line, col, "&&".to_string(), MutationType::LogicalOperator, 
+                    self.add_candidate(line, col, bytes, "&&".to_string(), MutationType::LogicalOperator, 
                                       vec!["||".to_string()]);
                 }
                 BinOp::Or(_) => {
-                    self.add_candidate(line, col, "||".to_string(), MutationType::LogicalOperator, 
+                    self.add_candidate(line, col, bytes, "||".to_string(), MutationType::LogicalOperator, 
                                       vec!["&&".to_string()]);
                 }
                 
                 // Bitwise operators
                 BinOp::BitAnd(_) => {
-                    self.add_candidate(line, col, "&".to_string(), MutationType::BitwiseOperator, 
+                    self.add_candidate(line, col, bytes, "&".to_string(), MutationType::BitwiseOperator, 
                                       vec!["|".to_string(), "^".to_string()]);
                 }
                 BinOp::BitOr(_) => {
-                    self.add_candidate(line, col, "|".to_string(), MutationType::BitwiseOperator, 
+                    self.add_candidate(line, col, bytes, "|".to_string(), MutationType::BitwiseOperator, 
                                       vec!["&".to_string(), "^".to_string()]);
                 }
                 BinOp::BitXor(_) => {
-                    self.add_candidate(line, col, "^".to_string(), MutationType::BitwiseOperator, 
+                    self.add_candidate(line, col, bytes, "^".to_string(), MutationType::BitwiseOperator, 
                                       vec!["&".to_string(), "|".to_string()]);
                 }
                 
                 // Relational operators
                 BinOp::Eq(_) => {
-                    self.add_candidate(line, col, "==".to_string(), MutationType::RelationalOperator, 
+                    self.add_candidate(line, col, bytes, "==".to_string(), MutationType::RelationalOperator, 
                                       vec!["!=".to_string(), "<".to_string(), ">".to_string()]);
                 }
                 BinOp::Ne(_) => {
-                    self.add_candidate(line, col, "!=".to_string(), MutationType::RelationalOperator, 
+                    self.add_candidate(line, col, bytes, "!=".to_string(), MutationType::RelationalOperator, 
                                       vec!["==".to_string()]);
                 }
                 BinOp::Lt(_) => {
-                    self.add_candidate(line, col, "<".to_string(), MutationType::RelationalOperator, 
+                    self.add_candidate(line, col, bytes, "<".to_string(), MutationType::RelationalOperator, 
                                       vec!["<=".to_string(), ">".to_string(), "==".to_string()]);
                 }
                 BinOp::Le(_) => {
-                    self.add_candidate(line, col, "<=".to_string(), MutationType::RelationalOperator, 
+                    self.add_candidate(line, col, bytes, "<=".to_string(), MutationType::RelationalOperator, 
                                       vec!["<".to_string(), ">=".to_string()]);
                 }
                 BinOp::Gt(_) => {
-                    self.add_candidate(line, col, ">".to_string(), MutationType::RelationalOperator, 
+                    self.add_candidate(line, col, bytes, ">".to_string(), MutationType::RelationalOperator, 
                                       vec![">=".to_string(), "<".to_string(), "==".to_string()]);
                 }
                 BinOp::Ge(_) => {
-                    self.add_candidate(line, col, ">=".to_string(), MutationType::RelationalOperator, 
+                    self.add_candidate(line, col, bytes, ">=".to_string(), MutationType::RelationalOperator, 
                                       vec![">".to_string(), "<=".to_string()]);
                 }
                 
@@ -234,14 +1133,14 @@ impl VisitMut for MutationVisitor {
     
     // Visit unary operations for operator mutations
     fn visit_expr_unary_mut(&mut self, node: &mut ExprUnary) {
-        if let Some((line, col)) = self.get_location(&node) {
+        if let Some((line, col, bytes)) = self.get_location(&node.op) {
             match node.op {
                 UnOp::Not(_) => {
-                    self.add_candidate(line, col, "!".to_string(), MutationType::LogicalOperator, 
+                    self.add_candidate(line, col, bytes, "!".to_string(), MutationType::LogicalOperator, 
                                       vec!["".to_string()]);
                 }
                 UnOp::Neg(_) => {
-                    self.add_candidate(line, col, "-".to_string(), MutationType::ArithmeticOperator, 
+                    self.add_candidate(line, col, bytes, "-".to_string(), MutationType::ArithmeticOperator, 
                                       vec!["".to_string()]);
                 }
                 _ => {}
@@ -253,11 +1152,57 @@ impl VisitMut for MutationVisitor {
     }
 }
 
+/// Operator `mutation` should turn `op` into, provided both belong to the same
+/// family (arithmetic, relational, logical, bitwise or compound assignment).
+fn replacement_bin_op(op: &BinOp, mutation: &str) -> Option<BinOp> {
+    let span = op.span();
+    let replacement = match mutation.trim() {
+        "+" => BinOp::Add(syn::Token![+](span)),
+        "-" => BinOp::Sub(syn::Token![-](span)),
+        "*" => BinOp::Mul(syn::Token![*](span)),
+        "/" => BinOp::Div(syn::Token![/](span)),
+        "%" => BinOp::Rem(syn::Token![%](span)),
+        "==" => BinOp::Eq(syn::Token![==](span)),
+        "!=" => BinOp::Ne(syn::Token![!=](span)),
+        "<" => BinOp::Lt(syn::Token![<](span)),
+        "<=" => BinOp::Le(syn::Token![<=](span)),
+        ">" => BinOp::Gt(syn::Token![>](span)),
+        ">=" => BinOp::Ge(syn::Token![>=](span)),
+        "&&" => BinOp::And(syn::Token![&&](span)),
+        "||" => BinOp::Or(syn::Token![||](span)),
+        "&" => BinOp::BitAnd(syn::Token![&](span)),
+        "|" => BinOp::BitOr(syn::Token![|](span)),
+        "^" => BinOp::BitXor(syn::Token![^](span)),
+        "<<" => BinOp::Shl(syn::Token![<<](span)),
+        ">>" => BinOp::Shr(syn::Token![>>](span)),
+        "+=" => BinOp::AddAssign(syn::Token![+=](span)),
+        "-=" => BinOp::SubAssign(syn::Token![-=](span)),
+        "*=" => BinOp::MulAssign(syn::Token![*=](span)),
+        "/=" => BinOp::DivAssign(syn::Token![/=](span)),
+        "%=" => BinOp::RemAssign(syn::Token![%=](span)),
+        _ => return None,
+    };
+    (bin_op_family(op)? == bin_op_family(&replacement)?).then_some(replacement)
+}
+
+fn bin_op_family(op: &BinOp) -> Option<u8> {
+    match op {
+        BinOp::Add(_) | BinOp::Sub(_) | BinOp::Mul(_) | BinOp::Div(_) | BinOp::Rem(_) => Some(0),
+        BinOp::Eq(_) | BinOp::Ne(_) | BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_) => Some(1),
+        BinOp::And(_) | BinOp::Or(_) => Some(2),
+        BinOp::BitAnd(_) | BinOp::BitOr(_) | BinOp::BitXor(_) | BinOp::Shl(_) | BinOp::Shr(_) => Some(3),
+        BinOp::AddAssign(_) | BinOp::SubAssign(_) | BinOp::MulAssign(_) | BinOp::DivAssign(_)
+        | BinOp::RemAssign(_) => Some(4),
+        _ => None,
+    }
+}
+
 #[allow(dead_code)]
 struct AstMutationApplier<'a> {
     candidate: &'a MutationCandidate,
     mutation: &'a str,
     pub mutation_applied: bool,
+    pub error: Option<MutationError>,
 }
 
 #[allow(dead_code)]
@@ -267,12 +1212,12 @@ impl<'a> AstMutationApplier<'a> {
             candidate,
             mutation,
             mutation_applied: false,
+            error: None,
         }
     }
     
-    fn get_location(&self, _expr: &impl ToTokens) -> Option<(usize, usize)> {
-        // Simplified implementation, similar to MutationVisitor
-        Some((1, 1))
+    fn get_location(&self, node: &impl Spanned) -> Option<(usize, usize)> {
+        span_location(node)
     }
     
     fn should_apply_mutation(&self, line: usize, column: usize) -> bool {
@@ -288,29 +1233,23 @@ impl<'a> VisitMut for AstMutationApplier<'a> {
             return;
         }
         
-        if let Some((line, col)) = self.get_location(&node) {
-            if self.should_apply_mutation(line, col) {
-                match self.candidate.mutation_type {
-                    MutationType::ConstantReplacement => {
-                        match &mut node.lit {
-                            Lit::Int(lit_int) => {
-                                if let Ok(_) = i64::from_str(self.mutation) {
-                                    debug!("Applying constant mutation: {} -> {}", 
-                                          lit_int.to_token_stream(), self.mutation);
-                                    self.mutation_applied = true;
-                                }
-                            }
-                            Lit::Bool(lit_bool) => {
-                                if let Ok(new_val) = bool::from_str(self.mutation) {
-                                    lit_bool.value = new_val;
-                                    self.mutation_applied = true;
-                                }
-                            }
-                            _ => {}
-                        }
+        if let Some((line, col)) = self.get_location(&node)
+            && self.should_apply_mutation(line, col)
+            && self.candidate.mutation_type == MutationType::ConstantReplacement
+        {
+            match &mut node.lit {
+                Lit::Int(lit_int) if i64::from_str(self.mutation).is_ok() => {
+                    debug!("Applying constant mutation: {} -> {}", 
+                          lit_int.to_token_stream(), self.mutation);
+                    self.mutation_applied = true;
+                }
+                Lit::Bool(lit_bool) => {
+                    if let Ok(new_val) = bool::from_str(self.mutation) {
+                        lit_bool.value = new_val;
+                        self.mutation_applied = true;
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
         
@@ -326,14 +1265,24 @@ impl<'a> VisitMut for AstMutationApplier<'a> {
             return;
         }
         
-        if let Some((line, col)) = self.get_location(&node) {
-            if self.should_apply_mutation(line, col) {
-                // Applying binary operation mutations is complex in AST
-                // This is a simplified placeholder implementation
-                debug!("Attempting to apply mutation to binary operation at line {}, col {}", line, col);
-                self.mutation_applied = true;
-                // In a real implementation, you would replace the operator based on the mutation type
+        if let Some((line, col)) = self.get_location(&node.op)
+            && self.should_apply_mutation(line, col)
+        {
+            match replacement_bin_op(&node.op, self.mutation) {
+                Some(op) => {
+                    debug!("Applying binary mutation at line {}, col {}: {} -> {}",
+                          line, col, node.op.to_token_stream(), self.mutation);
+                    node.op = op;
+                    self.mutation_applied = true;
+                }
+                None => {
+                    self.error = Some(MutationError::InvalidMutation {
+                        mutation: self.mutation.to_string(),
+                        suggestions: self.candidate.suggested_mutations.clone(),
+                    });
+                }
             }
+            return;
         }
         
         // Continue visiting if mutation wasn't applied
@@ -342,6 +1291,90 @@ impl<'a> VisitMut for AstMutationApplier<'a> {
         }
     }
     
+    // Return values are swapped as a whole expression, and so are `break` and
+    // `continue`. Nested expressions can start at the same location, so only
+    // the outermost one is replaced.
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if self.mutation_applied {
+            return;
+        }
+
+        let replaces_expr = match self.candidate.mutation_type {
+            MutationType::ReturnValue => true,
+            MutationType::BreakContinueReplacement => matches!(node, Expr::Break(_) | Expr::Continue(_)),
+            _ => false,
+        };
+        if replaces_expr
+            && let Some((line, col)) = self.get_location(node)
+            && self.should_apply_mutation(line, col)
+        {
+            match syn::parse_str::<Expr>(self.mutation) {
+                Ok(replacement) => {
+                    debug!("Replacing return value at line {}, col {} with {}", line, col, self.mutation);
+                    *node = replacement;
+                    self.mutation_applied = true;
+                }
+                Err(_) => {
+                    self.error = Some(MutationError::InvalidMutation {
+                        mutation: self.mutation.to_string(),
+                        suggestions: self.candidate.suggested_mutations.clone(),
+                    });
+                }
+            }
+            return;
+        }
+
+        syn::visit_mut::visit_expr_mut(self, node);
+    }
+
+    fn visit_expr_match_mut(&mut self, node: &mut syn::ExprMatch) {
+        if self.mutation_applied {
+            return;
+        }
+
+        if self.candidate.mutation_type == MutationType::SwitchCase
+            && let Some((line, col)) = self.get_location(&node.match_token)
+            && self.should_apply_mutation(line, col)
+        {
+            match ArmMutation::from_str(self.mutation) {
+                Ok(mutation) if mutation.apply(&mut node.arms) => {
+                    debug!("Applying `{}` to the match at line {}, col {}", self.mutation, line, col);
+                    self.mutation_applied = true;
+                }
+                _ => {
+                    self.error = Some(MutationError::InvalidMutation {
+                        mutation: self.mutation.to_string(),
+                        suggestions: self.candidate.suggested_mutations.clone(),
+                    });
+                }
+            }
+            return;
+        }
+
+        syn::visit_mut::visit_expr_match_mut(self, node);
+    }
+
+    // Statement deletions drop the statement starting at the candidate's
+    // location from its block.
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        if self.mutation_applied {
+            return;
+        }
+
+        if self.candidate.mutation_type == MutationType::StatementDeletion
+            && let Some(index) = node.stmts.iter().position(|stmt| {
+                self.get_location(stmt).is_some_and(|(line, col)| self.should_apply_mutation(line, col))
+            })
+        {
+            debug!("Deleting statement at line {}, col {}", self.candidate.line, self.candidate.column);
+            node.stmts.remove(index);
+            self.mutation_applied = true;
+            return;
+        }
+
+        syn::visit_mut::visit_block_mut(self, node);
+    }
+
     // More visit_* methods would be implemented similarly
 }
 
@@ -372,6 +1405,146 @@ mod tests {
         assert!(!candidates.is_empty());
     }
     
+    #[test]
+    fn test_candidates_report_source_locations() {
+        let source_code = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+
+        let candidates = AstMutator::new().find_ast_mutations(source_code).unwrap();
+
+        let plus = candidates.iter().find(|c| c.original_code == "+").unwrap();
+        assert_eq!((plus.line, plus.column), (2, 7));
+        assert_eq!(&source_code[plus.byte_start.unwrap()..plus.byte_end.unwrap()], "+");
+    }
+
+    #[test]
+    fn test_unparsable_source_is_a_parse_error() {
+        let result = AstMutator::new().find_ast_mutations("fn broken( {");
+        assert!(matches!(result, Err(MutationError::Parse(_))));
+    }
+
+    #[test]
+    fn test_tuple_comparison_yields_field_swap() {
+        let source_code = "fn same(a: i32, b: i32, c: i32, d: i32) -> bool {\n    (a, b) == (c, d)\n}\n";
+
+        let candidates = field_swap_candidates(source_code).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].mutation_type, MutationType::FieldSwap);
+        assert_eq!(candidates[0].original_code, "(a, b)");
+        assert_eq!((candidates[0].line, candidates[0].column), (2, 5));
+        assert_eq!(candidates[0].suggested_mutations, vec!["(b, a)".to_string()]);
+
+        let source_code = "fn origin(p: Point) -> bool {\n    p == Point { x: 0, y: 1, label: \"o\" }\n}\n";
+        let candidates = field_swap_candidates(source_code).unwrap();
+        assert_eq!(candidates[0].suggested_mutations, vec!["Point { x: 1, y: 0, label: \"o\" }".to_string()]);
+    }
+
+    #[test]
+    fn test_statement_is_deleted_from_two_statement_function() {
+        let source_code = "pub fn record(log: &mut Vec<i32>, x: i32) -> usize {\n    log.push(x);\n    let total = log.len();\n    total\n}\n\n#[test]\nfn records() {\n    assert_eq!(record(&mut vec![], 1), 1);\n}\n";
+
+        let candidates = statement_deletion_candidates(source_code).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].original_code, "log.push(x);");
+        assert_eq!((candidates[0].line, candidates[0].column), (2, 5));
+
+        let mutated = AstMutator::new().apply_ast_mutation(source_code, &candidates[0], "").unwrap();
+        assert!(!mutated.contains("push"));
+        assert!(mutated.contains("let total = log . len () ;"));
+        assert!(parse_file(&mutated).is_ok());
+    }
+
+    #[test]
+    fn test_bool_tail_expression_yields_return_value_candidate() {
+        let source_code = "pub fn f(a: i32, b: i32) -> bool {\n    a > b\n}\n\npub fn g(v: &[i32]) -> Option<usize> {\n    if v.is_empty() {\n        return None;\n    }\n    v.iter().position(|x| { return *x > 0; })\n}\n";
+
+        let candidates = return_value_candidates(source_code).unwrap();
+
+        let found: Vec<(usize, &str, &str)> = candidates
+            .iter()
+            .map(|c| (c.line, c.original_code.as_str(), c.suggested_mutations[0].as_str()))
+            .collect();
+        assert_eq!(found, vec![(2, "a > b", "!(a > b)"), (9, "v.iter().position(|x| { return *x > 0; })", "None")]);
+
+        let mutated = AstMutator::new().apply_ast_mutation(source_code, &candidates[0], "!(a > b)").unwrap();
+        assert!(mutated.contains("! (a > b)"));
+        assert!(parse_file(&mutated).is_ok());
+
+        let unit_result = "pub fn save() -> Result<(), String> {\n    Ok(())\n}\n";
+        assert!(return_value_candidates(unit_result).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_break_in_for_loop_becomes_continue() {
+        let source_code = "pub fn first_even(v: &[i32]) -> Option<i32> {\n    let mut found = None;\n    'scan: for x in v {\n        if x % 2 != 0 {\n            continue;\n        }\n        found = Some(*x);\n        break 'scan;\n    }\n    let _ = loop { break 1 };\n    found\n}\n";
+
+        let candidates = break_continue_candidates(source_code).unwrap();
+
+        let found: Vec<(usize, &str, &str)> = candidates
+            .iter()
+            .map(|c| (c.line, c.original_code.as_str(), c.suggested_mutations[0].as_str()))
+            .collect();
+        assert_eq!(found, vec![(5, "continue", "break"), (8, "break 'scan", "continue 'scan")]);
+
+        let mutated = AstMutator::new().apply_ast_mutation(source_code, &candidates[1], "continue 'scan").unwrap();
+        assert!(mutated.contains("continue 'scan ;"));
+        assert!(!mutated.contains("break 'scan"));
+        assert!(mutated.contains("break 1"));
+        assert!(parse_file(&mutated).is_ok());
+    }
+
+    #[test]
+    fn test_unwrap_becomes_unwrap_or_default() {
+        use crate::mutation::mutators::CodeMutator;
+
+        let source_code = "pub fn load() -> usize {\n    foo().unwrap()\n}\n\npub fn parse(s: &str) -> Result<i32, std::num::ParseIntError> {\n    let n = s.trim().parse::<i32>()?;\n    Ok(n)\n}\n";
+
+        let candidates = optional_unwrap_candidates(source_code).unwrap();
+
+        let found: Vec<(usize, usize, &str, &str)> = candidates
+            .iter()
+            .map(|c| (c.line, c.column, c.original_code.as_str(), c.suggested_mutations[0].as_str()))
+            .collect();
+        assert_eq!(found, vec![(2, 11, "unwrap()", "unwrap_or_default()"), (6, 36, "?", ".unwrap()")]);
+
+        let mutated = CodeMutator::new().apply_mutation(source_code, &candidates[0], "unwrap_or_default()").unwrap();
+        assert!(mutated.contains("    foo().unwrap_or_default()\n"));
+        let mutated = CodeMutator::new().apply_mutation(source_code, &candidates[1], ".unwrap()").unwrap();
+        assert!(mutated.contains("s.trim().parse::<i32>().unwrap();"));
+    }
+
+    #[test]
+    fn test_match_arms_are_swapped_without_losing_exhaustiveness() {
+        let source_code = "pub fn describe(n: i32) -> &'static str {\n    match n {\n        0 => \"zero\",\n        x if x < 0 => \"negative\",\n        _ => \"positive\",\n    }\n}\n";
+
+        let candidates = switch_case_candidates(source_code).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        let candidate = &candidates[0];
+        assert_eq!((candidate.line, candidate.column, candidate.original_code.as_str()), (2, 5, "match n"));
+        assert_eq!(
+            candidate.suggested_mutations,
+            vec![
+                "swap arms 1 and 2",
+                "swap arms 2 and 3",
+                "delete arm 1",
+                "delete arm 2",
+                "replace guard of arm 2 with false",
+            ]
+        );
+
+        let mutated = AstMutator::new().apply_ast_mutation(source_code, candidate, "swap arms 2 and 3").unwrap();
+        let file = parse_file(&mutated).unwrap();
+        let syn::Item::Fn(function) = &file.items[0] else { panic!("expected a function") };
+        let Some(Stmt::Expr(Expr::Match(matched), None)) = function.block.stmts.first() else {
+            panic!("expected a match")
+        };
+        let bodies: Vec<String> = matched.arms.iter().map(|arm| arm.body.to_token_stream().to_string()).collect();
+        assert_eq!(bodies, vec!["\"zero\"", "\"positive\"", "\"negative\""]);
+        assert!(is_catch_all(matched.arms.last().unwrap()));
+    }
+
     #[test]
     fn test_ast_mutator_applies_mutation() {
         let source_code = r#"
@@ -390,15 +1563,33 @@ mod tests {
             matches!(c.mutation_type, MutationType::RelationalOperator)
         );
         
-        if let Some(candidate) = operator_candidate {
-            if !candidate.suggested_mutations.is_empty() {
-                let mutation = &candidate.suggested_mutations[0];
-                let result = mutator.apply_ast_mutation(source_code, candidate, mutation);
-                
-                // In a real scenario, we'd check the actual modified code
-                // But for this test, we'll just check that the operation didn't fail
-                assert!(result.is_ok());
-            }
+        if let Some(candidate) = operator_candidate
+            && !candidate.suggested_mutations.is_empty()
+        {
+            let mutation = &candidate.suggested_mutations[0];
+            let result = mutator.apply_ast_mutation(source_code, candidate, mutation);
+            
+            // In a real scenario, we'd check the actual modified code
+            // But for this test, we'll just check that the operation didn't fail
+            assert!(result.is_ok());
         }
     }
+
+    #[test]
+    fn test_binary_operator_is_rewritten() {
+        let source_code = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+        let mutator = AstMutator::new();
+        let candidate = mutator
+            .find_ast_mutations(source_code)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.original_code == "+")
+            .unwrap();
+
+        let mutated = mutator.apply_ast_mutation(source_code, &candidate, "-").unwrap();
+        assert!(mutated.contains("a - b"));
+
+        let invalid = mutator.apply_ast_mutation(source_code, &candidate, "&&");
+        assert!(matches!(invalid, Err(MutationError::InvalidMutation { .. })));
+    }
 }
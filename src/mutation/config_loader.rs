@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use tracing::{info, warn};
@@ -5,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use serde_yaml;
 use toml;
 
-use crate::mutation::types::{MutationTestConfig, MutationType};
+use crate::mutation::types::{MutationTestConfig, MutationType, NumericMutationKind, ReportFormat, SampleStrategy};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MutationConfigFile {
@@ -16,27 +17,94 @@ pub struct MutationConfigFile {
     pub mutation_types: Option<Vec<String>>,
     pub excluded_mutations: Option<Vec<String>>,
     pub excluded_files: Option<Vec<String>>,
+    pub excluded_dirs: Option<Vec<String>>,
     pub excluded_functions: Option<Vec<String>>,
     pub min_coverage_percent: Option<f64>,
     pub parallel_jobs: Option<usize>,
     pub report_format: Option<String>,
     pub report_output_path: Option<String>,
     pub ast_mutations_enabled: Option<bool>,
+    pub shuffle_seed: Option<u64>,
+    pub group_by_module: Option<bool>,
+    pub allow_non_test_command: Option<bool>,
+    pub skip_trait_impls: Option<bool>,
+    pub skipped_traits: Option<Vec<String>>,
+    pub max_concurrent_compiles: Option<usize>,
+    pub debug_skips: Option<bool>,
+    pub max_error_rate: Option<f64>,
+    pub allowed_files: Option<Vec<String>>,
+    pub crate_name: Option<String>,
+    pub crate_edition: Option<String>,
+    pub cap_lints: Option<bool>,
+    pub incremental_workspace: Option<bool>,
+    pub on_survivor_command: Option<String>,
+    pub on_survivor_inline: Option<bool>,
+    pub shared_target_dir: Option<bool>,
+    pub timeout_multiplier: Option<f64>,
+    /// Profile from `profiles` to overlay when none is chosen on the
+    /// command line.
+    pub profile: Option<String>,
+    /// Named overlays, e.g. `[profiles.fast]`, with the same keys as the
+    /// base config.
+    pub profiles: Option<HashMap<String, MutationConfigFile>>,
+    pub flaky_retries: Option<u32>,
+    pub project_root: Option<String>,
+    pub max_total_mutations: Option<usize>,
+    pub sample_strategy: Option<SampleStrategy>,
+    pub numeric_mutations: Option<Vec<String>>,
+    pub coverage_file: Option<String>,
+    pub max_file_size_bytes: Option<u64>,
 }
 
+/// Config files looked up, in order, relative to the project root when no
+/// explicit path is given. The first one that parses wins.
+pub const DEFAULT_CONFIG_PATHS: &[&str] = &[
+    "flux.config.yaml",
+    "flux.config.yml",
+    "flux.config.toml",
+    ".flux/config.yaml",
+    ".flux/config.toml",
+    "mutation.toml",
+    ".config/mutation.toml",
+];
+
+/// One file per line (`#` starts a comment); when present, only these files
+/// are mutated.
+pub const ALLOWLIST_PATH: &str = ".mutation-allowlist";
+
 #[allow(dead_code)] 
-pub struct ConfigLoader;
+#[derive(Default)]
+pub struct ConfigLoader {
+    profile: Option<String>,
+}
 
 #[allow(dead_code)]
 impl ConfigLoader {
     
     #[allow(dead_code)]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+    
+    /// Overlays the named profile on the base config, taking precedence
+    /// over the file's own `profile` key.
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
     }
     
     #[allow(dead_code)]
     pub fn load_config(&self, config_path: Option<&str>) -> MutationTestConfig {
+        self.load_config_in(Path::new("."), config_path)
+    }
+    
+    /// Loads the configuration for the project rooted at `root`. An explicit
+    /// `config_path` wins; otherwise the first of [`DEFAULT_CONFIG_PATHS`]
+    /// that parses is used. The allowlist is then read from
+    /// [`ALLOWLIST_PATH`] under the config's `project_root`, or `root`
+    /// without one, unless the config already lists `allowed_files`.
+    #[allow(dead_code)]
+    pub fn load_config_in(&self, root: &Path, config_path: Option<&str>) -> MutationTestConfig {
         let mut config = MutationTestConfig::default();
         
         if let Some(path) = config_path {
@@ -44,7 +112,9 @@ impl ConfigLoader {
                 match self.parse_config_file(path) {
                     Ok(file_config) => {
                         info!("Loading mutation configuration from {}", path);
-                        self.apply_config(&mut config, file_config);
+                        if let Err(e) = self.apply_with_profile(&mut config, file_config) {
+                            warn!("{}", e);
+                        }
                     }
                     Err(e) => {
                         warn!("Failed to parse config file {}: {}", path, e);
@@ -53,35 +123,121 @@ impl ConfigLoader {
             } else {
                 warn!("Config file not found: {}", path);
             }
-        } else {
-            // Look for config in default locations
-            let default_paths = [
-                "flux.config.yaml",
-                "flux.config.yml",
-                "flux.config.toml",
-                ".flux/config.yaml",
-                ".flux/config.toml",
-            ];
-            
-            for path in &default_paths {
-                if Path::new(path).exists() {
-                    match self.parse_config_file(path) {
-                        Ok(file_config) => {
-                            info!("Loading mutation configuration from {}", path);
-                            self.apply_config(&mut config, file_config);
-                            break;
-                        }
-                        Err(e) => {
-                            warn!("Failed to parse config file {}: {}", path, e);
-                        }
-                    }
-                }
+        } else if let Some((path, file_config)) = self.find_default_config(root) {
+            info!("Loading mutation configuration from {}", path);
+            if let Err(e) = self.apply_with_profile(&mut config, file_config) {
+                warn!("{}", e);
             }
         }
         
+        if let Err(e) = self.apply_allowlist(&mut config, root) {
+            warn!("{}", e);
+        }
+        
         config
     }
     
+    /// Loads the config for a command-line run in the project at `root`: an
+    /// explicit `config_path` as [`Self::load_config_strict`] does, otherwise
+    /// leniently as [`Self::load_config_in`] does. Either way, a profile
+    /// chosen with [`Self::with_profile`] that the config doesn't define is an
+    /// error.
+    pub fn load_cli_config(&self, root: &Path, config_path: Option<&str>) -> Result<MutationTestConfig, String> {
+        if let Some(path) = config_path {
+            return self.load_config_strict_in(root, path);
+        }
+        if let Some(name) = &self.profile {
+            let profiles = self.find_default_config(root).and_then(|(_, file_config)| file_config.profiles).unwrap_or_default();
+            if !profiles.contains_key(name) {
+                return Err(unknown_profile(name, &profiles));
+            }
+        }
+        Ok(self.load_config_in(root, None))
+    }
+    
+    /// The first of [`DEFAULT_CONFIG_PATHS`] under `root` that parses, and
+    /// its path.
+    fn find_default_config(&self, root: &Path) -> Option<(String, MutationConfigFile)> {
+        for path in DEFAULT_CONFIG_PATHS {
+            let path = root.join(path);
+            if !path.exists() {
+                continue;
+            }
+            let path = path.to_string_lossy().into_owned();
+            match self.parse_config_file(&path) {
+                Ok(file_config) => return Some((path, file_config)),
+                Err(e) => warn!("Failed to parse config file {}: {}", path, e),
+            }
+        }
+        None
+    }
+    
+    /// Like [`Self::load_config`] with an explicit path, but a missing or
+    /// unparsable file, an unknown mutation type or report format, or an
+    /// out-of-range value is an error instead of a warning.
+    pub fn load_config_strict(&self, config_path: &str) -> Result<MutationTestConfig, String> {
+        self.load_config_strict_in(Path::new("."), config_path)
+    }
+    
+    /// Like [`Self::load_config_strict`] for the project rooted at `root`,
+    /// which the allowlist is read from when the config sets no
+    /// `project_root`.
+    pub fn load_config_strict_in(&self, root: &Path, config_path: &str) -> Result<MutationTestConfig, String> {
+        if !Path::new(config_path).exists() {
+            return Err(format!("Config file not found: {}", config_path));
+        }
+        let file_config = self
+            .parse_config_file(config_path)
+            .map_err(|e| format!("Invalid config file {}: {}", config_path, e))?;
+        
+        check_names(&file_config)?;
+        check_values(&file_config)?;
+        for profile in file_config.profiles.iter().flat_map(HashMap::values) {
+            check_names(profile)?;
+            check_values(profile)?;
+        }
+        
+        let mut config = MutationTestConfig::default();
+        self.apply_with_profile(&mut config, file_config)?;
+        validate_config(&config)?;
+        self.apply_allowlist(&mut config, root)?;
+        Ok(config)
+    }
+    
+    /// Applies `file_config`, then the selected profile over it. Naming a
+    /// profile the file doesn't define is an error; the base is still applied.
+    fn apply_with_profile(&self, config: &mut MutationTestConfig, mut file_config: MutationConfigFile) -> Result<(), String> {
+        let mut profiles = file_config.profiles.take().unwrap_or_default();
+        let selected = self.profile.clone().or_else(|| file_config.profile.take());
+        self.apply_config(config, file_config);
+        
+        if let Some(name) = selected {
+            let profile = profiles.remove(&name).ok_or_else(|| unknown_profile(&name, &profiles))?;
+            info!("Applying mutation profile {}", name);
+            self.apply_config(config, profile);
+        }
+        Ok(())
+    }
+    
+    /// Reads [`ALLOWLIST_PATH`] under the config's `project_root`, or `root`
+    /// without one, unless the config already lists `allowed_files`.
+    fn apply_allowlist(&self, config: &mut MutationTestConfig, root: &Path) -> Result<(), String> {
+        let allowlist = config.project_root.as_deref().map_or(root, Path::new).join(ALLOWLIST_PATH);
+        if !config.allowed_files.is_empty() || !allowlist.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(&allowlist)
+            .map_err(|e| format!("Failed to read allowlist {}: {}", allowlist.display(), e))?;
+        info!("Loading mutation allowlist from {}", allowlist.display());
+        config.allowed_files = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        Ok(())
+    }
+    
     #[allow(dead_code)]
     fn parse_config_file(&self, path: &str) -> Result<MutationConfigFile, String> {
         let content = fs::read_to_string(path)
@@ -98,6 +254,22 @@ impl ConfigLoader {
         }
     }
     
+    /// Writes a fully-resolved config to `path` as YAML or TOML, chosen by
+    /// extension like [`Self::load_config`], so a run can be reproduced
+    /// later with `--config`.
+    pub fn save_config(&self, config: &MutationTestConfig, path: &str) -> Result<(), String> {
+        let content = if path.ends_with(".yaml") || path.ends_with(".yml") {
+            serde_yaml::to_string(config)
+                .map_err(|e| format!("Failed to serialize YAML config: {}", e))?
+        } else if path.ends_with(".toml") {
+            toml::to_string_pretty(config)
+                .map_err(|e| format!("Failed to serialize TOML config: {}", e))?
+        } else {
+            return Err(format!("Unsupported config file format: {}", path));
+        };
+        fs::write(path, content).map_err(|e| format!("Failed to write config file: {}", e))
+    }
+    
     #[allow(dead_code)]
     fn apply_config(&self, config: &mut MutationTestConfig, file_config: MutationConfigFile) {
         if let Some(timeout) = file_config.timeout_seconds {
@@ -144,6 +316,10 @@ impl ConfigLoader {
             config.excluded_files = excluded_files;
         }
         
+        if let Some(excluded_dirs) = file_config.excluded_dirs {
+            config.excluded_dirs = excluded_dirs;
+        }
+        
         if let Some(excluded_functions) = file_config.excluded_functions {
             config.excluded_functions = excluded_functions;
         }
@@ -157,14 +333,7 @@ impl ConfigLoader {
         }
         
         if let Some(format_str) = file_config.report_format {
-            let format = match format_str.to_lowercase().as_str() {
-                "json" => crate::mutation::types::ReportFormat::JSON,
-                "csv" => crate::mutation::types::ReportFormat::CSV,
-                "html" => crate::mutation::types::ReportFormat::HTML,
-                "markdown" | "md" => crate::mutation::types::ReportFormat::Markdown,
-                _ => crate::mutation::types::ReportFormat::Console,
-            };
-            config.report_format = Some(format);
+            config.report_format = Some(parse_report_format(&format_str).unwrap_or(ReportFormat::Console));
         }
         
         if let Some(output_path) = file_config.report_output_path {
@@ -174,7 +343,208 @@ impl ConfigLoader {
         if let Some(ast_enabled) = file_config.ast_mutations_enabled {
             config.ast_mutations_enabled = ast_enabled;
         }
+        
+        if let Some(seed) = file_config.shuffle_seed {
+            config.shuffle_seed = Some(seed);
+        }
+        
+        if let Some(group_by_module) = file_config.group_by_module {
+            config.group_by_module = group_by_module;
+        }
+        
+        if let Some(allow_non_test_command) = file_config.allow_non_test_command {
+            config.allow_non_test_command = allow_non_test_command;
+        }
+        
+        if let Some(skip_trait_impls) = file_config.skip_trait_impls {
+            config.skip_trait_impls = skip_trait_impls;
+        }
+        
+        if let Some(skipped_traits) = file_config.skipped_traits {
+            config.skipped_traits = skipped_traits;
+        }
+        
+        if let Some(max_concurrent_compiles) = file_config.max_concurrent_compiles {
+            config.max_concurrent_compiles = Some(max_concurrent_compiles);
+        }
+        
+        if let Some(debug_skips) = file_config.debug_skips {
+            config.debug_skips = debug_skips;
+        }
+        
+        if let Some(max_error_rate) = file_config.max_error_rate {
+            config.max_error_rate = Some(max_error_rate);
+        }
+        
+        if let Some(allowed_files) = file_config.allowed_files {
+            config.allowed_files = allowed_files;
+        }
+        
+        if let Some(crate_name) = file_config.crate_name {
+            config.crate_name = crate_name;
+        }
+        
+        if let Some(crate_edition) = file_config.crate_edition {
+            config.crate_edition = crate_edition;
+        }
+        
+        if let Some(cap_lints) = file_config.cap_lints {
+            config.cap_lints = cap_lints;
+        }
+        
+        if let Some(incremental_workspace) = file_config.incremental_workspace {
+            config.incremental_workspace = incremental_workspace;
+        }
+        
+        if let Some(on_survivor_command) = file_config.on_survivor_command {
+            config.on_survivor_command = Some(on_survivor_command);
+        }
+        
+        if let Some(on_survivor_inline) = file_config.on_survivor_inline {
+            config.on_survivor_inline = on_survivor_inline;
+        }
+        
+        if let Some(shared_target_dir) = file_config.shared_target_dir {
+            config.shared_target_dir = shared_target_dir;
+        }
+        
+        if let Some(timeout_multiplier) = file_config.timeout_multiplier {
+            if valid_timeout_multiplier(timeout_multiplier) {
+                config.timeout_multiplier = Some(timeout_multiplier);
+            } else {
+                warn!("Ignoring timeout_multiplier {}: it must be a finite number greater than 0", timeout_multiplier);
+            }
+        }
+        
+        if let Some(flaky_retries) = file_config.flaky_retries {
+            config.flaky_retries = flaky_retries;
+        }
+        
+        if let Some(project_root) = file_config.project_root {
+            config.project_root = Some(project_root);
+        }
+        
+        if let Some(max_total_mutations) = file_config.max_total_mutations {
+            config.max_total_mutations = Some(max_total_mutations);
+        }
+        
+        if let Some(sample_strategy) = file_config.sample_strategy {
+            config.sample_strategy = sample_strategy;
+        }
+        
+        if let Some(kinds) = file_config.numeric_mutations {
+            let mut parsed = Vec::new();
+            for kind_str in kinds {
+                match kind_str.parse::<NumericMutationKind>() {
+                    Ok(kind) => parsed.push(kind),
+                    Err(e) => warn!("Invalid numeric mutation '{}': {}", kind_str, e),
+                }
+            }
+            if !parsed.is_empty() {
+                config.numeric_mutations = parsed;
+            }
+        }
+        
+        if let Some(coverage_file) = file_config.coverage_file {
+            config.coverage_file = Some(coverage_file);
+        }
+        
+        if let Some(max_file_size_bytes) = file_config.max_file_size_bytes {
+            config.max_file_size_bytes = Some(max_file_size_bytes);
+        }
+    }
+}
+
+/// The report format named in a config file, case-insensitively.
+fn parse_report_format(name: &str) -> Option<ReportFormat> {
+    match name.to_lowercase().as_str() {
+        "console" => Some(ReportFormat::Console),
+        "json" => Some(ReportFormat::JSON),
+        "csv" => Some(ReportFormat::CSV),
+        "html" => Some(ReportFormat::HTML),
+        "markdown" | "md" => Some(ReportFormat::Markdown),
+        "cobertura" => Some(ReportFormat::Cobertura),
+        "sarif" => Some(ReportFormat::Sarif),
+        "junit" => Some(ReportFormat::JUnit),
+        "jsonl" | "json-lines" | "jsonlines" => Some(ReportFormat::JsonLines),
+        _ => None,
+    }
+}
+
+/// The error for selecting a profile `profiles` doesn't define.
+fn unknown_profile(name: &str, profiles: &HashMap<String, MutationConfigFile>) -> String {
+    let mut available: Vec<&str> = profiles.keys().map(String::as_str).collect();
+    available.sort_unstable();
+    if available.is_empty() {
+        format!("Unknown profile '{}': the config defines no profiles", name)
+    } else {
+        format!("Unknown profile '{}'; available profiles: {}", name, available.join(", "))
+    }
+}
+
+/// Checks that the mutation types, numeric mutations and report format named
+/// in a config file exist.
+fn check_names(file_config: &MutationConfigFile) -> Result<(), String> {
+    for type_str in file_config.mutation_types.iter().chain(&file_config.excluded_mutations).flatten() {
+        type_str
+            .parse::<MutationType>()
+            .map_err(|e| format!("Invalid mutation type '{}': {}", type_str, e))?;
+    }
+    for kind_str in file_config.numeric_mutations.iter().flatten() {
+        kind_str.parse::<NumericMutationKind>()?;
+    }
+    if let Some(format_str) = &file_config.report_format {
+        parse_report_format(format_str).ok_or_else(|| format!("Unknown report format '{}'", format_str))?;
+    }
+    Ok(())
+}
+
+/// Rejects values in a config file that loading would otherwise drop with a
+/// warning.
+fn check_values(file_config: &MutationConfigFile) -> Result<(), String> {
+    if let Some(multiplier) = file_config.timeout_multiplier
+        && !valid_timeout_multiplier(multiplier)
+    {
+        return Err(format!("timeout_multiplier must be a finite number greater than 0, got {}", multiplier));
+    }
+    Ok(())
+}
+
+fn valid_timeout_multiplier(multiplier: f64) -> bool {
+    multiplier.is_finite() && multiplier > 0.0
+}
+
+/// Rejects settings no run can work with.
+fn validate_config(config: &MutationTestConfig) -> Result<(), String> {
+    if config.timeout_seconds == 0 {
+        return Err("timeout_seconds must be greater than 0".to_string());
+    }
+    if config.max_mutations_per_line == 0 {
+        return Err("max_mutations_per_line must be greater than 0".to_string());
+    }
+    if let Some(percent) = config.min_coverage_percent
+        && !(0.0..=100.0).contains(&percent)
+    {
+        return Err(format!("min_coverage_percent must be between 0 and 100, got {}", percent));
+    }
+    if let Some(rate) = config.max_error_rate
+        && !(0.0..=1.0).contains(&rate)
+    {
+        return Err(format!("max_error_rate must be between 0 and 1, got {}", rate));
+    }
+    if config.timeout_multiplier.is_some_and(|multiplier| !valid_timeout_multiplier(multiplier)) {
+        return Err("timeout_multiplier must be a finite number greater than 0".to_string());
+    }
+    if config.parallel_jobs == Some(0) {
+        return Err("parallel_jobs must be greater than 0".to_string());
     }
+    if config.max_concurrent_compiles == Some(0) {
+        return Err("max_concurrent_compiles must be greater than 0".to_string());
+    }
+    if config.max_total_mutations == Some(0) {
+        return Err("max_total_mutations must be greater than 0".to_string());
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -215,6 +585,31 @@ ast_mutations_enabled: true
         assert!(config.ast_mutations_enabled);
     }
     
+    #[test]
+    fn test_saved_config_round_trips() {
+        let temp_dir = tempdir().unwrap();
+        let config = MutationTestConfig {
+            timeout_seconds: 45,
+            mutation_types: vec![MutationType::ArithmeticOperator, MutationType::OverflowMethod],
+            excluded_mutations: vec![MutationType::StringLiteral],
+            excluded_functions: vec!["main".to_string()],
+            report_format: Some(crate::mutation::types::ReportFormat::JUnit),
+            max_error_rate: Some(0.25),
+            shuffle_seed: Some(7),
+            cap_lints: true,
+            ..MutationTestConfig::default()
+        };
+        let loader = ConfigLoader::new();
+        
+        for name in ["saved.toml", "saved.yaml"] {
+            let path = temp_dir.path().join(name);
+            let path = path.to_str().unwrap();
+            loader.save_config(&config, path).unwrap();
+            assert_eq!(loader.load_config_in(temp_dir.path(), Some(path)), config, "{}", name);
+        }
+        assert!(loader.save_config(&config, temp_dir.path().join("saved.ini").to_str().unwrap()).is_err());
+    }
+    
     #[test]
     fn test_load_toml_config() {
         let temp_dir = tempdir().unwrap();
@@ -266,4 +661,122 @@ mutation_types:
         assert!(config.mutation_types.contains(&MutationType::LogicalOperator));
         assert_eq!(config.mutation_types.len(), 2); // Only the valid types
     }
+    
+    #[test]
+    fn test_strict_loading_rejects_what_lenient_loading_ignores() {
+        let temp_dir = tempdir().unwrap();
+        let loader = ConfigLoader::new();
+        let strict = |name: &str, content: &str| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            loader.load_config_strict(path.to_str().unwrap())
+        };
+        
+        let config = strict("ok.toml", "timeout_seconds = 30\nmutation_types = [\"arithmetic\"]\n").unwrap();
+        assert_eq!(config.timeout_seconds, 30);
+        assert_eq!(config.mutation_types, vec![MutationType::ArithmeticOperator]);
+        
+        let missing = temp_dir.path().join("missing.toml");
+        assert!(loader.load_config_strict(missing.to_str().unwrap()).unwrap_err().contains("not found"));
+        assert!(strict("broken.toml", "timeout_seconds = \"soon\"\n").unwrap_err().contains("Failed to parse TOML"));
+        assert!(strict("types.yaml", "mutation_types:\n  - arithmetic\n  - invalid_type\n").unwrap_err().contains("invalid_type"));
+        assert!(strict("excluded.yaml", "excluded_mutations: [bogus]\n").unwrap_err().contains("bogus"));
+        assert!(strict("format.toml", "report_format = \"pdf\"\n").unwrap_err().contains("pdf"));
+        assert!(strict("coverage.toml", "min_coverage_percent = 120.0\n").unwrap_err().contains("min_coverage_percent"));
+        assert!(strict("timeout.toml", "timeout_seconds = 0\n").unwrap_err().contains("timeout_seconds"));
+        assert!(strict("rate.toml", "max_error_rate = 1.5\n").unwrap_err().contains("max_error_rate"));
+        assert!(strict("jobs.toml", "parallel_jobs = 0\n").unwrap_err().contains("parallel_jobs"));
+        assert!(strict("multiplier.toml", "timeout_multiplier = inf\n").unwrap_err().contains("timeout_multiplier"));
+        
+        let lenient = loader.load_config(Some(temp_dir.path().join("coverage.toml").to_str().unwrap()));
+        assert_eq!(lenient.min_coverage_percent, Some(120.0));
+        for multiplier in ["inf", "nan", "-2.0", "0.0"] {
+            fs::write(temp_dir.path().join("multiplier.toml"), format!("timeout_multiplier = {}\n", multiplier)).unwrap();
+            let lenient = loader.load_config(Some(temp_dir.path().join("multiplier.toml").to_str().unwrap()));
+            assert_eq!(lenient.timeout_multiplier, None, "{}", multiplier);
+        }
+    }
+    
+    #[test]
+    fn test_selected_profile_overlays_the_base_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("flux.config.toml");
+        fs::write(
+            &config_path,
+            r#"
+timeout_seconds = 90
+mutation_types = ["boolean"]
+profile = "fast"
+
+[profiles.fast]
+mutation_types = ["arithmetic", "relational"]
+max_mutations_per_line = 2
+
+[profiles.thorough]
+mutation_types = ["arithmetic", "relational", "logical", "boolean", "numeric", "string"]
+ast_mutations_enabled = true
+"#,
+        )
+        .unwrap();
+        let path = config_path.to_str().unwrap();
+        
+        let fast = ConfigLoader::new().load_config(Some(path));
+        assert_eq!(fast.mutation_types, vec![MutationType::ArithmeticOperator, MutationType::RelationalOperator]);
+        assert_eq!(fast.max_mutations_per_line, 2);
+        assert_eq!(fast.timeout_seconds, 90);
+        assert!(!fast.ast_mutations_enabled);
+        
+        let thorough = ConfigLoader::new().with_profile(Some("thorough".to_string())).load_config(Some(path));
+        assert_eq!(thorough.mutation_types.len(), 6);
+        assert!(thorough.ast_mutations_enabled);
+        assert_eq!(thorough.max_mutations_per_line, MutationTestConfig::default().max_mutations_per_line);
+        
+        let unknown = ConfigLoader::new().with_profile(Some("nightly".to_string()));
+        let error = unknown.load_config_strict(path).unwrap_err();
+        assert!(error.contains("'nightly'") && error.ends_with("available profiles: fast, thorough"), "{}", error);
+        assert_eq!(unknown.load_config(Some(path)).mutation_types, vec![MutationType::BooleanLiteral]);
+        
+        let discovered = unknown.load_cli_config(temp_dir.path(), None).unwrap_err();
+        assert!(discovered.ends_with("available profiles: fast, thorough"), "{}", discovered);
+        let found = ConfigLoader::new().with_profile(Some("thorough".to_string())).load_cli_config(temp_dir.path(), None);
+        assert!(found.unwrap().ast_mutations_enabled);
+        let empty = tempdir().unwrap();
+        assert!(unknown.load_cli_config(empty.path(), None).unwrap_err().contains("no profiles"));
+    }
+    
+    #[test]
+    fn test_mutation_toml_at_root_is_auto_loaded() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("mutation.toml"), "timeout_seconds = 12\n").unwrap();
+        fs::write(
+            temp_dir.path().join(ALLOWLIST_PATH),
+            "# files to mutate\nsrc/lib.rs\n\nsrc/math.rs\n",
+        )
+        .unwrap();
+        
+        let loader = ConfigLoader::new();
+        let config = loader.load_config_in(temp_dir.path(), None);
+        
+        assert_eq!(config.timeout_seconds, 12);
+        assert_eq!(config.allowed_files, vec!["src/lib.rs".to_string(), "src/math.rs".to_string()]);
+        
+        let explicit = temp_dir.path().join("mutation.toml");
+        let strict = loader.load_cli_config(temp_dir.path(), Some(explicit.to_str().unwrap())).unwrap();
+        assert_eq!(strict.allowed_files, config.allowed_files);
+    }
+    
+    #[test]
+    fn test_allowlist_is_read_from_the_configured_project_root() {
+        let project = tempdir().unwrap();
+        fs::write(project.path().join(ALLOWLIST_PATH), "src/lib.rs\n").unwrap();
+        let configs = tempdir().unwrap();
+        let config_path = configs.path().join("ci.toml");
+        fs::write(&config_path, format!("project_root = {:?}\n", project.path())).unwrap();
+        
+        let loader = ConfigLoader::new();
+        let strict = loader.load_config_strict(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(strict.allowed_files, vec!["src/lib.rs".to_string()]);
+        let lenient = loader.load_config_in(configs.path(), Some(config_path.to_str().unwrap()));
+        assert_eq!(lenient.allowed_files, strict.allowed_files);
+    }
 }
@@ -0,0 +1,216 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Execution counts per line of each source file in a coverage report.
+/// Lines the report has no data for, such as blank lines or declarations,
+/// are absent rather than zero.
+#[derive(Debug, Default)]
+pub struct CoverageMap {
+    files: HashMap<PathBuf, HashMap<usize, u64>>,
+}
+
+#[derive(Deserialize)]
+struct LlvmCovExport {
+    data: Vec<LlvmCovData>,
+}
+
+#[derive(Deserialize)]
+struct LlvmCovData {
+    files: Vec<LlvmCovFile>,
+}
+
+#[derive(Deserialize)]
+struct LlvmCovFile {
+    filename: String,
+    segments: Vec<Vec<Value>>,
+}
+
+/// `[line, column, count, has_count, is_region_entry, is_gap_region]`; older
+/// exports leave out the last field.
+struct Segment {
+    line: usize,
+    count: u64,
+    has_count: bool,
+    is_region_entry: bool,
+    is_gap: bool,
+}
+
+impl Segment {
+    fn parse(fields: &[Value]) -> Option<Self> {
+        Some(Segment {
+            line: fields.first()?.as_u64()? as usize,
+            count: fields.get(2)?.as_u64()?,
+            has_count: fields.get(3)?.as_bool()?,
+            is_region_entry: fields.get(4)?.as_bool()?,
+            is_gap: fields.get(5).and_then(Value::as_bool).unwrap_or(false),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct TarpaulinReport {
+    files: Vec<TarpaulinFile>,
+}
+
+#[derive(Deserialize)]
+struct TarpaulinFile {
+    path: Vec<String>,
+    traces: Vec<TarpaulinTrace>,
+}
+
+#[derive(Deserialize)]
+struct TarpaulinTrace {
+    line: usize,
+    stats: TarpaulinStats,
+}
+
+#[derive(Deserialize)]
+struct TarpaulinStats {
+    #[serde(rename = "Line")]
+    line: Option<u64>,
+}
+
+impl CoverageMap {
+    /// Reads an `llvm-cov export --format=text` or tarpaulin `--out Json`
+    /// report, telling them apart by their top-level keys.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&json).map_err(|e| format!("Invalid coverage report {}: {}", path.display(), e))
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if value.get("data").is_some() {
+            let export: LlvmCovExport = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            let files = export
+                .data
+                .into_iter()
+                .flat_map(|data| data.files)
+                .map(|file| {
+                    let segments: Vec<Segment> = file.segments.iter().filter_map(|s| Segment::parse(s)).collect();
+                    (PathBuf::from(file.filename), llvm_line_counts(segments))
+                })
+                .collect();
+            Ok(CoverageMap { files })
+        } else if value.get("files").is_some() {
+            let report: TarpaulinReport = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            let files = report
+                .files
+                .into_iter()
+                .map(|file| {
+                    let counts = file
+                        .traces
+                        .iter()
+                        .filter_map(|trace| Some((trace.line, trace.stats.line?)))
+                        .collect();
+                    (file.path.iter().collect(), counts)
+                })
+                .collect();
+            Ok(CoverageMap { files })
+        } else {
+            Err("expected an llvm-cov export or a tarpaulin JSON report".to_string())
+        }
+    }
+
+    /// Lines of `file` that the report records as never executed. A file the
+    /// report doesn't mention has none, so nothing is skipped for it.
+    pub fn uncovered_lines(&self, file: &Path) -> BTreeSet<usize> {
+        self.counts_for(file)
+            .map(|counts| counts.iter().filter(|(_, count)| **count == 0).map(|(line, _)| *line).collect())
+            .unwrap_or_default()
+    }
+
+    // Reports record absolute paths, so `file` is looked up by its canonical
+    // path first. A path merely ending in `file` only counts when it is the
+    // only one: in a workspace `src/lib.rs` ends several crates' paths.
+    fn counts_for(&self, file: &Path) -> Option<&HashMap<usize, u64>> {
+        if let Some(counts) = fs::canonicalize(file).ok().and_then(|canonical| self.files.get(&canonical)) {
+            return Some(counts);
+        }
+        let relative = file.strip_prefix(".").unwrap_or(file);
+        let mut matches = self.files.iter().filter(|(recorded, _)| recorded.ends_with(relative));
+        match (matches.next(), matches.next()) {
+            (Some((_, counts)), None) => Some(counts),
+            _ => None,
+        }
+    }
+}
+
+// A line's count is the highest of the region it starts inside and the
+// regions that begin on it, as `llvm-cov` itself reports line coverage.
+fn llvm_line_counts(mut segments: Vec<Segment>) -> HashMap<usize, u64> {
+    segments.sort_by_key(|segment| segment.line);
+    let mut counts = HashMap::new();
+    let mut wrapped: Option<&Segment> = None;
+    let mut next = 0;
+    let last_line = segments.last().map_or(0, |segment| segment.line);
+    for line in 1..=last_line {
+        let starts = segments[next..].iter().take_while(|segment| segment.line == line).count();
+        let on_line = &segments[next..next + starts];
+        next += starts;
+
+        let mut count = wrapped.filter(|segment| segment.has_count && !segment.is_gap).map(|segment| segment.count);
+        for segment in on_line.iter().filter(|s| s.has_count && s.is_region_entry && !s.is_gap) {
+            count = Some(count.unwrap_or(0).max(segment.count));
+        }
+        if let Some(count) = count {
+            counts.insert(line, count);
+        }
+        if let Some(last) = on_line.last() {
+            wrapped = Some(last);
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_report_formats_yield_uncovered_lines() {
+        let llvm = r#"{"data": [{"files": [{"filename": "/work/project/src/lib.rs", "segments": [
+            [1, 40, 3, true, true, false],
+            [3, 13, 0, true, true, false],
+            [6, 6, 3, true, false, false],
+            [7, 2, 0, false, false, false]
+        ]}]}]}"#;
+        let coverage = CoverageMap::parse(llvm).unwrap();
+        assert_eq!(coverage.uncovered_lines(Path::new("src/lib.rs")), BTreeSet::from([4, 5, 6]));
+        assert!(coverage.uncovered_lines(Path::new("src/main.rs")).is_empty());
+
+        let tarpaulin = r#"{"files": [{"path": ["/", "work", "project", "src", "lib.rs"], "traces": [
+            {"line": 2, "address": [], "length": 1, "stats": {"Line": 0}},
+            {"line": 4, "address": [], "length": 1, "stats": {"Line": 5}}
+        ]}]}"#;
+        let coverage = CoverageMap::parse(tarpaulin).unwrap();
+        assert_eq!(coverage.uncovered_lines(Path::new("./src/lib.rs")), BTreeSet::from([2]));
+
+        assert!(CoverageMap::parse(r#"{"lines": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_canonical_path_wins_and_ambiguous_suffixes_are_ignored() {
+        let dir = PathBuf::from(format!("target/coverage_test_{}/src", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("lib.rs");
+        fs::write(&file, "").unwrap();
+        let canonical = fs::canonicalize(&file).unwrap();
+        let elsewhere = Path::new("/elsewhere").join(&file);
+        let report = serde_json::json!({"files": [
+            {"path": ["/", "work", "crate_a", "src", "lib.rs"], "traces": [{"line": 1, "stats": {"Line": 0}}]},
+            {"path": ["/", "work", "crate_b", "src", "lib.rs"], "traces": [{"line": 2, "stats": {"Line": 0}}]},
+            {"path": [elsewhere], "traces": [{"line": 3, "stats": {"Line": 0}}]},
+            {"path": [canonical], "traces": [{"line": 4, "stats": {"Line": 0}}]}
+        ]});
+        let coverage = CoverageMap::parse(&report.to_string()).unwrap();
+
+        assert_eq!(coverage.uncovered_lines(&file), BTreeSet::from([4]));
+        assert!(coverage.uncovered_lines(Path::new("src/lib.rs")).is_empty());
+        assert_eq!(coverage.uncovered_lines(Path::new("crate_b/src/lib.rs")), BTreeSet::from([2]));
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
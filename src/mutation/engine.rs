@@ -1,58 +1,263 @@
+use crate::mutation::error::MutationError;
 use crate::mutation::logger::MutationLogger;
 use crate::mutation::{
-    analyzer::CodeAnalyzer,
+    analyzer::{CodeAnalyzer, drop_duplicate_mutants},
+    ast_mutator::AstMutator,
+    coverage::CoverageMap,
+    hooks::SurvivorHook,
     mutators::CodeMutator,
-    runner::MutationRunner,
-    types::{MutationCandidate, MutationReport, MutationResult, MutationTestConfig, TestOutcome},
+    runner::{MutationRunner, TestRunner},
+    types::{
+        MutationCandidate, MutationReport, MutationResult, MutationTestConfig, MutationType, SampleStrategy,
+        SamplingSummary, TestOutcome,
+    },
 };
-use std::time::Instant;
-use tracing::{info, warn};
-use rayon::prelude::*; 
+use futures::stream::{FuturesOrdered, StreamExt};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, mpsc};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Live counters for a run in progress, shared with whoever reports on it.
+#[derive(Debug, Default)]
+pub struct RunProgress {
+    pub total: AtomicUsize,
+    pub completed: AtomicUsize,
+}
+
+/// Where the last run spent its time. `test_execution` is summed across
+/// mutants, so `orchestration` (the rest of the mutation phase) is only exact
+/// when `parallel_jobs` is 1.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(dead_code)]
+pub struct RunTimings {
+    pub discovery: Duration,
+    pub orchestration: Duration,
+    pub test_execution: Duration,
+}
 
 pub struct MutationEngine {
     analyzer: CodeAnalyzer,
     mutator: CodeMutator,
-    runner: MutationRunner,
+    runner: Box<dyn TestRunner>,
     config: MutationTestConfig,
+    progress: Arc<RunProgress>,
+    timings: Mutex<RunTimings>,
+    test_nanos: AtomicU64,
+    survivor_hook: Option<SurvivorHook>,
+    result_sender: Option<mpsc::UnboundedSender<MutationResult>>,
+    mutant_slots: Option<Arc<Semaphore>>,
 }
 
 #[allow(dead_code)]
 impl MutationEngine {
     pub fn new(config: MutationTestConfig) -> Self {
-        let test_command = config.test_command.clone();
-        let timeout = config.timeout_seconds;
-
         Self {
             analyzer: CodeAnalyzer::new(config.clone()),
             mutator: CodeMutator::new(),
-            runner: MutationRunner::new(timeout, test_command),
+            runner: Box::new(Self::build_runner(&config)),
             config,
+            progress: Arc::new(RunProgress::default()),
+            timings: Mutex::new(RunTimings::default()),
+            test_nanos: AtomicU64::new(0),
+            survivor_hook: None,
+            result_sender: None,
+            mutant_slots: None,
         }
     }
 
-    pub async fn run_mutation_testing(&self, source_code: &str) -> Result<MutationReport, String> {
+    /// An engine for mutation-testing `file`. With `project_root` set,
+    /// mutants are tested in a copy of that project, so `file` must lie
+    /// inside it. With `coverage_file` set, the file's uncovered lines are
+    /// looked up in it.
+    pub fn for_file(config: MutationTestConfig, file: &Path) -> Result<Self, String> {
+        let coverage = match &config.coverage_file {
+            Some(coverage_file) => Some(CoverageMap::load(Path::new(coverage_file))?),
+            None => None,
+        };
+        Self::for_file_with_coverage(config, file, coverage.as_ref())
+    }
+
+    /// Like [`MutationEngine::for_file`], with the coverage report already
+    /// loaded, so a run over many files reads it only once.
+    pub fn for_file_with_coverage(
+        config: MutationTestConfig,
+        file: &Path,
+        coverage: Option<&CoverageMap>,
+    ) -> Result<Self, String> {
+        let Some(root) = &config.project_root else {
+            return Ok(Self::new(config).with_coverage_of(file, coverage));
+        };
+        let root = fs::canonicalize(root).map_err(|e| format!("Invalid project_root {}: {}", root, e))?;
+        let path = fs::canonicalize(file).map_err(|e| format!("Failed to resolve {}: {}", file.display(), e))?;
+        let relative = path
+            .strip_prefix(&root)
+            .map_err(|_| format!("{} is not inside project_root {}", file.display(), root.display()))?
+            .to_path_buf();
+
+        let mut engine = Self::new(config).with_coverage_of(file, coverage);
+        engine.runner = Box::new(Self::build_runner(&engine.config).with_project(&root, &relative));
+        Ok(engine)
+    }
+
+    // With coverage, candidates on lines of `file` that no test executes are
+    // dropped.
+    fn with_coverage_of(mut self, file: &Path, coverage: Option<&CoverageMap>) -> Self {
+        if let Some(coverage) = coverage {
+            self.analyzer = CodeAnalyzer::new(self.config.clone()).with_uncovered_lines(coverage.uncovered_lines(file));
+        }
+        self
+    }
+
+    fn build_runner(config: &MutationTestConfig) -> MutationRunner {
+        MutationRunner::new(config.timeout_seconds, config.test_command.clone())
+            .with_compile_limit(config.max_concurrent_compiles)
+            .with_crate(&config.crate_name, &config.crate_edition)
+            .with_cap_lints(config.cap_lints)
+            .with_incremental_workspace(config.incremental_workspace)
+            .with_shared_target_dir(config.shared_target_dir)
+            .with_timeout_multiplier(config.timeout_multiplier)
+            .with_flaky_retries(config.flaky_retries)
+    }
+
+    pub fn with_test_runner(mut self, runner: impl TestRunner + 'static) -> Self {
+        self.runner = Box::new(runner);
+        self
+    }
+
+    /// Runs `hook` for each survivor as soon as its test run finishes.
+    pub fn with_survivor_hook(mut self, hook: SurvivorHook) -> Self {
+        self.survivor_hook = Some(hook);
+        self
+    }
+
+    /// Sends every result to `sender` as soon as its candidate finishes, in
+    /// candidate order.
+    pub fn with_result_sender(mut self, sender: mpsc::UnboundedSender<MutationResult>) -> Self {
+        self.result_sender = Some(sender);
+        self
+    }
+
+    /// Takes a permit from `slots` for each mutant's test run, so engines
+    /// sharing it never run more mutants between them than it has permits,
+    /// e.g. when several files are tested at once.
+    pub fn with_mutant_slots(mut self, slots: Arc<Semaphore>) -> Self {
+        self.mutant_slots = Some(slots);
+        self
+    }
+
+    pub fn last_timings(&self) -> RunTimings {
+        *self.timings.lock().unwrap()
+    }
+
+    pub fn progress(&self) -> Arc<RunProgress> {
+        self.progress.clone()
+    }
+
+    pub async fn run_mutation_testing(&self, source_code: &str) -> Result<MutationReport, MutationError> {
+        self.run_mutation_testing_until_cancelled(source_code, &CancellationToken::new()).await
+    }
+
+    /// Like [`Self::run_mutation_testing`], but stops once `cancel` fires:
+    /// no further candidates are dispatched, in-flight test processes are
+    /// killed and the run ends with [`MutationError::Cancelled`].
+    pub async fn run_mutation_testing_until_cancelled(
+        &self,
+        source_code: &str,
+        cancel: &CancellationToken,
+    ) -> Result<MutationReport, MutationError> {
         info!("Starting mutation testing");
         let start_time = Instant::now();
 
         self.runner.validate_test_setup(source_code).await?;
         info!("Test setup validation passed");
 
-        let candidates = self.analyzer.find_mutation_candidates(source_code);
+        let discovery_start = Instant::now();
+        let (mut candidates, uncovered_mutations, capped_mutations) = self.discover_candidates(source_code);
         info!("Found {} mutation candidates", candidates.len());
 
+        let mut sampling = None;
+        if let Some(max_mutations) = self.config.max_total_mutations {
+            let total_candidates = candidates.len();
+            let total_mutants: usize = candidates.iter().map(|c| c.suggested_mutations.len()).sum();
+            candidates =
+                sample_candidates(candidates, max_mutations, self.config.sample_strategy, self.config.shuffle_seed);
+            if candidates.len() < total_candidates {
+                info!(
+                    "Sampled {} of {} candidates ({:?}) to stay within {} mutations",
+                    candidates.len(),
+                    total_candidates,
+                    self.config.sample_strategy,
+                    max_mutations
+                );
+                sampling = Some(SamplingSummary {
+                    strategy: self.config.sample_strategy,
+                    sampled_candidates: candidates.len(),
+                    total_candidates,
+                    sampled_out_mutations: total_mutants
+                        - candidates.iter().map(|c| c.suggested_mutations.len()).sum::<usize>(),
+                });
+            }
+        }
+
+        if let Some(seed) = self.config.shuffle_seed {
+            shuffle_candidates(&mut candidates, seed);
+            info!("Shuffled candidate order with seed {}", seed);
+        }
+        let discovery = discovery_start.elapsed();
+        *self.timings.lock().unwrap() = RunTimings { discovery, ..Default::default() };
+
         if candidates.is_empty() {
             warn!("No mutation candidates found in source code");
-            return Ok(MutationReport::new());
+            return Ok(MutationReport { capped_mutations, ..MutationReport::new() }.with_uncovered(uncovered_mutations));
         }
 
-        let mut report = MutationReport::new();
+        let mut report =
+            MutationReport { sampling, capped_mutations, ..MutationReport::new() }.with_uncovered(uncovered_mutations);
+        let total = candidates.iter().map(|c| c.suggested_mutations.len()).sum();
+        self.progress.total.store(total, Ordering::Relaxed);
+        self.progress.completed.store(0, Ordering::Relaxed);
+        self.test_nanos.store(0, Ordering::Relaxed);
+        let mutation_start = Instant::now();
 
-        let results: Vec<Vec<MutationResult>> = candidates
-            .par_iter()
-            .map(|candidate| {
-                tokio::runtime::Handle::current().block_on(self.process_candidate(source_code, candidate))
-            })
-            .collect();
+        // Up to `threads` candidates are in flight on the runtime at once.
+        // `FuturesOrdered` yields them in candidate order, so results are
+        // aggregated exactly as a sequential run would.
+        let threads = self.config.effective_threads();
+        let mut pending = candidates.iter();
+        let mut in_flight = FuturesOrdered::new();
+        for candidate in pending.by_ref().take(threads) {
+            in_flight.push_back(self.process_candidate(source_code, candidate));
+        }
+        let mut results: Vec<Vec<MutationResult>> = Vec::with_capacity(candidates.len());
+        loop {
+            // Dropping `in_flight` kills the test processes still running.
+            let mutation_results = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    warn!("Mutation run cancelled with {} candidate(s) left", in_flight.len() + pending.len());
+                    return Err(MutationError::Cancelled);
+                }
+                next = in_flight.next() => match next {
+                    Some(mutation_results) => mutation_results,
+                    None => break,
+                },
+            };
+            if let Some(sender) = &self.result_sender {
+                for result in &mutation_results {
+                    let _ = sender.send(result.clone());
+                }
+            }
+            results.push(mutation_results);
+            if let Some(candidate) = pending.next() {
+                in_flight.push_back(self.process_candidate(source_code, candidate));
+            }
+        }
 
         for mutation_results in results {
             for result in mutation_results {
@@ -60,12 +265,19 @@ impl MutationEngine {
             }
         }
 
-        let total_time = start_time.elapsed();
-        report.execution_time_seconds = total_time.as_secs_f64();
+        let test_execution = Duration::from_nanos(self.test_nanos.load(Ordering::Relaxed));
+        *self.timings.lock().unwrap() = RunTimings {
+            discovery,
+            orchestration: mutation_start.elapsed().saturating_sub(test_execution),
+            test_execution,
+        };
+
+        report.total_wall_time_seconds = start_time.elapsed().as_secs_f64();
 
         info!(
-            "Mutation testing completed in {:.2}s. Score: {:.1}% ({}/{} killed)",
-            report.execution_time_seconds,
+            "Mutation testing completed in {:.2}s ({:.2}s of mutant runs). Score: {:.1}% ({}/{} killed)",
+            report.total_wall_time_seconds,
+            report.total_cpu_time_seconds,
             report.mutation_score,
             report.killed_mutations,
             report.total_mutations
@@ -74,6 +286,31 @@ impl MutationEngine {
         Ok(report)
     }
 
+    /// Line-scanned candidates, merged with a full syntax-tree pass when
+    /// `ast_mutations_enabled` is set, along with the numbers of mutants
+    /// dropped for being on uncovered lines and for exceeding
+    /// `max_mutations_per_line`.
+    fn discover_candidates(&self, source_code: &str) -> (Vec<MutationCandidate>, usize, usize) {
+        let mut candidates = self.analyzer.find_mutation_candidates(source_code);
+        if self.config.ast_mutations_enabled {
+            match AstMutator::new().find_ast_mutations(source_code) {
+                Ok(found) => {
+                    candidates = merge_candidates(candidates, self.analyzer.filter_extra_candidates(source_code, found))
+                }
+                Err(e) => warn!("Skipping syntax-tree candidates: {}", e),
+            }
+        }
+        let uncovered = self.analyzer.drop_uncovered(&mut candidates);
+        if uncovered > 0 {
+            info!("Skipped {} mutants on lines no test covers", uncovered);
+        }
+        let capped = limit_candidates_per_line(&mut candidates, self.config.max_mutations_per_line, self.config.shuffle_seed);
+        if capped > 0 {
+            info!("Skipped {} mutants on lines with more than {} candidates", capped, self.config.max_mutations_per_line);
+        }
+        (candidates, uncovered, capped)
+    }
+
     async fn process_candidate(
         &self,
         source_code: &str,
@@ -96,15 +333,29 @@ impl MutationEngine {
                 .apply_mutation(source_code, candidate, mutation)
             {
                 Ok(mutated_code) => {
-                    MutationLogger::info(&format!(
+                    MutationLogger::step(&format!(
                         "Testing mutated code: {}",
                         Self::shorten_code(&mutated_code)
                     ));
-                    let test_result = self.runner.run_tests_for_mutation(&mutated_code).await;
+                    let slot = match &self.mutant_slots {
+                        Some(slots) => Some(slots.acquire().await.expect("mutant slots are never closed")),
+                        None => None,
+                    };
+                    let test_start = Instant::now();
+                    let test_result = if self.config.incremental_workspace
+                        && candidate.mutation_type.is_body_only()
+                    {
+                        self.runner.run_tests_for_body_mutation(&mutated_code).await
+                    } else {
+                        self.runner.run_tests_for_mutation(&mutated_code).await
+                    };
+                    drop(slot);
+                    self.test_nanos.fetch_add(test_start.elapsed().as_nanos() as u64, Ordering::Relaxed);
                     let execution_time = start_time.elapsed().as_millis() as u64;
-                    let test_outcome: TestOutcome = test_result.clone().into();
+                    let error_message = test_result.error_message();
+                    let test_outcome: TestOutcome = test_result.into();
 
-                    MutationLogger::info(&format!(
+                    MutationLogger::step(&format!(
                         "Test outcome for mutation at line {}, col {}: {:?} (Execution time: {} ms)",
                         candidate.line, candidate.column, test_outcome, execution_time
                     ));
@@ -115,19 +366,23 @@ impl MutationEngine {
                         None
                     };
 
-                    results.push(MutationResult {
+                    let result = MutationResult {
                         candidate: candidate.clone(),
                         mutated_code,
                         test_result: test_outcome.clone(),
                         execution_time_ms: execution_time,
-                        error_message: None,
+                        error_message,
                         killing_tests,
                         suggested_improvement: if matches!(test_outcome, TestOutcome::Survived) {
-                            Some("Add or improve tests to catch this mutation (e.g., assert on edge cases or logic).".to_string())
+                            Some(Self::suggest_improvement(candidate))
                         } else {
                             None
                         },
-                    });
+                    };
+                    if let Some(hook) = &self.survivor_hook {
+                        hook.notify_async(&result).await;
+                    }
+                    results.push(result);
                 }
                 Err(error) => {
                     MutationLogger::error(&format!(
@@ -140,23 +395,88 @@ impl MutationEngine {
                         mutated_code: String::new(),
                         test_result: TestOutcome::Error,
                         execution_time_ms: execution_time,
-                        error_message: Some(error),
+                        error_message: Some(error.to_string()),
                         killing_tests: None,
                         suggested_improvement: None,
                     });
                 }
             }
+            self.progress.completed.fetch_add(1, Ordering::Relaxed);
         }
 
         results
     }
 
+    pub fn suggest_improvement(candidate: &MutationCandidate) -> String {
+        match candidate.mutation_type {
+            MutationType::PanicMacro if candidate.original_code.starts_with("panic!") => {
+                "This panic guard never fires under test: add a #[should_panic] test for the invariant, or drop the guard if the condition cannot occur.".to_string()
+            }
+            MutationType::PanicMacro => format!(
+                "No test reaches `{}`: this branch is either dead code or an untested invariant.",
+                candidate.original_code
+            ),
+            MutationType::FieldSwap => format!(
+                "Swapping fields in `{}` went unnoticed: compare against values whose fields differ.",
+                candidate.original_code
+            ),
+            MutationType::OverflowMethod => format!(
+                "Changing the overflow behaviour of `{}` went unnoticed: test with operands at the type's limits.",
+                candidate.original_code
+            ),
+            MutationType::AssignmentOperator => format!(
+                "Changing `{}` went unnoticed: assert on the variable's value after the update.",
+                candidate.original_code
+            ),
+            MutationType::IncrementDecrement => format!(
+                "Stepping the other way at `{}` went unnoticed: assert on the exact count or index after the step.",
+                candidate.original_code
+            ),
+            MutationType::ConditionalBoundary => format!(
+                "Moving the boundary of `{}` by one went unnoticed: test with values exactly at the boundary.",
+                candidate.original_code
+            ),
+            MutationType::ReturnValue => format!(
+                "Returning a different value than `{}` went unnoticed: assert on what the function returns.",
+                Self::shorten_code(&candidate.original_code)
+            ),
+            MutationType::OptionalUnwrap => format!(
+                "Replacing `{}` went unnoticed: add a test where the value is missing or an error.",
+                candidate.original_code
+            ),
+            MutationType::BreakContinueReplacement => format!(
+                "Swapping `{}` for its counterpart went unnoticed: test a loop that ends early or skips an item.",
+                candidate.original_code
+            ),
+            MutationType::SwitchCase => format!(
+                "Rearranging the arms of `{}` went unnoticed: test an input for each arm and assert on its result.",
+                candidate.original_code
+            ),
+            MutationType::StatementDeletion => format!(
+                "Deleting `{}` went unnoticed: assert on the effect this statement has.",
+                Self::shorten_code(&candidate.original_code)
+            ),
+            MutationType::ClosurePredicate => format!(
+                "A constant predicate in place of `{}` went unnoticed: assert on which items the iterator keeps.",
+                candidate.original_code
+            ),
+            MutationType::StringLiteral => format!(
+                "Changing {} went unnoticed: assert on the exact text where it is used.",
+                candidate.original_code
+            ),
+            MutationType::DefaultValue => format!(
+                "Changing the fallback in `{}` went unnoticed: add a test where the value is missing.",
+                candidate.original_code
+            ),
+            _ => "Add or improve tests to catch this mutation (e.g., assert on edge cases or logic).".to_string(),
+        }
+    }
+
     fn shorten_code(code: &str) -> String {
         let trimmed = code.trim();
-        if trimmed.len() > 60 {
-            format!("{}...", &trimmed[..60])
-        } else {
-            trimmed.to_string()
+        match trimmed.char_indices().nth(60) {
+            Some((end, _)) => format!("{}...", &trimmed[..end]),
+            None => trimmed.to_string(),
         }
     }
 
@@ -167,13 +487,13 @@ impl MutationEngine {
     pub fn update_config(&mut self, config: MutationTestConfig) {
         self.config = config.clone();
         self.analyzer = CodeAnalyzer::new(config.clone());
-        self.runner = MutationRunner::new(config.timeout_seconds, config.test_command.clone());
+        self.runner = Box::new(Self::build_runner(&config));
     }
 
-    pub async fn dry_run(&self, source_code: &str) -> Result<Vec<MutationCandidate>, String> {
+    pub async fn dry_run(&self, source_code: &str) -> Result<Vec<MutationCandidate>, MutationError> {
         info!("Running dry run to find mutation candidates");
 
-        let candidates = self.analyzer.find_mutation_candidates(source_code);
+        let (candidates, _, _) = self.discover_candidates(source_code);
 
         info!("Dry run found {} potential mutations:", candidates.len());
         for (index, candidate) in candidates.iter().enumerate() {
@@ -196,7 +516,7 @@ impl MutationEngine {
         source_code: &str,
         candidate: &MutationCandidate,
         mutation: &str,
-    ) -> Result<MutationResult, String> {
+    ) -> Result<MutationResult, MutationError> {
         let start_time = Instant::now();
 
         let mutated_code = self
@@ -204,6 +524,7 @@ impl MutationEngine {
             .apply_mutation(source_code, candidate, mutation)?;
 
         let test_result_runner = self.runner.run_tests_for_mutation(&mutated_code).await;
+        let error_message = test_result_runner.error_message();
         let test_result: TestOutcome = test_result_runner.into();
 
         let execution_time = start_time.elapsed().as_millis() as u64;
@@ -213,23 +534,118 @@ impl MutationEngine {
             mutated_code,
             test_result: test_result.clone(),
             execution_time_ms: execution_time,
-            error_message: None,
+            error_message,
             killing_tests: match &test_result {
                 TestOutcome::Killed { killing_tests } => Some(killing_tests.clone()),
                 _ => None,
             },
             suggested_improvement: match test_result {
-                TestOutcome::Survived => Some("Add or improve tests to catch this mutation (e.g., assert on edge cases or logic).".to_string()),
+                TestOutcome::Survived => Some(Self::suggest_improvement(candidate)),
                 _ => None,
             },
         })
     }
 }
 
+/// Keeps one candidate per `(line, column, mutation_type, original_code)`,
+/// preferring the syntax-tree one since its byte range comes from the
+/// parser, and each mutant once across both. The result is ordered by
+/// location.
+pub fn merge_candidates(line_candidates: Vec<MutationCandidate>, ast_candidates: Vec<MutationCandidate>) -> Vec<MutationCandidate> {
+    let mut seen = HashSet::new();
+    let mut merged: Vec<MutationCandidate> = ast_candidates
+        .into_iter()
+        .chain(line_candidates)
+        .filter(|c| seen.insert((c.line, c.column, c.mutation_type.clone(), c.original_code.clone())))
+        .collect();
+    merged.sort_by(|a, b| (a.line, a.column, &a.mutation_type).cmp(&(b.line, b.column, &b.mutation_type)));
+    drop_duplicate_mutants(&mut merged);
+    merged
+}
+
+/// Keeps whole candidates, in source order, until they add up to at most
+/// `max_mutations` mutants. `First` takes them top to bottom; `Random` in an
+/// order drawn with `seed`; `Stratified` does the same within each mutation
+/// type, giving each type its share of the budget.
+pub fn sample_candidates(
+    candidates: Vec<MutationCandidate>,
+    max_mutations: usize,
+    strategy: SampleStrategy,
+    seed: Option<u64>,
+) -> Vec<MutationCandidate> {
+    let total: usize = candidates.iter().map(|c| c.suggested_mutations.len()).sum();
+    if total <= max_mutations {
+        return candidates;
+    }
+
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    if strategy != SampleStrategy::First {
+        seed.map_or_else(fastrand::Rng::new, fastrand::Rng::with_seed).shuffle(&mut order);
+    }
+
+    let mut keep = vec![false; candidates.len()];
+    let mut fill = |indices: &[usize], mut budget: usize| {
+        for &i in indices {
+            let mutants = candidates[i].suggested_mutations.len();
+            if mutants <= budget {
+                keep[i] = true;
+                budget -= mutants;
+            }
+        }
+    };
+    if strategy == SampleStrategy::Stratified {
+        let mut by_type: BTreeMap<&MutationType, Vec<usize>> = BTreeMap::new();
+        for &i in &order {
+            by_type.entry(&candidates[i].mutation_type).or_default().push(i);
+        }
+        for indices in by_type.values() {
+            let mutants: usize = indices.iter().map(|&i| candidates[i].suggested_mutations.len()).sum();
+            fill(indices, max_mutations * mutants / total);
+        }
+    } else {
+        fill(&order, max_mutations);
+    }
+
+    candidates.into_iter().zip(keep).filter_map(|(candidate, keep)| keep.then_some(candidate)).collect()
+}
+
+/// Keeps at most `limit` candidates per line: the first ones, or a sample
+/// drawn with `seed` when one is set. Expects candidates sorted by line and
+/// keeps that order. Returns the number of mutants dropped.
+pub fn limit_candidates_per_line(candidates: &mut Vec<MutationCandidate>, limit: usize, seed: Option<u64>) -> usize {
+    let mut rng = seed.map(fastrand::Rng::with_seed);
+    let mut kept = Vec::with_capacity(candidates.len());
+    let mut dropped = 0;
+    for line in candidates.chunk_by(|a, b| a.line == b.line) {
+        if line.len() <= limit {
+            kept.extend_from_slice(line);
+            continue;
+        }
+        let mut picked: Vec<usize> = (0..line.len()).collect();
+        if let Some(rng) = &mut rng {
+            rng.shuffle(&mut picked);
+        }
+        let (keep, drop) = picked.split_at_mut(limit);
+        keep.sort_unstable();
+        dropped += drop.iter().map(|&i| line[i].suggested_mutations.len()).sum::<usize>();
+        debug!("Keeping {} of {} candidates on line {}", limit, line.len(), line[0].line);
+        kept.extend(keep.iter().map(|&i| line[i].clone()));
+    }
+    *candidates = kept;
+    dropped
+}
+
+/// Reorders candidates deterministically so large files are sampled evenly
+/// rather than top-to-bottom. The same seed always yields the same order.
+pub fn shuffle_candidates(candidates: &mut [MutationCandidate], seed: u64) {
+    fastrand::Rng::with_seed(seed).shuffle(candidates);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mutation::types::MutationType;
+    use crate::mutation::reports::ReportGenerator;
+    use crate::mutation::types::ReportFormat;
 
     #[test]
     fn test_mutation_engine_creation() {
@@ -250,6 +666,7 @@ mod tests {
             report_format: Some(crate::mutation::types::ReportFormat::Console),
             report_output_path: None,
             ast_mutations_enabled: false,
+            ..Default::default()
         };
 
         let engine = MutationEngine::new(config);
@@ -257,6 +674,27 @@ mod tests {
         assert_eq!(engine.config.max_mutations_per_line, 100);
     }
 
+    #[tokio::test]
+    async fn test_filter_types_restrict_dry_run_candidates() {
+        let source = "pub fn check(a: i32, b: i32) -> bool {\n    a + b > 10\n}\n";
+        let mut config = MutationTestConfig::default();
+        config.apply_filter_types(Some(&[]));
+        assert_eq!(config.mutation_types, MutationTestConfig::default().mutation_types);
+
+        config.apply_filter_types(Some(&[MutationType::ArithmeticOperator]));
+        let candidates = MutationEngine::new(config).dry_run(source).await.unwrap();
+
+        assert!(!candidates.is_empty());
+        assert!(candidates.iter().all(|c| c.mutation_type == MutationType::ArithmeticOperator));
+    }
+
+    #[test]
+    fn test_shortened_code_ends_on_a_char_boundary() {
+        let code = "é".repeat(70);
+        assert_eq!(MutationEngine::shorten_code(&code), format!("{}...", "é".repeat(60)));
+        assert_eq!(MutationEngine::shorten_code("  a + b  "), "a + b");
+    }
+
     #[test]
     fn test_mutation_engine_config_update() {
         let mut engine = MutationEngine::new(MutationTestConfig::default());
@@ -275,6 +713,7 @@ mod tests {
             report_format: Some(crate::mutation::types::ReportFormat::JSON),
             report_output_path: Some("reports/".to_string()),
             ast_mutations_enabled: true,
+            ..Default::default()
         };
 
         engine.update_config(new_config);
@@ -298,6 +737,7 @@ mod tests {
             report_format: Some(crate::mutation::types::ReportFormat::Markdown),
             report_output_path: None,
             ast_mutations_enabled: false,
+            ..Default::default()
         };
 
         let engine = MutationEngine::new(config.clone());
@@ -318,4 +758,276 @@ mod tests {
         assert_eq!(config.max_mutations_per_line, 5);
         assert!(!config.mutation_types.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_single_thread_runs_candidates_in_order() {
+        let config = MutationTestConfig {
+            test_command: "true".to_string(),
+            mutation_types: vec![MutationType::ArithmeticOperator],
+            excluded_patterns: vec![],
+            parallel_jobs: Some(1),
+            ..Default::default()
+        };
+        let source = "fn calc(a: i32, b: i32) -> i32 {\n    a + b\n}\nfn diff(a: i32, b: i32) -> i32 {\n    a - b\n}\nfn prod(a: i32, b: i32) -> i32 {\n    a * b\n}\n#[test]\nfn it_works() {}\n";
+
+        let report = MutationEngine::new(config).run_mutation_testing(source).await.unwrap();
+
+        let lines: Vec<usize> = report.results.iter().map(|r| r.candidate.line).collect();
+        assert_eq!(lines.first(), Some(&2));
+        assert!(lines.windows(2).all(|w| w[0] <= w[1]));
+        assert!(lines.contains(&5) && lines.contains(&8));
+    }
+
+    #[tokio::test]
+    async fn test_candidates_run_concurrently_on_the_runtime() {
+        struct SlowRunner {
+            in_flight: AtomicUsize,
+            peak: Arc<AtomicUsize>,
+        }
+
+        #[async_trait::async_trait]
+        impl TestRunner for SlowRunner {
+            async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+                Ok(())
+            }
+
+            async fn run_tests_for_mutation(&self, _mutated_code: &str) -> crate::mutation::runner::TestOutcome {
+                let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(in_flight, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                crate::mutation::runner::TestOutcome::Survived
+            }
+        }
+
+        let config = MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator],
+            parallel_jobs: Some(2),
+            ..Default::default()
+        };
+        let source = "fn calc(a: i32, b: i32) -> i32 {\n    a + b\n}\nfn diff(a: i32, b: i32) -> i32 {\n    a - b\n}\n#[test]\nfn it_works() {}\n";
+        let peak = Arc::new(AtomicUsize::new(0));
+        let runner = SlowRunner { in_flight: AtomicUsize::new(0), peak: peak.clone() };
+
+        let engine = MutationEngine::new(config).with_test_runner(runner);
+        let report = engine.run_mutation_testing(source).await.unwrap();
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(report.total_mutations, report.survived_mutations);
+        assert!(
+            report.total_wall_time_seconds <= report.total_cpu_time_seconds,
+            "wall {}s should not exceed the {}s spent in overlapping mutant runs",
+            report.total_wall_time_seconds,
+            report.total_cpu_time_seconds
+        );
+        let lines: Vec<usize> = report.results.iter().map(|r| r.candidate.line).collect();
+        assert!(lines.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[tokio::test]
+    async fn test_engines_sharing_mutant_slots_stay_within_them() {
+        struct SlowRunner {
+            in_flight: Arc<AtomicUsize>,
+            peak: Arc<AtomicUsize>,
+        }
+
+        #[async_trait::async_trait]
+        impl TestRunner for SlowRunner {
+            async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+                Ok(())
+            }
+
+            async fn run_tests_for_mutation(&self, _mutated_code: &str) -> crate::mutation::runner::TestOutcome {
+                let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(in_flight, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                crate::mutation::runner::TestOutcome::Survived
+            }
+        }
+
+        let config = MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator],
+            parallel_jobs: Some(3),
+            ..Default::default()
+        };
+        let source = "fn calc(a: i32, b: i32) -> i32 {\n    a + b\n}\nfn diff(a: i32, b: i32) -> i32 {\n    a - b\n}\nfn prod(a: i32, b: i32) -> i32 {\n    a * b\n}\n#[test]\nfn it_works() {}\n";
+        let (in_flight, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let slots = Arc::new(Semaphore::new(3));
+        let engine = || {
+            MutationEngine::new(config.clone())
+                .with_test_runner(SlowRunner { in_flight: in_flight.clone(), peak: peak.clone() })
+                .with_mutant_slots(slots.clone())
+        };
+        let (first, second) = (engine(), engine());
+
+        let (first_report, second_report) =
+            tokio::join!(first.run_mutation_testing(source), second.run_mutation_testing(source));
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        let (first_report, second_report) = (first_report.unwrap(), second_report.unwrap());
+        assert_eq!(first.progress().completed.load(Ordering::Relaxed), first_report.total_mutations);
+        assert_eq!(second.progress().completed.load(Ordering::Relaxed), second_report.total_mutations);
+    }
+
+    #[tokio::test]
+    async fn test_mutants_over_the_per_line_limit_are_counted_in_the_report() {
+        struct SurvivingRunner;
+
+        #[async_trait::async_trait]
+        impl TestRunner for SurvivingRunner {
+            async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+                Ok(())
+            }
+
+            async fn run_tests_for_mutation(&self, _mutated_code: &str) -> crate::mutation::runner::TestOutcome {
+                crate::mutation::runner::TestOutcome::Survived
+            }
+        }
+
+        let source = "pub fn calc(a: i32, b: i32, c: i32) -> i32 {\n    a + b - c\n}\n#[test]\nfn it_works() {}\n";
+        let report = |max_mutations_per_line| {
+            let config = MutationTestConfig {
+                mutation_types: vec![MutationType::ArithmeticOperator],
+                max_mutations_per_line,
+                ..Default::default()
+            };
+            async move { MutationEngine::new(config).with_test_runner(SurvivingRunner).run_mutation_testing(source).await.unwrap() }
+        };
+
+        let all = report(5).await;
+        let capped = report(1).await;
+
+        assert_eq!(all.capped_mutations, 0);
+        assert!(capped.capped_mutations > 0);
+        assert_eq!(capped.total_mutations + capped.capped_mutations, all.total_mutations);
+        let console = ReportGenerator::new().generate_report(&capped, ReportFormat::Console, None).unwrap();
+        assert!(console.contains("Over max_mutations_per_line"));
+    }
+
+    #[tokio::test]
+    async fn test_candidates_found_by_both_analyzers_are_tested_once() {
+        #[derive(Default)]
+        struct RecordingRunner {
+            mutants: Arc<Mutex<Vec<String>>>,
+        }
+
+        #[async_trait::async_trait]
+        impl TestRunner for RecordingRunner {
+            async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+                Ok(())
+            }
+
+            async fn run_tests_for_mutation(&self, mutated_code: &str) -> crate::mutation::runner::TestOutcome {
+                self.mutants.lock().unwrap().push(mutated_code.to_string());
+                crate::mutation::runner::TestOutcome::Survived
+            }
+        }
+
+        let config = MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator, MutationType::RelationalOperator],
+            ast_mutations_enabled: true,
+            ..Default::default()
+        };
+        let source = "pub fn check(a: i32, b: i32) -> bool {\n    a + b > 10\n}\n";
+        let runner = RecordingRunner::default();
+        let mutants = runner.mutants.clone();
+
+        let engine = MutationEngine::new(config).with_test_runner(runner);
+        let line_only = engine.analyzer.find_mutation_candidates(source);
+        let report = engine.run_mutation_testing(source).await.unwrap();
+
+        let mutants = mutants.lock().unwrap();
+        assert_eq!(mutants.len(), report.total_mutations);
+        assert_eq!(mutants.iter().collect::<HashSet<_>>().len(), mutants.len());
+        let keys: HashSet<(usize, usize)> = report.results.iter().map(|r| (r.candidate.line, r.candidate.column)).collect();
+        assert_eq!(keys.len(), line_only.len());
+        assert!(report.results.iter().any(|r| r.candidate.original_code == ">" && r.mutated_code.contains("a + b >= 10")));
+    }
+
+    #[test]
+    fn test_seed_picks_the_same_candidates_per_line() {
+        let candidates: Vec<MutationCandidate> = [1, 1, 1, 1, 1, 2, 2]
+            .into_iter()
+            .enumerate()
+            .map(|(column, line)| MutationCandidate {
+                line,
+                column,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            })
+            .collect();
+        let limited = |seed: Option<u64>| {
+            let mut kept = candidates.clone();
+            let dropped = limit_candidates_per_line(&mut kept, 3, seed);
+            (kept.iter().map(|c| c.column).collect::<Vec<_>>(), dropped)
+        };
+
+        assert_eq!(limited(None), (vec![0, 1, 2, 5, 6], 2));
+        let (sampled, dropped) = limited(Some(9));
+        assert_eq!((sampled.len(), dropped), (5, 2));
+        assert!(sampled.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(limited(Some(9)).0, sampled);
+    }
+
+    #[test]
+    fn test_stratified_sampling_keeps_each_types_share() {
+        let candidate = |line: usize, mutation_type: MutationType| MutationCandidate {
+            line,
+            column: 1,
+            original_code: "+".to_string(),
+            mutation_type,
+            suggested_mutations: vec!["-".to_string()],
+            module_path: String::new(),
+            byte_start: None,
+            byte_end: None,
+        };
+        let candidates: Vec<MutationCandidate> = (1..=100)
+            .map(|line| match line % 10 {
+                0 => candidate(line, MutationType::LogicalOperator),
+                1..=3 => candidate(line, MutationType::RelationalOperator),
+                _ => candidate(line, MutationType::ArithmeticOperator),
+            })
+            .collect();
+
+        let sampled = sample_candidates(candidates.clone(), 40, SampleStrategy::Stratified, Some(3));
+        let share = |mutation_type: MutationType| sampled.iter().filter(|c| c.mutation_type == mutation_type).count();
+        assert!(sampled.len() <= 40);
+        assert!(share(MutationType::ArithmeticOperator).abs_diff(24) <= 1);
+        assert!(share(MutationType::RelationalOperator).abs_diff(12) <= 1);
+        assert!(share(MutationType::LogicalOperator).abs_diff(4) <= 1);
+        assert!(sampled.windows(2).all(|w| w[0].line < w[1].line));
+
+        let first = sample_candidates(candidates, 40, SampleStrategy::First, None);
+        assert_eq!(first.last().unwrap().line, 40);
+    }
+
+    #[test]
+    fn test_shuffle_candidates_is_reproducible() {
+        let candidates: Vec<MutationCandidate> = (1..=20)
+            .map(|line| MutationCandidate {
+                line,
+                column: 1,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            })
+            .collect();
+        let order = |seed| {
+            let mut shuffled = candidates.clone();
+            shuffle_candidates(&mut shuffled, seed);
+            shuffled.iter().map(|c| c.line).collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(42), order(42));
+        assert_ne!(order(42), order(7));
+        assert_ne!(order(42), (1..=20).collect::<Vec<_>>());
+    }
 }
@@ -0,0 +1,37 @@
+use crate::mutation::types::MutationType;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[allow(dead_code)]
+pub enum MutationError {
+    #[error("Failed to parse code as Rust AST: {0}")]
+    Parse(#[from] syn::Error),
+
+    #[error("Invalid line number: {0}")]
+    InvalidLine(usize),
+
+    #[error("'{original}' not found at position {position}")]
+    PositionNotFound { original: String, position: usize },
+
+    #[error("Mutation '{mutation}' is not in the suggested mutations list: {suggestions:?}")]
+    InvalidMutation { mutation: String, suggestions: Vec<String> },
+
+    #[error("Unsupported mutation type: {0:?}")]
+    UnsupportedType(MutationType),
+
+    #[error("Failed to apply AST mutation at line {line}, column {column}")]
+    NotApplied { line: usize, column: usize },
+
+    #[error("No test functions found in source code. Mutation testing requires tests to be effective.")]
+    NoTests,
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("Mutation run was cancelled")]
+    Cancelled,
+}
@@ -0,0 +1,120 @@
+use crate::mutation::analyzer::{CodeAnalyzer, enclosing_function};
+use crate::mutation::engine::MutationEngine;
+use crate::mutation::mutators::CodeMutator;
+use crate::mutation::types::{MutationReport, MutationTestConfig, mutation_fingerprint};
+
+/// Everything needed to triage one survivor: where it lives, what changed,
+/// which tests exercise the line and what kind of test would catch it.
+/// `prior` is an earlier report for the same file; without it no covering
+/// tests are known.
+#[allow(dead_code)]
+pub fn explain_survivor(
+    source_code: &str,
+    fingerprint: &str,
+    config: &MutationTestConfig,
+    prior: Option<&MutationReport>,
+) -> Result<String, String> {
+    let mutator = CodeMutator::new();
+    let (candidate, mutated_code) = CodeAnalyzer::new(config.clone())
+        .find_mutation_candidates(source_code)
+        .into_iter()
+        .find_map(|candidate| {
+            candidate.suggested_mutations.iter().find_map(|mutation| {
+                let mutated = mutator.apply_mutation(source_code, &candidate, mutation).ok()?;
+                (mutation_fingerprint(&candidate, &mutated) == fingerprint).then(|| (candidate.clone(), mutated))
+            })
+        })
+        .ok_or_else(|| format!("No mutant with fingerprint {} in this file; has the source changed since the report?", fingerprint))?;
+
+    let mut covering_tests: Vec<String> = Vec::new();
+    for result in prior.iter().flat_map(|report| &report.results) {
+        if result.candidate.line == candidate.line {
+            for test in result.killing_tests.iter().flatten() {
+                if !covering_tests.contains(test) {
+                    covering_tests.push(test.clone());
+                }
+            }
+        }
+    }
+
+    let mut output = format!("Survivor {}\n", fingerprint);
+    output.push_str(&format!(
+        "Function: {}\n",
+        enclosing_function(source_code, candidate.line).as_deref().unwrap_or("<top level>")
+    ));
+    output.push_str(&format!(
+        "Mutation: {:?} '{}' at line {}, col {}\n\n",
+        candidate.mutation_type, candidate.original_code, candidate.line, candidate.column
+    ));
+    output.push_str(&line_diff(source_code, &mutated_code));
+    output.push('\n');
+    match (prior, covering_tests.is_empty()) {
+        (None, _) => output.push_str(&format!("Tests covering line {}: unknown (pass --report)\n", candidate.line)),
+        (Some(_), true) => output.push_str(&format!("Tests covering line {}: none killed a mutant here\n", candidate.line)),
+        (Some(_), false) => output.push_str(&format!("Tests covering line {}: {}\n", candidate.line, covering_tests.join(", "))),
+    }
+    output.push_str(&format!("Suggested test: {}\n", MutationEngine::suggest_improvement(&candidate)));
+    Ok(output)
+}
+
+/// The changed block between two versions, as one `@@` hunk with `-`/`+` lines.
+fn line_diff(original: &str, mutated: &str) -> String {
+    let before: Vec<&str> = original.lines().collect();
+    let after: Vec<&str> = mutated.lines().collect();
+    let prefix = before.iter().zip(&after).take_while(|(a, b)| a == b).count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut diff = format!("@@ line {} @@\n", prefix + 1);
+    for line in &before[prefix..before.len() - suffix] {
+        diff.push_str(&format!("-{}\n", line));
+    }
+    for line in &after[prefix..after.len() - suffix] {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutation::types::{MutationResult, MutationType, TestOutcome};
+
+    #[test]
+    fn test_explanation_names_function_and_shows_diff() {
+        let source = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\n#[test]\nfn adds() {\n    assert_eq!(add(0, 0), 0);\n}\n";
+        let config = MutationTestConfig {
+            mutation_types: vec![MutationType::ArithmeticOperator],
+            ..Default::default()
+        };
+        let candidate = CodeAnalyzer::new(config.clone()).find_mutation_candidates(source).remove(0);
+        let survivor = MutationResult {
+            mutated_code: CodeMutator::new().apply_mutation(source, &candidate, "-").unwrap(),
+            candidate: candidate.clone(),
+            test_result: TestOutcome::Survived,
+            execution_time_ms: 10,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        };
+        let mut prior = MutationReport::new();
+        prior.add_result(MutationResult {
+            mutated_code: CodeMutator::new().apply_mutation(source, &candidate, "*").unwrap(),
+            test_result: TestOutcome::Killed { killing_tests: vec!["adds".to_string()] },
+            killing_tests: Some(vec!["adds".to_string()]),
+            ..survivor.clone()
+        });
+
+        let explanation = explain_survivor(source, &survivor.fingerprint(), &config, Some(&prior)).unwrap();
+
+        assert!(explanation.contains("Function: add\n"));
+        assert!(explanation.contains("@@ line 2 @@\n-    a + b\n+    a - b\n"));
+        assert!(explanation.contains("Tests covering line 2: adds"));
+        assert!(explanation.contains("Suggested test:"));
+        assert!(explain_survivor(source, "0000000000000000", &config, None).is_err());
+    }
+}
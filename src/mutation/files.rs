@@ -0,0 +1,178 @@
+use crate::mutation::runner::MutationRunner;
+use crate::mutation::types::MutationTestConfig;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct FileSelection {
+    pub files: Vec<String>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Resolves the set of files a run would mutate: explicit paths and globs,
+/// the optional file list, minus excluded files/dirs and files without tests.
+#[allow(dead_code)]
+pub fn resolve_files(inputs: &[String], file_list: Option<&str>, config: &MutationTestConfig) -> Result<FileSelection, String> {
+    let mut requested = inputs.to_vec();
+    if let Some(list_path) = file_list {
+        let content = fs::read_to_string(list_path)
+            .map_err(|e| format!("Failed to read file list {}: {}", list_path, e))?;
+        requested.extend(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string),
+        );
+    }
+
+    let mut selection = FileSelection::default();
+    for entry in requested {
+        for path in expand_entry(&entry)? {
+            if selection.files.contains(&path) || selection.skipped.iter().any(|s| s.path == path) {
+                continue;
+            }
+            match exclusion_reason(&path, config) {
+                Some(reason) => selection.skipped.push(SkippedFile { path, reason }),
+                None => selection.files.push(path),
+            }
+        }
+    }
+
+    Ok(selection)
+}
+
+fn expand_entry(entry: &str) -> Result<Vec<String>, String> {
+    if !entry.contains(['*', '?', '[']) {
+        return Ok(vec![entry.to_string()]);
+    }
+
+    let paths = glob::glob(entry).map_err(|e| format!("Invalid glob pattern '{}': {}", entry, e))?;
+    let mut files: Vec<String> = paths
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Why `path` is left out of a run, or `None` if it is mutated.
+pub fn exclusion_reason(path: &str, config: &MutationTestConfig) -> Option<String> {
+    let file = Path::new(path);
+
+    if !config.allowed_files.is_empty() && !config.allowed_files.iter().any(|allowed| file.ends_with(allowed.as_str())) {
+        return Some("not in the mutation allowlist".to_string());
+    }
+
+    if let Some(excluded) = config.excluded_files.iter().find(|excluded| matches_excluded_file(file, excluded)) {
+        return Some(format!("matches excluded_files entry '{}'", excluded));
+    }
+
+    if let Some(dir) = config.excluded_dirs.iter().find(|dir| file.starts_with(dir.as_str())) {
+        return Some(format!("inside excluded directory '{}'", dir));
+    }
+
+    if let Some(limit) = config.max_file_size_bytes
+        && let Ok(metadata) = fs::metadata(file)
+        && metadata.len() > limit
+    {
+        return Some(format!("{} bytes exceeds max_file_size_bytes ({})", metadata.len(), limit));
+    }
+
+    // Unreadable files are kept so the run itself reports the read error.
+    match fs::read_to_string(file) {
+        Ok(source) if !MutationRunner::contains_tests(&source) => Some("no tests found".to_string()),
+        _ => None,
+    }
+}
+
+/// Whether `file` matches an `excluded_files` entry. Entries with glob
+/// characters (`src/generated/**`, `*.pb.rs`) are matched as patterns
+/// anywhere in the path; plain entries match a trailing path.
+fn matches_excluded_file(file: &Path, entry: &str) -> bool {
+    if !entry.contains(['*', '?', '[']) {
+        return file.ends_with(entry);
+    }
+    let matches = |pattern: &str| glob::Pattern::new(pattern).is_ok_and(|pattern| pattern.matches_path(file));
+    matches(entry) || (!entry.starts_with('/') && matches(&format!("**/{}", entry)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_excluded_files_are_absent_from_listing() {
+        let dir = tempdir().unwrap();
+        let with_tests = "pub fn f() {}\n#[cfg(test)]\nmod tests {}\n";
+        for name in ["keep.rs", "generated.rs", "no_tests.rs"] {
+            let content = if name == "no_tests.rs" { "pub fn f() {}\n" } else { with_tests };
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        fs::create_dir(dir.path().join("vendor")).unwrap();
+        fs::write(dir.path().join("vendor/dep.rs"), with_tests).unwrap();
+
+        let config = MutationTestConfig {
+            excluded_files: vec!["generated.rs".to_string()],
+            excluded_dirs: vec![dir.path().join("vendor").to_string_lossy().to_string()],
+            ..Default::default()
+        };
+        let inputs = vec![
+            dir.path().join("*.rs").to_string_lossy().to_string(),
+            dir.path().join("vendor/dep.rs").to_string_lossy().to_string(),
+        ];
+
+        let selection = resolve_files(&inputs, None, &config).unwrap();
+
+        let listed: Vec<&str> = selection.files.iter().map(|f| f.rsplit('/').next().unwrap()).collect();
+        assert_eq!(listed, vec!["keep.rs"]);
+        assert_eq!(selection.skipped.len(), 3);
+        assert!(selection.skipped.iter().any(|s| s.path.ends_with("no_tests.rs") && s.reason == "no tests found"));
+    }
+
+    #[test]
+    fn test_excluded_files_globs_skip_matching_files() {
+        let dir = tempdir().unwrap();
+        let with_tests = "pub fn f() {}\n#[cfg(test)]\nmod tests {}\n";
+        fs::create_dir_all(dir.path().join("src/generated")).unwrap();
+        for name in ["src/lib.rs", "src/api.pb.rs", "src/generated/schema.rs"] {
+            fs::write(dir.path().join(name), with_tests).unwrap();
+        }
+
+        let config = MutationTestConfig {
+            excluded_files: vec!["src/generated/**".to_string(), "*.pb.rs".to_string()],
+            ..Default::default()
+        };
+        let inputs = vec![dir.path().join("src/**/*.rs").to_string_lossy().to_string()];
+
+        let selection = resolve_files(&inputs, None, &config).unwrap();
+
+        assert_eq!(selection.files, vec![dir.path().join("src/lib.rs").to_string_lossy().to_string()]);
+        assert!(selection.skipped.iter().any(|s| s.path.ends_with("schema.rs") && s.reason == "matches excluded_files entry 'src/generated/**'"));
+        assert!(matches_excluded_file(Path::new("src/generated/schema.rs"), "src/generated/**"));
+        assert!(!matches_excluded_file(Path::new("src/lib.rs"), "*.pb.rs"));
+    }
+
+    #[test]
+    fn test_files_over_the_size_limit_are_skipped() {
+        let dir = tempdir().unwrap();
+        let with_tests = "pub fn f() {}\n#[cfg(test)]\nmod tests {}\n";
+        fs::write(dir.path().join("small.rs"), with_tests).unwrap();
+        fs::write(dir.path().join("generated.rs"), with_tests.repeat(100)).unwrap();
+
+        let config = MutationTestConfig { max_file_size_bytes: Some(1024), ..Default::default() };
+        let selection = resolve_files(&[dir.path().join("*.rs").to_string_lossy().to_string()], None, &config).unwrap();
+
+        assert_eq!(selection.files, vec![dir.path().join("small.rs").to_string_lossy().to_string()]);
+        assert_eq!(selection.skipped.len(), 1);
+        assert_eq!(selection.skipped[0].reason, format!("{} bytes exceeds max_file_size_bytes (1024)", with_tests.len() * 100));
+    }
+}
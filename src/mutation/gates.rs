@@ -0,0 +1,117 @@
+use crate::mutation::types::MutationReport;
+
+/// Process exit code when a run is rejected for having too many mutations
+/// end in an error outcome.
+pub const ERROR_RATE_EXIT_CODE: i32 = 3;
+
+/// Process exit code when the mutation score is below `--fail-under`.
+pub const FAIL_UNDER_EXIT_CODE: i32 = 4;
+
+/// Percentage of mutations across all reports that were never tested: those
+/// ending in an error, or whose mutant didn't build (`CompileError` and
+/// `Uncompilable`).
+pub fn error_rate(reports: &[(String, MutationReport)]) -> f64 {
+    let total: usize = reports.iter().map(|(_, report)| report.total_mutations).sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let errors: usize = reports
+        .iter()
+        .map(|(_, report)| report.error_mutations + report.compile_error_mutations + report.uncompilable_mutations)
+        .sum();
+    errors as f64 / total as f64 * 100.0
+}
+
+/// A high error rate means mutations are being misapplied, so the score says
+/// nothing about the tests; fail rather than report it.
+pub fn check_error_rate(reports: &[(String, MutationReport)], max_error_rate: f64) -> Result<(), String> {
+    let rate = error_rate(reports);
+    if rate > max_error_rate {
+        return Err(format!(
+            "{:.1}% of mutations ended in an error or failed to build (limit {:.1}%); the mutation setup is likely broken",
+            rate, max_error_rate
+        ));
+    }
+    Ok(())
+}
+
+/// Mutation score over all reports together, weighting each file by the
+/// number of mutations it scored.
+pub fn aggregate_score(reports: &[(String, MutationReport)]) -> f64 {
+    let scored: usize = reports.iter().map(|(_, report)| report.scored_mutations()).sum();
+    if scored == 0 {
+        return 0.0;
+    }
+    let detected: usize = reports.iter().map(|(_, report)| report.detected_mutations()).sum();
+    detected as f64 / scored as f64 * 100.0
+}
+
+/// Fails when the aggregate score is below `fail_under`, listing the files
+/// scoring below it. Runs where nothing could be scored pass.
+pub fn check_fail_under(reports: &[(String, MutationReport)], fail_under: f64) -> Result<(), Vec<String>> {
+    let score = aggregate_score(reports);
+    if score >= fail_under || reports.iter().all(|(_, report)| report.scored_mutations() == 0) {
+        return Ok(());
+    }
+    let mut messages = vec![format!("Mutation score {:.1}% is below the required {:.1}%", score, fail_under)];
+    messages.extend(
+        reports
+            .iter()
+            .filter(|(_, report)| report.scored_mutations() > 0 && report.mutation_score < fail_under)
+            .map(|(file, report)| format!("{}: {:.1}%", file, report.mutation_score)),
+    );
+    Err(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_fails_when_most_mutations_error() {
+        let mut report = MutationReport::new();
+        report.total_mutations = 10;
+        report.error_mutations = 8;
+        report.killed_mutations = 2;
+        let reports = vec![("src/lib.rs".to_string(), report)];
+
+        assert_eq!(error_rate(&reports), 80.0);
+        assert!(check_error_rate(&reports, 50.0).is_err());
+        assert!(check_error_rate(&reports, 80.0).is_ok());
+        assert!(check_error_rate(&[], 0.0).is_ok());
+
+        let mut report = MutationReport::new();
+        report.total_mutations = 10;
+        report.error_mutations = 1;
+        report.compile_error_mutations = 2;
+        report.uncompilable_mutations = 4;
+        report.killed_mutations = 3;
+        let reports = vec![("src/lib.rs".to_string(), report)];
+        assert_eq!(error_rate(&reports), 70.0);
+        assert!(check_error_rate(&reports, 50.0).is_err());
+    }
+
+    #[test]
+    fn test_fail_under_names_the_files_below_it() {
+        let report = |killed: usize, survived: usize| {
+            let mut report = MutationReport::new();
+            report.total_mutations = killed + survived;
+            report.killed_mutations = killed;
+            report.survived_mutations = survived;
+            report.mutation_score = killed as f64 / (killed + survived) as f64 * 100.0;
+            report
+        };
+        let reports = vec![
+            ("src/good.rs".to_string(), report(9, 1)),
+            ("src/weak.rs".to_string(), report(1, 9)),
+        ];
+
+        assert_eq!(aggregate_score(&reports), 50.0);
+        assert!(check_fail_under(&reports, 50.0).is_ok());
+        assert_eq!(
+            check_fail_under(&reports, 80.0).unwrap_err(),
+            vec!["Mutation score 50.0% is below the required 80.0%".to_string(), "src/weak.rs: 10.0%".to_string()]
+        );
+        assert!(check_fail_under(&[], 80.0).is_ok());
+    }
+}
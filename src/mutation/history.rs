@@ -0,0 +1,127 @@
+use crate::mutation::types::MutationReport;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+pub const DEFAULT_HISTORY_PATH: &str = ".mutation-history.jsonl";
+
+/// One line of the local history file, summarizing a single file's run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub file: String,
+    pub mutation_score: f64,
+    pub total_mutations: usize,
+    pub killed_mutations: usize,
+    pub survived_mutations: usize,
+}
+
+impl HistoryEntry {
+    pub fn from_report(file: &str, report: &MutationReport) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            file: file.to_string(),
+            mutation_score: report.mutation_score,
+            total_mutations: report.total_mutations,
+            killed_mutations: report.killed_mutations,
+            survived_mutations: report.survived_mutations,
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub fn append_history(path: &Path, reports: &[(String, MutationReport)]) -> Result<usize, String> {
+    let mut out = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open history file {}: {}", path.display(), e))?;
+
+    for (file, report) in reports {
+        let line = serde_json::to_string(&HistoryEntry::from_report(file, report))
+            .map_err(|e| format!("Failed to serialize history entry: {}", e))?;
+        writeln!(out, "{}", line).map_err(|e| format!("Failed to write history file {}: {}", path.display(), e))?;
+    }
+
+    Ok(reports.len())
+}
+
+/// Reads every entry from the history file. A missing file is an empty history;
+/// malformed lines are skipped so a partially written line doesn't hide the rest.
+#[allow(dead_code)]
+pub fn read_history(path: &Path) -> Result<Vec<HistoryEntry>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read history file {}: {}", path.display(), e))?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Renders the recorded trend, one line per run, with the score change since
+/// the previous run of the same file.
+#[allow(dead_code)]
+pub fn format_trend(entries: &[HistoryEntry], file_filter: Option<&str>) -> String {
+    let mut output = String::new();
+    let mut previous: Vec<(&str, f64)> = Vec::new();
+
+    for entry in entries.iter().filter(|e| file_filter.is_none_or(|f| e.file == f)) {
+        let delta = match previous.iter_mut().find(|(file, _)| *file == entry.file) {
+            Some((_, last)) => {
+                let delta = format!("{:+.1}", entry.mutation_score - *last);
+                *last = entry.mutation_score;
+                delta
+            }
+            None => {
+                previous.push((&entry.file, entry.mutation_score));
+                "-".to_string()
+            }
+        };
+        output.push_str(&format!(
+            "{}  {}  {:.1}% ({})  killed {}/{}\n",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.file,
+            entry.mutation_score,
+            delta,
+            entry.killed_mutations,
+            entry.total_mutations
+        ));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_databaseless_run_appends_history_line() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(DEFAULT_HISTORY_PATH);
+
+        let mut report = MutationReport::new();
+        report.total_mutations = 4;
+        report.killed_mutations = 3;
+        report.mutation_score = 75.0;
+        append_history(&path, &[("src/lib.rs".to_string(), report.clone())]).unwrap();
+        report.mutation_score = 100.0;
+        append_history(&path, &[("src/lib.rs".to_string(), report)]).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        let first: HistoryEntry = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+        assert_eq!(first.file, "src/lib.rs");
+        assert_eq!(first.mutation_score, 75.0);
+
+        let entries = read_history(&path).unwrap();
+        assert!(format_trend(&entries, None).lines().nth(1).unwrap().contains("100.0% (+25.0)"));
+    }
+}
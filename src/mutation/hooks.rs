@@ -0,0 +1,175 @@
+use crate::mutation::logger::MutationLogger;
+use crate::mutation::types::{MutationResult, TestOutcome};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Runs a rendered `on_survivor_command`. `ShellHookRunner` hands it to
+/// `sh -c`; tests swap in a stub that records the commands.
+pub trait HookRunner: Send + Sync {
+    fn run(&self, command: &str) -> Result<(), String>;
+}
+
+pub struct ShellHookRunner;
+
+impl HookRunner for ShellHookRunner {
+    fn run(&self, command: &str) -> Result<(), String> {
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .status()
+            .map_err(|e| format!("Failed to run on-survivor command: {}", e))?;
+        if !status.success() {
+            return Err(format!("On-survivor command exited with {}", status));
+        }
+        Ok(())
+    }
+}
+
+/// The `on_survivor_command` template together with the file whose
+/// survivors it is run for.
+#[derive(Clone)]
+pub struct SurvivorHook {
+    template: String,
+    file: String,
+    runner: Arc<dyn HookRunner>,
+}
+
+impl SurvivorHook {
+    pub fn new(template: &str, file: &str) -> Self {
+        Self::with_runner(template, file, Arc::new(ShellHookRunner))
+    }
+
+    pub fn with_runner(template: &str, file: &str, runner: Arc<dyn HookRunner>) -> Self {
+        Self { template: template.to_string(), file: file.to_string(), runner }
+    }
+
+    /// Runs the hook if `result` survived. A failing hook is logged and does
+    /// not affect the run.
+    pub fn notify(&self, result: &MutationResult) {
+        if !matches!(result.test_result, TestOutcome::Survived) {
+            return;
+        }
+        let command = render_survivor_command(&self.template, &self.file, result);
+        if let Err(e) = self.runner.run(&command) {
+            MutationLogger::error_file(&self.file, &e);
+        }
+    }
+
+    /// [`SurvivorHook::notify`] on tokio's blocking pool, for the engine to
+    /// call without stalling a runtime worker while the command runs.
+    pub async fn notify_async(&self, result: &MutationResult) {
+        if !matches!(result.test_result, TestOutcome::Survived) {
+            return;
+        }
+        let hook = self.clone();
+        let result = result.clone();
+        if let Err(e) = tokio::task::spawn_blocking(move || hook.notify(&result)).await {
+            MutationLogger::error_file(&self.file, &format!("On-survivor command panicked: {}", e));
+        }
+    }
+
+    pub fn notify_all(&self, results: &[MutationResult]) {
+        for result in results {
+            self.notify(result);
+        }
+    }
+}
+
+/// Substitutes `{file}`, `{line}`, `{type}` and `{original}` in `template`.
+/// Values are shell-quoted, so templates should not quote placeholders.
+pub fn render_survivor_command(template: &str, file: &str, result: &MutationResult) -> String {
+    template
+        .replace("{file}", &shell_quote(file))
+        .replace("{line}", &result.candidate.line.to_string())
+        .replace("{type}", &format!("{:?}", result.candidate.mutation_type))
+        .replace("{original}", &shell_quote(&result.candidate.original_code))
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutation::types::{MutationCandidate, MutationType};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct RecordingRunner {
+        commands: Mutex<Vec<String>>,
+    }
+
+    impl HookRunner for RecordingRunner {
+        fn run(&self, command: &str) -> Result<(), String> {
+            self.commands.lock().unwrap().push(command.to_string());
+            Ok(())
+        }
+    }
+
+    fn result(line: usize, original: &str, test_result: TestOutcome) -> MutationResult {
+        MutationResult {
+            candidate: MutationCandidate {
+                line,
+                column: 5,
+                original_code: original.to_string(),
+                mutation_type: MutationType::RelationalOperator,
+                suggested_mutations: vec![">=".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+            mutated_code: String::new(),
+            test_result,
+            execution_time_ms: 0,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        }
+    }
+
+    #[test]
+    fn test_hook_runs_once_per_survivor_with_placeholders() {
+        let runner = Arc::new(RecordingRunner::default());
+        let hook = SurvivorHook::with_runner("notify {file}:{line} {type} {original}", "src/lib.rs", runner.clone());
+
+        hook.notify_all(&[
+            result(3, ">", TestOutcome::Survived),
+            result(7, "<", TestOutcome::Killed { killing_tests: vec!["t".to_string()] }),
+            result(9, "it's", TestOutcome::Survived),
+        ]);
+
+        assert_eq!(
+            *runner.commands.lock().unwrap(),
+            vec![
+                "notify 'src/lib.rs':3 RelationalOperator '>'".to_string(),
+                r"notify 'src/lib.rs':9 RelationalOperator 'it'\''s'".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_hook_leaves_the_runtime_free() {
+        // Waits for a signal sent by another task on the same single-threaded
+        // runtime, which only arrives if the hook isn't run on that thread.
+        struct WaitingRunner(Mutex<std::sync::mpsc::Receiver<()>>);
+
+        impl HookRunner for WaitingRunner {
+            fn run(&self, _command: &str) -> Result<(), String> {
+                self.0.lock().unwrap().recv_timeout(Duration::from_secs(5)).map_err(|e| e.to_string())
+            }
+        }
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let hook = SurvivorHook::with_runner("true", "src/lib.rs", Arc::new(WaitingRunner(Mutex::new(receiver))));
+        let started = std::time::Instant::now();
+        let survivor = result(3, ">", TestOutcome::Survived);
+        tokio::join!(hook.notify_async(&survivor), async move {
+            tokio::task::yield_now().await;
+            sender.send(()).unwrap();
+        });
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
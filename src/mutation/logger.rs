@@ -1,3 +1,6 @@
+use std::io::{self, IsTerminal, Write};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::time::SystemTime;
 
 const COLOR_INFO: &str = "\x1b[38;2;90;160;100m";
@@ -8,132 +11,283 @@ const COLOR_ERROR: &str = "\x1b[38;2;215;80;110m";
 const COLOR_FILENAME: &str = "\x1b[38;2;118;101;149m";
 const COLOR_RESET: &str = "\x1b[0m";
 
+/// Environment variable that selects the log format; `json` switches to
+/// [`LogFormat::Json`].
+pub const LOG_FORMAT_ENV: &str = "MUTATION_LOG_FORMAT";
+
+const FORMAT_UNSET: u8 = 0;
+const FORMAT_TEXT: u8 = 1;
+const FORMAT_JSON: u8 = 2;
+
+static FORMAT: AtomicU8 = AtomicU8::new(FORMAT_UNSET);
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+static COLORED: OnceLock<bool> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    /// Human-readable lines, colored when `MutationLogger::colored` allows.
+    Text,
+    /// One JSON object per line with `level`, `timestamp`, `message` and,
+    /// for file-attributed messages, `file`.
+    Json,
+}
+
+/// How much the logger prints. Each level adds to the one before it:
+/// warnings, errors and per-file summaries always print; `Normal` adds info
+/// messages, `Verbose` the per-mutation steps and `VeryVerbose` debug output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+    VeryVerbose,
+}
+
+impl Verbosity {
+    /// The level for `-q` or a count of `-v` flags.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::VeryVerbose,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Level {
+    Summary,
+    Info,
+    Debug,
+    Trace,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Summary | Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+            Level::Warn => "WARN ",
+            Level::Error => "ERROR",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Level::Summary | Level::Info => COLOR_INFO,
+            Level::Debug => COLOR_DEBUG,
+            Level::Trace => COLOR_TRACE,
+            Level::Warn => COLOR_WARN,
+            Level::Error => COLOR_ERROR,
+        }
+    }
+
+    /// The lowest verbosity at which messages of this level print.
+    fn verbosity(self) -> Verbosity {
+        match self {
+            Level::Summary | Level::Warn | Level::Error => Verbosity::Quiet,
+            Level::Info => Verbosity::Normal,
+            Level::Trace => Verbosity::Verbose,
+            Level::Debug => Verbosity::VeryVerbose,
+        }
+    }
+}
+
 pub struct MutationLogger;
 
 #[allow(dead_code)]
 impl MutationLogger {
+    /// Overrides the format picked from `MUTATION_LOG_FORMAT`.
+    pub fn set_format(format: LogFormat) {
+        let value = match format {
+            LogFormat::Text => FORMAT_TEXT,
+            LogFormat::Json => FORMAT_JSON,
+        };
+        FORMAT.store(value, Ordering::Relaxed);
+    }
+
+    pub fn format() -> LogFormat {
+        match FORMAT.load(Ordering::Relaxed) {
+            FORMAT_JSON => LogFormat::Json,
+            FORMAT_TEXT => LogFormat::Text,
+            _ => {
+                let json = std::env::var(LOG_FORMAT_ENV).is_ok_and(|value| value.eq_ignore_ascii_case("json"));
+                let format = if json { LogFormat::Json } else { LogFormat::Text };
+                Self::set_format(format);
+                format
+            }
+        }
+    }
+
+    pub fn set_verbosity(verbosity: Verbosity) {
+        VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+    }
+
+    pub fn verbosity() -> Verbosity {
+        match VERBOSITY.load(Ordering::Relaxed) {
+            0 => Verbosity::Quiet,
+            1 => Verbosity::Normal,
+            2 => Verbosity::Verbose,
+            _ => Verbosity::VeryVerbose,
+        }
+    }
+
+    /// A line of a file's final summary, printed even with `-q`.
+    pub fn summary_file(filename: &str, msg: &str) {
+        Self::log(Level::Summary, Some(filename), msg);
+    }
     pub fn info(msg: &str) {
-        println!(
-            "{}  {}INFO{}  {}{}{}",
-            Self::timestamp(),
-            COLOR_INFO,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Info, None, msg);
     }
     pub fn info_file(filename: &str, msg: &str) {
-        println!(
-            "{}  {}INFO{}  {}{}{} {}{}{}",
-            Self::timestamp(),
-            COLOR_INFO,
-            COLOR_RESET,
-            COLOR_FILENAME,
-            filename,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Info, Some(filename), msg);
     }
     pub fn step(msg: &str) {
-        println!(
-            "{}  {}TRACE{}  {}{}{}",
-            Self::timestamp(),
-            COLOR_TRACE,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Trace, None, msg);
     }
     pub fn debug(msg: &str) {
-        println!(
-            "{}  {}DEBUG{}  {}{}{}",
-            Self::timestamp(),
-            COLOR_DEBUG,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Debug, None, msg);
     }
     pub fn trace(msg: &str) {
-        println!(
-            "{}  {}TRACE{}  {}{}{}",
-            Self::timestamp(),
-            COLOR_TRACE,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Trace, None, msg);
     }
     pub fn warn(msg: &str) {
-        println!(
-            "{}  {}WARN {}  {}{}{}",
-            Self::timestamp(),
-            COLOR_WARN,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Warn, None, msg);
     }
     pub fn warn_file(filename: &str, msg: &str) {
-        println!(
-            "{}  {}WARN {}  {}{}{} {}{}{}",
-            Self::timestamp(),
-            COLOR_WARN,
-            COLOR_RESET,
-            COLOR_FILENAME,
-            filename,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Warn, Some(filename), msg);
     }
     pub fn error(msg: &str) {
-        println!(
-            "{}  {}ERROR{}  {}{}{}",
-            Self::timestamp(),
-            COLOR_ERROR,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Error, None, msg);
     }
     pub fn error_file(filename: &str, msg: &str) {
-        println!(
-            "{}  {}ERROR{}  {}{}{} {}{}{}",
-            Self::timestamp(),
-            COLOR_ERROR,
-            COLOR_RESET,
-            COLOR_FILENAME,
-            filename,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Error, Some(filename), msg);
     }
     pub fn fix(msg: &str) {
-        println!(
-            "{}  {}WARN {}  {}{}{}",
-            Self::timestamp(),
-            COLOR_WARN,
-            COLOR_RESET,
-            msg,
-            COLOR_RESET,
-            ""
-        );
+        Self::log(Level::Warn, None, msg);
+    }
+
+    /// Colors are used only when stdout is a terminal and `NO_COLOR` is unset
+    /// or empty.
+    pub fn colored() -> bool {
+        *COLORED.get_or_init(|| {
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            !no_color && io::stdout().is_terminal()
+        })
+    }
+
+    fn log(level: Level, filename: Option<&str>, msg: &str) {
+        let _ = Self::log_to(&mut io::stdout().lock(), level, filename, msg);
+    }
+
+    fn log_to<W: Write>(out: &mut W, level: Level, filename: Option<&str>, msg: &str) -> io::Result<()> {
+        if level.verbosity() > Self::verbosity() {
+            return Ok(());
+        }
+        Self::write_line(out, Self::format(), Self::colored(), level, filename, msg)
+    }
+
+    fn write_line<W: Write>(
+        out: &mut W,
+        format: LogFormat,
+        colored: bool,
+        level: Level,
+        filename: Option<&str>,
+        msg: &str,
+    ) -> io::Result<()> {
+        match format {
+            LogFormat::Text => {
+                let paint = |color: &'static str| if colored { color } else { "" };
+                let file = filename
+                    .map(|name| format!("{}{}{} ", paint(COLOR_FILENAME), name, paint(COLOR_RESET)))
+                    .unwrap_or_default();
+                writeln!(
+                    out,
+                    "{}  {}{}{}  {}{}{}",
+                    Self::timestamp(),
+                    paint(level.color()),
+                    level.label(),
+                    paint(COLOR_RESET),
+                    file,
+                    msg,
+                    paint(COLOR_RESET)
+                )
+            }
+            LogFormat::Json => {
+                let mut entry = serde_json::json!({
+                    "level": level.label().trim_end().to_lowercase(),
+                    "timestamp": chrono::Local::now().to_rfc3339(),
+                    "message": msg,
+                });
+                if let Some(filename) = filename {
+                    entry["file"] = filename.into();
+                }
+                writeln!(out, "{}", entry)
+            }
+        }
     }
+
     fn timestamp() -> String {
         let now = SystemTime::now();
         let datetime: chrono::DateTime<chrono::Local> = now.into();
         datetime.format("%Y-%m-%d %H:%M:%S").to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_format_writes_parsable_lines_without_ansi_codes() {
+        let mut out = Vec::new();
+        MutationLogger::write_line(&mut out, LogFormat::Json, true, Level::Warn, Some("src/lib.rs"), "mutant survived").unwrap();
+        MutationLogger::write_line(&mut out, LogFormat::Json, true, Level::Info, None, "done").unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(!output.contains('\x1b'));
+        let lines: Vec<serde_json::Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines[0]["level"], "warn");
+        assert_eq!(lines[0]["file"], "src/lib.rs");
+        assert_eq!(lines[0]["message"], "mutant survived");
+        assert!(chrono::DateTime::parse_from_rfc3339(lines[0]["timestamp"].as_str().unwrap()).is_ok());
+        assert_eq!(lines[1]["level"], "info");
+        assert!(lines[1].get("file").is_none());
+    }
+
+    #[test]
+    fn test_quiet_keeps_only_warnings_errors_and_summaries() {
+        let emitted = |verbosity: Verbosity| {
+            MutationLogger::set_verbosity(verbosity);
+            let mut out = Vec::new();
+            for level in [Level::Summary, Level::Info, Level::Trace, Level::Debug, Level::Warn, Level::Error] {
+                MutationLogger::log_to(&mut out, level, None, &format!("{:?}", level)).unwrap();
+            }
+            let output = String::from_utf8(out).unwrap();
+            ["Summary", "Info", "Trace", "Debug", "Warn", "Error"]
+                .into_iter()
+                .filter(|name| output.contains(&format!("{}\n", name)) || output.contains(&format!("\"{}\"", name)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(emitted(Verbosity::Quiet), vec!["Summary", "Warn", "Error"]);
+        assert_eq!(emitted(Verbosity::Normal), vec!["Summary", "Info", "Warn", "Error"]);
+        assert_eq!(emitted(Verbosity::Verbose), vec!["Summary", "Info", "Trace", "Warn", "Error"]);
+        assert_eq!(emitted(Verbosity::VeryVerbose).len(), 6);
+        MutationLogger::set_verbosity(Verbosity::Normal);
+    }
+
+    #[test]
+    fn test_uncolored_text_has_no_escape_sequences() {
+        let mut out = Vec::new();
+        MutationLogger::write_line(&mut out, LogFormat::Text, false, Level::Error, Some("src/lib.rs"), "build failed").unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(!output.contains('\x1b'));
+        assert!(output.ends_with("  ERROR  src/lib.rs build failed\n"));
+    }
+}
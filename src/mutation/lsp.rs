@@ -0,0 +1,267 @@
+use crate::mutation::engine::MutationEngine;
+use crate::mutation::types::{MutationCandidate, MutationTestConfig, TestOutcome};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tower_lsp::jsonrpc::{Error, Result};
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+pub const RUN_MUTATION_COMMAND: &str = "mutation-tester.runMutation";
+const DIAGNOSTIC_SOURCE: &str = "mutation-tester";
+
+/// Language server publishing mutation candidates of open documents as
+/// diagnostics and code lenses, with a command to run a single mutation.
+pub struct MutationLanguageServer {
+    client: Client,
+    engine: MutationEngine,
+    documents: RwLock<HashMap<Url, String>>,
+}
+
+impl MutationLanguageServer {
+    pub fn new(client: Client, config: MutationTestConfig) -> Self {
+        Self {
+            client,
+            engine: MutationEngine::new(config),
+            documents: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn candidates_for(&self, uri: &Url) -> Vec<MutationCandidate> {
+        let documents = self.documents.read().await;
+        match documents.get(uri) {
+            Some(text) => self.engine.dry_run(text).await.unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+
+    async fn refresh(&self, uri: Url, text: String) {
+        self.documents.write().await.insert(uri.clone(), text);
+        let diagnostics = self.candidates_for(&uri).await.iter().map(candidate_diagnostic).collect();
+        self.client.publish_diagnostics(uri, diagnostics, None).await;
+    }
+
+    async fn run_mutation(&self, arguments: Vec<Value>) -> Result<String> {
+        let [uri, candidate, mutation] = <[Value; 3]>::try_from(arguments)
+            .map_err(|_| Error::invalid_params("expected [uri, candidate, mutation]"))?;
+        let uri: Url = serde_json::from_value(uri).map_err(|e| Error::invalid_params(e.to_string()))?;
+        let candidate: MutationCandidate =
+            serde_json::from_value(candidate).map_err(|e| Error::invalid_params(e.to_string()))?;
+        let mutation = mutation.as_str().ok_or_else(|| Error::invalid_params("mutation must be a string"))?;
+
+        let source = self
+            .documents
+            .read()
+            .await
+            .get(&uri)
+            .cloned()
+            .ok_or_else(|| Error::invalid_params(format!("{} is not open", uri)))?;
+
+        let result = self
+            .engine
+            .test_single_mutation(&source, &candidate, mutation)
+            .await
+            .map_err(|e| Error {
+                code: tower_lsp::jsonrpc::ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })?;
+
+        let outcome = match &result.test_result {
+            TestOutcome::Killed { killing_tests } if !killing_tests.is_empty() => {
+                format!("killed by {}", killing_tests.join(", "))
+            }
+            TestOutcome::Killed { .. } => "killed".to_string(),
+            TestOutcome::Survived => "survived".to_string(),
+            TestOutcome::Timeout => "timed out".to_string(),
+            TestOutcome::Error => "errored".to_string(),
+            TestOutcome::Skipped => "skipped".to_string(),
+            TestOutcome::CompileError { note } => format!("failed to compile ({})", note),
+            TestOutcome::Uncompilable => "did not compile".to_string(),
+        };
+        Ok(format!(
+            "Mutation '{}' -> '{}' at line {}: {} ({} ms)",
+            candidate.original_code, mutation, candidate.line, outcome, result.execution_time_ms
+        ))
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for MutationLanguageServer {
+    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                        include_text: Some(true),
+                    })),
+                    ..Default::default()
+                })),
+                code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(false) }),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![RUN_MUTATION_COMMAND.to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: DIAGNOSTIC_SOURCE.to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.refresh(params.text_document.uri, params.text_document.text).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Diagnostics are only recomputed on save; just track the latest text.
+        if let Some(change) = params.content_changes.into_iter().last() {
+            self.documents.write().await.insert(params.text_document.uri, change.text);
+        }
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let text = match params.text {
+            Some(text) => text,
+            None => match self.documents.read().await.get(&uri) {
+                Some(text) => text.clone(),
+                None => return,
+            },
+        };
+        self.refresh(uri, text).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.documents.write().await.remove(&params.text_document.uri);
+        self.client.publish_diagnostics(params.text_document.uri, Vec::new(), None).await;
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        let uri = params.text_document.uri;
+        let lenses = self
+            .candidates_for(&uri)
+            .await
+            .into_iter()
+            .flat_map(|candidate| {
+                let range = candidate_range(&candidate);
+                let uri = uri.clone();
+                candidate.suggested_mutations.clone().into_iter().map(move |mutation| CodeLens {
+                    range,
+                    command: Some(Command {
+                        title: format!("Run mutation '{}' -> '{}'", candidate.original_code, mutation),
+                        command: RUN_MUTATION_COMMAND.to_string(),
+                        arguments: Some(vec![
+                            Value::String(uri.to_string()),
+                            serde_json::to_value(&candidate).unwrap_or(Value::Null),
+                            Value::String(mutation),
+                        ]),
+                    }),
+                    data: None,
+                })
+            })
+            .collect();
+        Ok(Some(lenses))
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
+        if params.command != RUN_MUTATION_COMMAND {
+            return Err(Error::method_not_found());
+        }
+        let message = self.run_mutation(params.arguments).await?;
+        self.client.show_message(MessageType::INFO, &message).await;
+        Ok(Some(Value::String(message)))
+    }
+}
+
+fn candidate_range(candidate: &MutationCandidate) -> Range {
+    let line = candidate.line.saturating_sub(1) as u32;
+    let start = candidate.column.saturating_sub(1) as u32;
+    let end = start + candidate.original_code.chars().count() as u32;
+    Range::new(Position::new(line, start), Position::new(line, end))
+}
+
+fn candidate_diagnostic(candidate: &MutationCandidate) -> Diagnostic {
+    Diagnostic {
+        range: candidate_range(candidate),
+        severity: Some(DiagnosticSeverity::HINT),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: format!(
+            "{:?} mutation candidate: '{}' -> {}",
+            candidate.mutation_type,
+            candidate.original_code,
+            candidate
+                .suggested_mutations
+                .iter()
+                .map(|m| format!("'{}'", m))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ..Default::default()
+    }
+}
+
+/// Serves the language server over stdin/stdout until the client exits.
+#[allow(dead_code)]
+pub async fn run_stdio(config: MutationTestConfig) {
+    let (service, socket) = LspService::new(|client| MutationLanguageServer::new(client, config));
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket).serve(service).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::StreamExt;
+    use serde_json::json;
+    use tower::{Service, ServiceExt};
+    use tower_lsp::jsonrpc::Request;
+
+    #[tokio::test]
+    async fn test_did_open_publishes_diagnostics() {
+        let (mut service, mut socket) =
+            LspService::new(|client| MutationLanguageServer::new(client, MutationTestConfig::default()));
+
+        let initialize = Request::build("initialize").params(json!({ "capabilities": {} })).id(1).finish();
+        let response = service.ready().await.unwrap().call(initialize).await.unwrap();
+        assert!(response.is_some());
+        let initialized = Request::build("initialized").params(json!({})).finish();
+        service.ready().await.unwrap().call(initialized).await.unwrap();
+
+        let did_open = Request::build("textDocument/didOpen")
+            .params(json!({
+                "textDocument": {
+                    "uri": "file:///tmp/lib.rs",
+                    "languageId": "rust",
+                    "version": 1,
+                    "text": "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n"
+                }
+            }))
+            .finish();
+        service.ready().await.unwrap().call(did_open).await.unwrap();
+
+        let published = loop {
+            let message = socket.next().await.expect("client socket closed");
+            if message.method() == "textDocument/publishDiagnostics" {
+                break message;
+            }
+        };
+        let params: PublishDiagnosticsParams = serde_json::from_value(published.params().unwrap().clone()).unwrap();
+
+        assert_eq!(params.uri.as_str(), "file:///tmp/lib.rs");
+        let arithmetic = params
+            .diagnostics
+            .iter()
+            .find(|d| d.message.starts_with("ArithmeticOperator"))
+            .expect("arithmetic candidate published");
+        assert_eq!(arithmetic.range.start, Position::new(1, 6));
+        assert_eq!(arithmetic.source.as_deref(), Some(DIAGNOSTIC_SOURCE));
+    }
+}
@@ -1,9 +1,43 @@
 pub mod analyzer;
 pub mod ast_mutator;
 pub mod config_loader;
+pub mod coverage;
 pub mod engine;
+pub mod error;
+pub mod explain;
+pub mod files;
+pub mod gates;
+pub mod history;
+pub mod hooks;
 pub mod logger;
+pub mod lsp;
 pub mod mutators;
+pub mod queue;
 pub mod reports;
 pub mod runner;
+pub mod sinks;
 pub mod types;
+pub mod watch;
+
+use crate::mutation::engine::MutationEngine;
+use crate::mutation::types::{MutationReport, MutationTestConfig};
+use std::path::Path;
+
+/// Mutation-tests one file: the single-call entry point for using this crate
+/// as a library. Files ruled out by `allowed_files`, `excluded_files`,
+/// `excluded_dirs` or for lacking tests are an error rather than an empty
+/// report.
+#[allow(dead_code)]
+pub async fn run_file(path: &Path, config: MutationTestConfig) -> Result<MutationReport, String> {
+    let file = path.to_string_lossy().to_string();
+    if let Some(reason) = files::exclusion_reason(&file, &config) {
+        return Err(format!("{} is excluded: {}", file, reason));
+    }
+    let code = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let mut report = MutationEngine::for_file(config, path)?
+        .run_mutation_testing(&code)
+        .await
+        .map_err(|e| format!("Mutation testing failed for {}: {}", file, e))?;
+    report.source_file = Some(file);
+    Ok(report)
+}
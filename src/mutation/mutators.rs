@@ -1,6 +1,9 @@
+use crate::mutation::ast_mutator::AstMutator;
+use crate::mutation::error::MutationError;
 use crate::mutation::types::{MutationCandidate, MutationType};
 use tracing::debug;
 
+#[derive(Default)]
 pub struct CodeMutator;
 
 #[allow(dead_code)]
@@ -14,21 +17,43 @@ impl CodeMutator {
         source_code: &str,
         candidate: &MutationCandidate,
         mutation: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, MutationError> {
         if !candidate
             .suggested_mutations
             .contains(&mutation.to_string())
         {
-            return Err(format!(
-                "Mutation '{}' is not in the suggested mutations list: {:?}",
-                mutation, candidate.suggested_mutations
-            ));
+            return Err(MutationError::InvalidMutation {
+                mutation: mutation.to_string(),
+                suggestions: candidate.suggested_mutations.clone(),
+            });
+        }
+
+        // Deleted statements and replaced return values can span several
+        // lines, so they are rewritten in the syntax tree rather than spliced
+        // into a line. `break`/`continue` swaps replace the whole node there
+        // too, label included, and so are a `match`'s rearranged arms.
+        if matches!(
+            candidate.mutation_type,
+            MutationType::StatementDeletion
+                | MutationType::ReturnValue
+                | MutationType::BreakContinueReplacement
+                | MutationType::SwitchCase
+        ) {
+            return AstMutator::new().apply_ast_mutation(source_code, candidate, mutation);
+        }
+
+        if let Some(mutated_code) = self.splice_byte_range(source_code, candidate, mutation) {
+            debug!(
+                "Applied mutation by byte range: {} -> {}",
+                candidate.original_code, mutation
+            );
+            return Ok(mutated_code);
         }
 
         let lines: Vec<&str> = source_code.lines().collect();
 
         if candidate.line == 0 || candidate.line > lines.len() {
-            return Err(format!("Invalid line number: {}", candidate.line));
+            return Err(MutationError::InvalidLine(candidate.line));
         }
 
         let mut mutated_lines = lines.clone();
@@ -47,18 +72,63 @@ impl CodeMutator {
         Ok(mutated_code)
     }
 
+    // Replaces exactly the recorded span, provided it still holds the original
+    // code; otherwise the caller falls back to the line/column search.
+    fn splice_byte_range(
+        &self,
+        source_code: &str,
+        candidate: &MutationCandidate,
+        mutation: &str,
+    ) -> Option<String> {
+        if !matches!(
+            candidate.mutation_type,
+            MutationType::ArithmeticOperator
+                | MutationType::RelationalOperator
+                | MutationType::ConditionalBoundary
+                | MutationType::LogicalOperator
+                | MutationType::OverflowMethod
+                | MutationType::AssignmentOperator
+                | MutationType::IncrementDecrement
+                | MutationType::ClosurePredicate
+                | MutationType::PanicMacro
+                | MutationType::DefaultValue
+                | MutationType::OptionalUnwrap
+                | MutationType::FieldSwap
+                | MutationType::BooleanLiteral
+                | MutationType::NumericLiteral
+                | MutationType::StringLiteral
+        ) {
+            return None;
+        }
+        let (start, end) = (candidate.byte_start?, candidate.byte_end?);
+        if source_code.get(start..end)? != candidate.original_code {
+            return None;
+        }
+        Some(format!("{}{}{}", &source_code[..start], mutation, &source_code[end..]))
+    }
+
     fn apply_line_mutation(
         &self,
         line: &str,
         candidate: &MutationCandidate,
         mutation: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, MutationError> {
         let target_pos = candidate.column.saturating_sub(1);
 
         match candidate.mutation_type {
             MutationType::ArithmeticOperator
             | MutationType::RelationalOperator
-            | MutationType::LogicalOperator => self.replace_operator_at_position(
+            | MutationType::ConditionalBoundary
+            | MutationType::LogicalOperator
+            | MutationType::OverflowMethod
+            | MutationType::AssignmentOperator
+            | MutationType::IncrementDecrement
+            | MutationType::ClosurePredicate
+            | MutationType::PanicMacro
+            | MutationType::DefaultValue
+            | MutationType::OptionalUnwrap
+            | MutationType::FieldSwap
+            | MutationType::StringLiteral => self.replace_operator_at_position(
                 line,
                 target_pos,
                 &candidate.original_code,
@@ -76,13 +146,7 @@ impl CodeMutator {
                 &candidate.original_code,
                 mutation,
             ),
-            MutationType::ConditionalBoundary => {
-                self.replace_condition_at_position(line, target_pos, mutation)
-            }
-            _ => Err(format!(
-                "Unsupported mutation type: {:?}",
-                candidate.mutation_type
-            )),
+            _ => Err(MutationError::UnsupportedType(candidate.mutation_type.clone())),
         }
     }
 
@@ -92,16 +156,20 @@ impl CodeMutator {
         pos: usize,
         original: &str,
         replacement: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, MutationError> {
+        let not_found = || MutationError::PositionNotFound {
+            original: original.to_string(),
+            position: pos,
+        };
         if pos >= line.len() {
-            return Err("Position out of bounds".to_string());
+            return Err(not_found());
         }
 
         let chars: Vec<char> = line.chars().collect();
         let original_chars: Vec<char> = original.chars().collect();
 
         if pos + original_chars.len() > chars.len() {
-            return Err("Original text extends beyond line".to_string());
+            return Err(not_found());
         }
 
         let slice_at_pos: String = chars[pos..pos + original_chars.len()].iter().collect();
@@ -109,10 +177,7 @@ impl CodeMutator {
             if let Some(found_pos) = self.find_nearest_occurrence(line, pos, original) {
                 return self.replace_operator_at_position(line, found_pos, original, replacement);
             }
-            return Err(format!(
-                "Original text '{}' not found at position {}",
-                original, pos
-            ));
+            return Err(not_found());
         }
 
         let mut result_chars = chars.clone();
@@ -137,47 +202,64 @@ impl CodeMutator {
         pos: usize,
         original: &str,
         replacement: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, MutationError> {
         if let Some(found_pos) = self.find_complete_word_at_position(line, pos, original) {
             self.replace_operator_at_position(line, found_pos, original, replacement)
         } else {
-            Err(format!(
-                "Literal '{}' not found as complete word near position {}",
-                original, pos
-            ))
-        }
-    }
-
-    fn replace_condition_at_position(
-        &self,
-        line: &str,
-        pos: usize,
-        replacement: &str,
-    ) -> Result<String, String> {
-        if let Some(condition_range) = self.find_condition_range(line, pos) {
-            let before = &line[..condition_range.0];
-            let after = &line[condition_range.1..];
-            Ok(format!("{}{}{}", before, replacement, after))
-        } else {
-            Err("Could not find condition boundaries".to_string())
+            Err(MutationError::PositionNotFound {
+                original: original.to_string(),
+                position: pos,
+            })
         }
     }
 
+    // Searches only the statement containing `around_pos`, so a line holding
+    // several statements with the same operator mutates the intended one.
     fn find_nearest_occurrence(
         &self,
         line: &str,
         around_pos: usize,
         target: &str,
     ) -> Option<usize> {
+        let chars: Vec<char> = line.chars().collect();
+        let target_chars: Vec<char> = target.chars().collect();
+        let (start, end) = Self::statement_bounds(&chars, around_pos);
         let search_radius = 10;
-        let start = around_pos.saturating_sub(search_radius);
-        let end = (around_pos + search_radius).min(line.len());
 
-        if let Some(relative_pos) = line[start..end].find(target) {
-            Some(start + relative_pos)
-        } else {
-            None
+        (start..end)
+            .filter(|&i| i + target_chars.len() <= end && chars[i..i + target_chars.len()] == target_chars[..])
+            .filter(|&i| i.abs_diff(around_pos) <= search_radius)
+            .min_by_key(|&i| i.abs_diff(around_pos))
+    }
+
+    /// Char range of the `;`-terminated statement containing `pos`, ignoring
+    /// semicolons inside string and char literals.
+    fn statement_bounds(chars: &[char], pos: usize) -> (usize, usize) {
+        let mut start = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut i = 0;
+        while i < chars.len() {
+            let ch = chars[i];
+            if in_string {
+                match ch {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => in_string = false,
+                    _ => escaped = false,
+                }
+            } else if ch == '"' {
+                in_string = true;
+            } else if ch == '\'' && chars.get(i + 2) == Some(&'\'') {
+                i += 2;
+            } else if ch == ';' {
+                if i >= pos {
+                    return (start, i);
+                }
+                start = i + 1;
+            }
+            i += 1;
         }
+        (start, chars.len())
     }
 
     fn find_complete_word_at_position(
@@ -189,8 +271,9 @@ impl CodeMutator {
         let chars: Vec<char> = line.chars().collect();
         let word_chars: Vec<char> = word.chars().collect();
 
-        let search_start = around_pos.saturating_sub(word.len());
-        let search_end = (around_pos + word.len()).min(chars.len());
+        let (statement_start, statement_end) = Self::statement_bounds(&chars, around_pos);
+        let search_start = around_pos.saturating_sub(word.len()).max(statement_start);
+        let search_end = (around_pos + word.len()).min(statement_end);
 
         for i in search_start..=search_end {
             if i + word_chars.len() <= chars.len() {
@@ -223,46 +306,11 @@ impl CodeMutator {
         true
     }
 
-    fn find_condition_range(&self, line: &str, around_pos: usize) -> Option<(usize, usize)> {
-        let chars: Vec<char> = line.chars().collect();
-
-        if let Some(if_pos) = line.find("if ") {
-            let condition_start = if_pos + 3;
-            if let Some(brace_pos) = line[condition_start..].find(" {") {
-                let condition_end = condition_start + brace_pos;
-                return Some((condition_start, condition_end));
-            }
-        }
-
-        let mut paren_start = None;
-        let mut paren_end = None;
-
-        for i in (0..around_pos).rev() {
-            if chars[i] == '(' {
-                paren_start = Some(i + 1);
-                break;
-            }
-        }
-
-        for i in around_pos..chars.len() {
-            if chars[i] == ')' {
-                paren_end = Some(i);
-                break;
-            }
-        }
-
-        if let (Some(start), Some(end)) = (paren_start, paren_end) {
-            Some((start, end))
-        } else {
-            None
-        }
-    }
-
     pub fn create_mutations_for_candidate(
         &self,
         source_code: &str,
         candidate: &MutationCandidate,
-    ) -> Vec<Result<String, String>> {
+    ) -> Vec<Result<String, MutationError>> {
         candidate
             .suggested_mutations
             .iter()
@@ -288,6 +336,9 @@ mod tests {
                 original_code: "+".to_string(),
                 mutation_type: MutationType::ArithmeticOperator,
                 suggested_mutations: vec!["-".to_string(), "*".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
             },
         );
 
@@ -317,6 +368,9 @@ mod tests {
                 original_code: "42".to_string(),
                 mutation_type: MutationType::NumericLiteral,
                 suggested_mutations: vec!["0".to_string(), "1".to_string(), "-42".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
             },
         );
 
@@ -343,6 +397,9 @@ mod tests {
                 original_code: "!".to_string(),
                 mutation_type: MutationType::LogicalOperator,
                 suggested_mutations: vec!["".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
             },
         );
 
@@ -367,6 +424,9 @@ mod tests {
                 original_code: "+".to_string(),
                 mutation_type: MutationType::ArithmeticOperator,
                 suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
             },
             "-",
         );
@@ -390,10 +450,81 @@ mod tests {
                 original_code: "+".to_string(),
                 mutation_type: MutationType::ArithmeticOperator,
                 suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
             },
             "/",
         );
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(MutationError::InvalidMutation { mutation, .. }) if mutation == "/"));
+    }
+
+    #[test]
+    fn test_mutation_stays_within_its_statement() {
+        let mutator = CodeMutator::new();
+        let source_code = "    a = x + y; b = x + y; c = \";\";";
+        let candidate = MutationCandidate {
+            line: 1,
+            column: 16,
+            original_code: "+".to_string(),
+            mutation_type: MutationType::ArithmeticOperator,
+            suggested_mutations: vec!["-".to_string()],
+            module_path: String::new(),
+            byte_start: None,
+            byte_end: None,
+        };
+
+        let mutated = mutator.apply_mutation(source_code, &candidate, "-").unwrap();
+
+        assert_eq!(mutated, "    a = x + y; b = x - y; c = \";\";");
+        let chars: Vec<char> = source_code.chars().collect();
+        assert_eq!(CodeMutator::statement_bounds(&chars, 30), (25, 33));
+    }
+
+    #[test]
+    fn test_byte_range_splices_exact_span() {
+        let mutator = CodeMutator::new();
+        let source_code = "fn f(a: u8, b: u8) -> u8 {\n    a + b + a\n}\n";
+        let candidate = MutationCandidate {
+            line: 2,
+            // Deliberately stale: the column points at the first `+`.
+            column: 7,
+            original_code: "+".to_string(),
+            mutation_type: MutationType::ArithmeticOperator,
+            suggested_mutations: vec!["-".to_string()],
+            module_path: String::new(),
+            byte_start: Some(37),
+            byte_end: Some(38),
+        };
+
+        let mutated = mutator.apply_mutation(source_code, &candidate, "-").unwrap();
+
+        assert_eq!(mutated, "fn f(a: u8, b: u8) -> u8 {\n    a + b - a\n}\n");
+    }
+
+    #[test]
+    fn test_mutation_error_variants() {
+        let mutator = CodeMutator::new();
+        let source_code = "fn add(a: i32, b: i32) -> i32 { a + b }";
+        let candidate = |line: usize, column: usize| MutationCandidate {
+            line,
+            column,
+            original_code: "*".to_string(),
+            mutation_type: MutationType::ArithmeticOperator,
+            suggested_mutations: vec!["/".to_string()],
+            module_path: String::new(),
+            byte_start: None,
+            byte_end: None,
+        };
+
+        assert!(matches!(
+            mutator.apply_mutation(source_code, &candidate(3, 1), "/"),
+            Err(MutationError::InvalidLine(3))
+        ));
+        assert!(matches!(
+            mutator.apply_mutation(source_code, &candidate(1, 29), "/"),
+            Err(MutationError::PositionNotFound { position: 28, .. })
+        ));
     }
 }
@@ -0,0 +1,167 @@
+use crate::mutation::types::MutationReport;
+use crate::mutation::logger::MutationLogger;
+use lapin::options::QueueDeclareOptions;
+use lapin::protocol::{AMQPErrorKind, AMQPSoftError};
+use lapin::types::{AMQPValue, FieldTable};
+use lapin::{BasicProperties, Channel, Connection};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Header counting how many times a queued job has been retried.
+pub const RETRY_HEADER: &str = "x-retry-count";
+
+/// Retries a failing job gets before it is moved to the dead-letter queue.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Highest job priority the job queue is declared to honour.
+pub const MAX_PRIORITY: u8 = 9;
+
+/// Arguments the job queue is declared with. Both the producer and the
+/// consumer must use them, since the broker rejects a redeclaration with
+/// different arguments.
+fn job_queue_arguments() -> FieldTable {
+    let mut arguments = FieldTable::default();
+    arguments.insert("x-max-priority".into(), AMQPValue::ShortShortUInt(MAX_PRIORITY));
+    arguments
+}
+
+/// Whether a queue declaration failed because the queue already exists
+/// with other arguments, as a queue created before priorities were
+/// supported does.
+pub fn is_precondition_failed(error: &lapin::Error) -> bool {
+    matches!(error, lapin::Error::ProtocolError(e) if *e.kind() == AMQPErrorKind::Soft(AMQPSoftError::PRECONDITIONFAILED))
+}
+
+/// Declares `queue_name` as the job queue and returns a channel to use it
+/// on. A queue left over from before priorities were supported can't be
+/// redeclared with `x-max-priority`, so it is then used as it is, with a
+/// warning that priorities are ignored until it is deleted and recreated.
+pub async fn declare_job_queue(connection: &Connection, queue_name: &str) -> lapin::Result<Channel> {
+    let channel = connection.create_channel().await?;
+    match channel.queue_declare(queue_name, QueueDeclareOptions::default(), job_queue_arguments()).await {
+        Ok(_) => Ok(channel),
+        Err(e) if is_precondition_failed(&e) => {
+            MutationLogger::warn_file(
+                queue_name,
+                &format!(
+                    "Queue {} exists without x-max-priority; job priorities are ignored until it is deleted and declared again",
+                    queue_name
+                ),
+            );
+            // The failed declaration closed the channel.
+            let channel = connection.create_channel().await?;
+            let passive = QueueDeclareOptions { passive: true, ..QueueDeclareOptions::default() };
+            channel.queue_declare(queue_name, passive, FieldTable::default()).await?;
+            Ok(channel)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Queue that jobs from `queue_name` land in once their retries run out.
+pub fn dead_letter_queue(queue_name: &str) -> String {
+    format!("{}_dead", queue_name)
+}
+
+/// Retries recorded on a delivery; a missing or unreadable header counts as
+/// none.
+pub fn retry_count(properties: &BasicProperties) -> u32 {
+    let Some(value) = properties.headers().as_ref().and_then(|headers| headers.inner().get(RETRY_HEADER)) else {
+        return 0;
+    };
+    match value {
+        AMQPValue::ShortShortUInt(n) => u32::from(*n),
+        AMQPValue::ShortUInt(n) => u32::from(*n),
+        AMQPValue::LongUInt(n) => *n,
+        AMQPValue::ShortShortInt(n) => u32::try_from(*n).unwrap_or(0),
+        AMQPValue::ShortInt(n) => u32::try_from(*n).unwrap_or(0),
+        AMQPValue::LongInt(n) => u32::try_from(*n).unwrap_or(0),
+        AMQPValue::LongLongInt(n) => u32::try_from(*n).unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// `properties` with the retry count bumped by one, keeping any other headers.
+pub fn with_next_retry(properties: &BasicProperties) -> BasicProperties {
+    let mut headers = properties.headers().clone().unwrap_or_default();
+    headers.insert(RETRY_HEADER.into(), AMQPValue::LongUInt(retry_count(properties) + 1));
+    properties.clone().with_headers(headers)
+}
+
+/// Properties to publish a failed job again with, or `None` once its retries
+/// are used up and it belongs in the dead-letter queue. Failures that can't
+/// go away on their own, such as an unparsable payload, skip the retries.
+pub fn retry_properties(properties: &BasicProperties, retryable: bool, max_retries: u32) -> Option<BasicProperties> {
+    (retryable && retry_count(properties) < max_retries).then(|| with_next_retry(properties))
+}
+
+/// Name of the JSON report for a job's file, with path separators flattened
+/// so `src/foo.rs` becomes `src_foo.rs.json`.
+pub fn report_file_name(file: &str) -> String {
+    format!("{}.json", file.replace(['/', '\\'], "_"))
+}
+
+/// Writes a completed job's report into `output_dir`, creating the directory
+/// if needed, and returns the path written.
+pub fn write_job_report(output_dir: &Path, file: &str, report: &MutationReport) -> Result<PathBuf, String> {
+    fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir.display(), e))?;
+    let path = output_dir.join(report_file_name(file));
+    let json = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize report: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lapin::protocol::AMQPError;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_only_precondition_failures_are_recognised() {
+        let mismatch = lapin::Error::ProtocolError(AMQPError::new(
+            AMQPErrorKind::Soft(AMQPSoftError::PRECONDITIONFAILED),
+            "PRECONDITION_FAILED - inequivalent arg 'x-max-priority'".into(),
+        ));
+        assert!(is_precondition_failed(&mismatch));
+
+        let missing = lapin::Error::ProtocolError(AMQPError::new(AMQPErrorKind::Soft(AMQPSoftError::NOTFOUND), "NOT_FOUND".into()));
+        assert!(!is_precondition_failed(&missing));
+        assert!(!is_precondition_failed(&lapin::Error::InvalidChannel(1)));
+    }
+
+    #[test]
+    fn test_retry_count_is_read_incremented_and_exhausted() {
+        let fresh = BasicProperties::default();
+        assert_eq!(retry_count(&fresh), 0);
+
+        let mut headers = FieldTable::default();
+        headers.insert("x-trace".into(), AMQPValue::LongString("abc".into()));
+        headers.insert(RETRY_HEADER.into(), AMQPValue::LongLongInt(2));
+        let retried = BasicProperties::default().with_headers(headers);
+        assert_eq!(retry_count(&retried), 2);
+
+        let next = with_next_retry(&retried);
+        assert_eq!(retry_count(&next), 3);
+        assert!(next.headers().as_ref().unwrap().inner().contains_key("x-trace"));
+
+        assert_eq!(retry_properties(&fresh, true, 3).map(|p| retry_count(&p)), Some(1));
+        assert!(retry_properties(&next, true, 3).is_none());
+        assert!(retry_properties(&fresh, false, 3).is_none());
+        assert_eq!(dead_letter_queue("mutation_jobs"), "mutation_jobs_dead");
+    }
+
+    #[test]
+    fn test_job_report_is_written_as_json_under_output_dir() {
+        let dir = TempDir::new().unwrap();
+        let output_dir = dir.path().join("reports");
+        let report = MutationReport { total_mutations: 2, killed_mutations: 1, survived_mutations: 1, ..Default::default() };
+
+        let path = write_job_report(&output_dir, "src/foo.rs", &report).unwrap();
+
+        assert_eq!(path, output_dir.join("src_foo.rs.json"));
+        let written: MutationReport = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written.total_mutations, 2);
+        assert_eq!(written.survived_mutations, 1);
+    }
+}
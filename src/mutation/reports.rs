@@ -1,8 +1,13 @@
-use crate::mutation::types::{MutationReport, ReportFormat, TestOutcome};
-use std::collections::HashMap;
+use crate::mutation::logger::MutationLogger;
+use crate::mutation::types::{
+    CandidateFormat, MutationCandidate, MutationReport, MutationResult, ReportFormat, SamplingSummary, TestOutcome,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, BufWriter, Write};
 use std::path::Path;
 use std::fs;
-use tracing::{info};
+use tracing::{info, warn};
 use plotters::prelude::*;
 use plotters::style::RGBColor;
 use serde_json;
@@ -14,9 +19,51 @@ const YELLOW: RGBColor = RGBColor(255, 255, 0);
 #[allow(dead_code)]
 const GREY: RGBColor = RGBColor(128, 128, 128);
 
+const ANSI_REMOVED: &str = "\x1b[31m";
+const ANSI_ADDED: &str = "\x1b[32m";
+const ANSI_RESET: &str = "\x1b[0m";
+
 #[allow(dead_code)]
+#[derive(Default)]
 pub struct ReportGenerator;
 
+/// The candidates found in one file, with their count per mutation type.
+#[derive(Debug, Serialize)]
+pub struct FileCandidates {
+    pub file: String,
+    pub total: usize,
+    pub by_type: BTreeMap<String, usize>,
+    pub candidates: Vec<MutationCandidate>,
+}
+
+impl FileCandidates {
+    pub fn new(file: &str, candidates: Vec<MutationCandidate>) -> Self {
+        let mut by_type = BTreeMap::new();
+        for candidate in &candidates {
+            *by_type.entry(format!("{:?}", candidate.mutation_type)).or_insert(0) += 1;
+        }
+        Self { file: file.to_string(), total: candidates.len(), by_type, candidates }
+    }
+}
+
+/// First line of a JSON-lines report: the report's totals without its results.
+#[derive(Serialize)]
+struct JsonLinesSummary<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'a str>,
+    total_mutations: usize,
+    killed_mutations: usize,
+    survived_mutations: usize,
+    error_mutations: usize,
+    timeout_mutations: usize,
+    skipped_mutations: usize,
+    compile_error_mutations: usize,
+    uncompilable_mutations: usize,
+    mutation_score: f64,
+    total_wall_time_seconds: f64,
+    total_cpu_time_seconds: f64,
+}
+
 #[allow(dead_code)]
 impl ReportGenerator {
     #[allow(dead_code)]
@@ -31,11 +78,82 @@ impl ReportGenerator {
             ReportFormat::CSV => self.generate_csv_report(report, output_path),
             ReportFormat::HTML => self.generate_html_report(report, output_path),
             ReportFormat::Markdown => self.generate_markdown_report(report, output_path),
+            ReportFormat::Cobertura => self.generate_cobertura_report(report, output_path),
+            ReportFormat::Sarif => self.generate_sarif_report(report, output_path),
+            ReportFormat::JUnit => self.generate_junit_report(report, output_path),
+            ReportFormat::JsonLines => self.generate_json_lines_report(report, output_path),
             ReportFormat::Console => self.generate_console_report(report),
         }
     }
 
+    /// One CSV table for several files' reports, each row starting with the
+    /// file it belongs to.
+    pub fn generate_csv(&self, reports: &[(&str, &MutationReport)]) -> String {
+        let mut csv = format!("file,{}\n", CSV_HEADER);
+        for (file, report) in reports {
+            for result in &report.results {
+                csv.push_str(&format!("{},{}", csv_quote(file), csv_row(result)));
+            }
+        }
+        csv
+    }
+
     #[allow(dead_code)]
+    pub fn generate_candidate_listing(&self, file: &str, candidates: &[MutationCandidate], format: CandidateFormat) -> String {
+        let mut output = String::new();
+        for candidate in candidates {
+            let line = match format {
+                CandidateFormat::Text => format!(
+                    "{}:{}:{} {:?} '{}' -> {:?}",
+                    file, candidate.line, candidate.column, candidate.mutation_type, candidate.original_code, candidate.suggested_mutations
+                ),
+                CandidateFormat::Rustc => format!(
+                    "{}:{}:{}: note: {:?} mutation candidate",
+                    file, candidate.line, candidate.column, candidate.mutation_type
+                ),
+            };
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Per-file and overall candidate counts by mutation type, for sizing a
+    /// run before starting it.
+    pub fn generate_candidate_summary(&self, files: &[FileCandidates]) -> String {
+        let mut output = String::new();
+        let mut totals: BTreeMap<&str, usize> = BTreeMap::new();
+        for file in files {
+            output.push_str(&format!("{}: {} candidate(s)\n", file.file, file.total));
+            for (mutation_type, count) in &file.by_type {
+                output.push_str(&format!("  {:<28} {:>5}\n", mutation_type, count));
+                *totals.entry(mutation_type).or_insert(0) += count;
+            }
+        }
+        let total: usize = files.iter().map(|file| file.total).sum();
+        output.push_str(&format!("Total: {} candidate(s) in {} file(s)\n", total, files.len()));
+        for (mutation_type, count) in totals {
+            output.push_str(&format!("  {:<28} {:>5}\n", mutation_type, count));
+        }
+        output
+    }
+
+    pub fn generate_module_summary(&self, report: &MutationReport) -> String {
+        let mut output = String::from("Scores by module:\n");
+        for (module, module_report) in report.by_module() {
+            let name = if module.is_empty() { "(crate root)" } else { module.as_str() };
+            output.push_str(&format!(
+                "  {:<40} {:>6.1}%  ({} killed, {} survived, {} total)\n",
+                name,
+                module_report.mutation_score,
+                module_report.killed_mutations,
+                module_report.survived_mutations,
+                module_report.total_mutations
+            ));
+        }
+        output
+    }
+
     pub fn generate_mutation_chart(&self, report: &MutationReport, output_path: &str) -> Result<(), String> {
         let path = Path::new(output_path);
         
@@ -49,6 +167,18 @@ impl ReportGenerator {
         Ok(())
     }
 
+    /// Charts are decoration: a drawing failure is logged and reported as
+    /// `false` so callers can carry on with the rest of the report.
+    pub fn try_generate_mutation_chart(&self, report: &MutationReport, output_path: &str) -> bool {
+        match self.generate_mutation_chart(report, output_path) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Skipping mutation charts: {}", e);
+                false
+            }
+        }
+    }
+
     #[allow(dead_code)]
     fn generate_json_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
         let json = serde_json::to_string_pretty(report)
@@ -63,30 +193,261 @@ impl ReportGenerator {
         Ok(json)
     }
 
+    /// Writes `report` as JSON lines. `file` labels the summary line and
+    /// defaults to the report's own `source_file`.
+    pub fn write_json_lines<W: Write>(&self, report: &MutationReport, file: Option<&str>, writer: &mut W) -> io::Result<()> {
+        let summary = JsonLinesSummary {
+            file: file.or(report.source_file.as_deref()),
+            total_mutations: report.total_mutations,
+            killed_mutations: report.killed_mutations,
+            survived_mutations: report.survived_mutations,
+            error_mutations: report.error_mutations,
+            timeout_mutations: report.timeout_mutations,
+            skipped_mutations: report.skipped_mutations,
+            compile_error_mutations: report.compile_error_mutations,
+            uncompilable_mutations: report.uncompilable_mutations,
+            mutation_score: report.mutation_score,
+            total_wall_time_seconds: report.total_wall_time_seconds,
+            total_cpu_time_seconds: report.total_cpu_time_seconds,
+        };
+        serde_json::to_writer(&mut *writer, &summary)?;
+        writer.write_all(b"\n")?;
+        for result in &report.results {
+            serde_json::to_writer(&mut *writer, result)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// With an output path the lines are streamed to the file and nothing is
+    /// returned, so a huge result set never has to fit in one string.
+    fn generate_json_lines_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
+        let Some(path) = output_path else {
+            let mut buffer = Vec::new();
+            self.write_json_lines(report, None, &mut buffer)
+                .map_err(|e| format!("Failed to serialize report to JSON lines: {}", e))?;
+            return String::from_utf8(buffer).map_err(|e| e.to_string());
+        };
+
+        let file = fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        self.write_json_lines(report, None, &mut writer)
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("Failed to write JSON lines report to {}: {}", path, e))?;
+        info!("JSON lines report written to {}", path);
+        Ok(String::new())
+    }
+
+    /// A synthetic Cobertura document with one class per file. A line with a
+    /// killed mutant is covered (hits = mutants killed there) unless another
+    /// mutant on it survived, which leaves it uncovered with zero hits. Other
+    /// outcomes don't contribute lines.
+    pub fn generate_cobertura(&self, reports: &[(&str, &MutationReport)]) -> String {
+        let mut classes = String::new();
+        let (mut total_valid, mut total_covered) = (0usize, 0usize);
+
+        for (file, report) in reports {
+            let mut lines: BTreeMap<usize, (usize, bool)> = BTreeMap::new();
+            for result in &report.results {
+                match result.test_result {
+                    TestOutcome::Killed { .. } => lines.entry(result.candidate.line).or_default().0 += 1,
+                    TestOutcome::Survived => lines.entry(result.candidate.line).or_default().1 = true,
+                    _ => {}
+                }
+            }
+            let covered = lines.values().filter(|(_, survived)| !survived).count();
+            total_valid += lines.len();
+            total_covered += covered;
+
+            let name = html_escape(file);
+            classes.push_str(&format!(
+                "        <class name=\"{}\" filename=\"{}\" line-rate=\"{:.4}\" branch-rate=\"0\" complexity=\"0\">\n          <methods/>\n          <lines>\n",
+                name,
+                name,
+                line_rate(covered, lines.len())
+            ));
+            for (line, (killed, survived)) in &lines {
+                let hits = if *survived { 0 } else { *killed };
+                classes.push_str(&format!("            <line number=\"{}\" hits=\"{}\"/>\n", line, hits));
+            }
+            classes.push_str("          </lines>\n        </class>\n");
+        }
+
+        let rate = line_rate(total_covered, total_valid);
+        let mut xml = String::from("<?xml version=\"1.0\" ?>\n");
+        xml.push_str("<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n");
+        xml.push_str(&format!(
+            "<coverage line-rate=\"{:.4}\" branch-rate=\"0\" lines-covered=\"{}\" lines-valid=\"{}\" branches-covered=\"0\" branches-valid=\"0\" complexity=\"0\" version=\"{}\" timestamp=\"{}\">\n",
+            rate,
+            total_covered,
+            total_valid,
+            env!("CARGO_PKG_VERSION"),
+            chrono::Utc::now().timestamp_millis()
+        ));
+        xml.push_str("  <sources>\n    <source>.</source>\n  </sources>\n  <packages>\n");
+        xml.push_str(&format!(
+            "    <package name=\"mutation\" line-rate=\"{:.4}\" branch-rate=\"0\" complexity=\"0\">\n      <classes>\n",
+            rate
+        ));
+        xml.push_str(&classes);
+        xml.push_str("      </classes>\n    </package>\n  </packages>\n</coverage>\n");
+        xml
+    }
+
+    #[allow(dead_code)]
+    fn generate_cobertura_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
+        let file = report.source_file.as_deref().unwrap_or("unknown");
+        let xml = self.generate_cobertura(&[(file, report)]);
+
+        if let Some(path) = output_path {
+            fs::write(path, &xml)
+                .map_err(|e| format!("Failed to write Cobertura report to {}: {}", path, e))?;
+            info!("Cobertura report written to {}", path);
+        }
+
+        Ok(xml)
+    }
+
+    /// A SARIF 2.1.0 log with one warning per survived mutation, keyed by
+    /// mutation type.
+    pub fn generate_sarif(&self, reports: &[(&str, &MutationReport)]) -> Result<String, String> {
+        let mut rules: Vec<String> = Vec::new();
+        let mut results = Vec::new();
+
+        for (file, report) in reports {
+            for result in report.results.iter().filter(|r| r.test_result == TestOutcome::Survived) {
+                let rule_id = format!("{:?}", result.candidate.mutation_type);
+                if !rules.contains(&rule_id) {
+                    rules.push(rule_id.clone());
+                }
+                results.push(serde_json::json!({
+                    "ruleId": rule_id,
+                    "level": "warning",
+                    "partialFingerprints": { "mutation/v1": result.fingerprint() },
+                    "message": {
+                        "text": format!(
+                            "Mutation '{}' -> '{}' survived; no test failed.",
+                            result.candidate.original_code, result.mutated_code
+                        )
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": file },
+                            "region": {
+                                "startLine": result.candidate.line,
+                                "startColumn": result.candidate.column
+                            }
+                        }
+                    }]
+                }));
+            }
+        }
+
+        let sarif = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules
+                            .iter()
+                            .map(|id| serde_json::json!({ "id": id, "shortDescription": { "text": format!("{} mutation survived", id) } }))
+                            .collect::<Vec<_>>()
+                    }
+                },
+                "results": results
+            }]
+        });
+
+        serde_json::to_string_pretty(&sarif).map_err(|e| format!("Failed to serialize SARIF report: {}", e))
+    }
+
+    #[allow(dead_code)]
+    fn generate_sarif_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
+        let file = report.source_file.as_deref().unwrap_or("unknown");
+        let sarif = self.generate_sarif(&[(file, report)])?;
+
+        if let Some(path) = output_path {
+            fs::write(path, &sarif)
+                .map_err(|e| format!("Failed to write SARIF report to {}: {}", path, e))?;
+            info!("SARIF report written to {}", path);
+        }
+
+        Ok(sarif)
+    }
+
+    /// JUnit XML with one `<testsuite>` per file and one `<testcase>` per
+    /// mutant: survivors fail, errors and compile errors error, skipped and
+    /// uncompilable mutants are skipped and everything else passes.
+    pub fn generate_junit(&self, reports: &[(&str, &MutationReport)]) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+
+        for (file, report) in reports {
+            let errors = report.results.iter().filter(|r| matches!(r.test_result, TestOutcome::Error | TestOutcome::CompileError { .. })).count();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+                html_escape(file),
+                report.results.len(),
+                report.survived_mutations,
+                errors,
+                report.skipped_mutations + report.uncompilable_mutations,
+                report.total_wall_time_seconds
+            ));
+
+            for result in &report.results {
+                let candidate = &result.candidate;
+                let location = format!("line {}, column {}", candidate.line, candidate.column);
+                xml.push_str(&format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                    html_escape(file),
+                    html_escape(&format!("{:?} '{}' at {} [{}]", candidate.mutation_type, candidate.original_code, location, result.fingerprint())),
+                    result.execution_time_ms as f64 / 1000.0
+                ));
+                match &result.test_result {
+                    TestOutcome::Survived => {
+                        let message = match &result.suggested_improvement {
+                            Some(suggestion) => format!("Mutant survived at {}. {}", location, suggestion),
+                            None => format!("Mutant survived at {}.", location),
+                        };
+                        xml.push_str(&format!(">\n      <failure message=\"{}\" type=\"survived\"/>\n    </testcase>\n", html_escape(&message)));
+                    }
+                    TestOutcome::Error | TestOutcome::CompileError { .. } => {
+                        let message = result.error_message.clone().unwrap_or_else(|| format!("Mutant could not be tested at {}.", location));
+                        xml.push_str(&format!(">\n      <error message=\"{}\"/>\n    </testcase>\n", html_escape(&message)));
+                    }
+                    TestOutcome::Skipped | TestOutcome::Uncompilable => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                    TestOutcome::Killed { .. } | TestOutcome::Timeout => xml.push_str("/>\n"),
+                }
+            }
+
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
     #[allow(dead_code)]
+    fn generate_junit_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
+        let file = report.source_file.as_deref().unwrap_or("unknown");
+        let xml = self.generate_junit(&[(file, report)]);
+
+        if let Some(path) = output_path {
+            fs::write(path, &xml)
+                .map_err(|e| format!("Failed to write JUnit report to {}: {}", path, e))?;
+            info!("JUnit report written to {}", path);
+        }
+
+        Ok(xml)
+    }
+
     fn generate_csv_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
-        let mut csv_content = String::from("mutation_type,original_code,test_result,execution_time_ms,line,column\n");
+        let mut csv_content = format!("{}\n", CSV_HEADER);
         
         for result in &report.results {
-            let test_result = match result.test_result {
-                TestOutcome::Killed { .. } => "killed",
-                TestOutcome::Survived => "survived",
-                TestOutcome::Timeout => "timeout",
-                TestOutcome::Error => "error",
-                TestOutcome::Skipped => "skipped",
-            };
-            
-            let line = format!(
-                "{:?},{},{},{},{},{}\n",
-                result.candidate.mutation_type,
-                result.candidate.original_code.replace(',', "\\,"),
-                test_result,
-                result.execution_time_ms,
-                result.candidate.line,
-                result.candidate.column
-            );
-            
-            csv_content.push_str(&line);
+            csv_content.push_str(&csv_row(result));
         }
         
         if let Some(path) = output_path {
@@ -100,119 +461,26 @@ impl ReportGenerator {
 
     #[allow(dead_code)]
     fn generate_html_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
-        let mut html = String::from(r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Mutation Testing Report</title>
-    <style>
-        body { font-family: Arial, sans-serif; margin: 0; padding: 20px; }
-        .summary { background-color: #f5f5f5; padding: 15px; border-radius: 5px; margin-bottom: 20px; }
-        table { width: 100%; border-collapse: collapse; margin-bottom: 20px; }
-        th, td { padding: 8px; text-align: left; border-bottom: 1px solid #ddd; }
-        th { background-color: #f2f2f2; }
-        .killed { background-color: #d4edda; }
-        .survived { background-color: #f8d7da; }
-        .timeout { background-color: #fff3cd; }
-        .error { background-color: #f5c6cb; }
-        .skipped { background-color: #e2e3e5; }
-        .score-high { color: green; }
-        .score-medium { color: orange; }
-        .score-low { color: red; }
-    </style>
-</head>
-<body>
-    <h1>Mutation Testing Report</h1>
-    
-    <div class="summary">
-        <h2>Summary</h2>
-        <p>Total Mutations: "#);
-        
-        html.push_str(&format!("{}</p>", report.total_mutations));
-        html.push_str(&format!("<p>Killed Mutations: {}</p>", report.killed_mutations));
-        html.push_str(&format!("<p>Survived Mutations: {}</p>", report.survived_mutations));
-        html.push_str(&format!("<p>Error Mutations: {}</p>", report.error_mutations));
-        html.push_str(&format!("<p>Timeout Mutations: {}</p>", report.timeout_mutations));
-        html.push_str(&format!("<p>Skipped Mutations: {}</p>", report.skipped_mutations));
-        
-        let score_class = if report.mutation_score >= 80.0 {
-            "score-high"
-        } else if report.mutation_score >= 60.0 {
-            "score-medium"
-        } else {
-            "score-low"
-        };
-        
-        html.push_str(&format!(
-            r#"<p>Mutation Score: <span class="{}">{:.2}%</span></p>
-            <p>Execution Time: {:.2} seconds</p>
-        </div>"#,
-            score_class, report.mutation_score, report.execution_time_seconds
-        ));
-        
-        html.push_str(r#"
-    <h2>Mutation Results</h2>
-    <table>
-        <thead>
-            <tr>
-                <th>Mutation Type</th>
-                <th>Line</th>
-                <th>Column</th>
-                <th>Original Code</th>
-                <th>Mutated Code</th>
-                <th>Result</th>
-                <th>Execution Time (ms)</th>
-            </tr>
-        </thead>
-        <tbody>
-"#);
-        
-        for result in &report.results {
-            let row_class = match result.test_result {
-                TestOutcome::Killed { .. } => "killed",
-                TestOutcome::Survived => "survived",
-                TestOutcome::Timeout => "timeout",
-                TestOutcome::Error => "error",
-                TestOutcome::Skipped => "skipped",
-            };
-            
-            let test_result = match &result.test_result {
-                TestOutcome::Killed { killing_tests } => format!("Killed (by {} tests)", killing_tests.len()),
-                TestOutcome::Survived => "Survived".to_string(),
-                TestOutcome::Timeout => "Timeout".to_string(),
-                TestOutcome::Error => "Error".to_string(),
-                TestOutcome::Skipped => "Skipped".to_string(),
+        let mut html = String::from(HTML_HEAD);
+        html.push_str(&html_summary(report));
+
+        if let Some(path) = output_path {
+            let chart_dir = match Path::new(path).parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_string_lossy().into_owned(),
+                _ => ".".to_string(),
             };
-            
-            html.push_str(&format!(
-                r#"<tr class="{}">
-                    <td>{:?}</td>
-                    <td>{}</td>
-                    <td>{}</td>
-                    <td><pre>{}</pre></td>
-                    <td><pre>{}</pre></td>
-                    <td>{}</td>
-                    <td>{}</td>
-                </tr>"#,
-                row_class,
-                result.candidate.mutation_type,
-                result.candidate.line,
-                result.candidate.column,
-                html_escape(&result.candidate.original_code),
-                html_escape(&result.mutated_code),
-                test_result,
-                result.execution_time_ms
-            ));
+            html.push_str("\n    <h2>Charts</h2>\n");
+            if self.try_generate_mutation_chart(report, &chart_dir) {
+                html.push_str(r#"    <img src="mutation_outcomes.png" alt="Mutation outcomes">
+    <img src="mutation_types.png" alt="Mutations by type">
+"#);
+            } else {
+                html.push_str("    <p class=\"chart-unavailable\">Charts could not be generated for this run.</p>\n");
+            }
         }
         
-        html.push_str(r#"
-        </tbody>
-    </table>
-</body>
-</html>
-"#);
+        html.push_str(&html_results_table(report));
+        html.push_str(HTML_TAIL);
         
         if let Some(path) = output_path {
             fs::write(path, &html)
@@ -223,6 +491,31 @@ impl ReportGenerator {
         Ok(html)
     }
 
+    /// One HTML page for several files: an index linking to each file's
+    /// section, then the per-file summary and results table.
+    pub fn generate_html(&self, reports: &[(&str, &MutationReport)]) -> String {
+        let mut html = String::from(HTML_HEAD);
+        html.push_str("\n    <h2>Files</h2>\n    <ul class=\"index\">\n");
+        for (index, (file, report)) in reports.iter().enumerate() {
+            html.push_str(&format!(
+                "        <li><a href=\"#file-{}\">{}</a> ({:.2}%)</li>\n",
+                index,
+                html_escape(file),
+                report.mutation_score
+            ));
+        }
+        html.push_str("    </ul>\n");
+
+        for (index, (file, report)) in reports.iter().enumerate() {
+            html.push_str(&format!("\n    <section id=\"file-{}\">\n    <h2>{}</h2>\n", index, html_escape(file)));
+            html.push_str(&html_summary(report));
+            html.push_str(&html_results_table(report));
+            html.push_str("\n    </section>\n");
+        }
+        html.push_str(HTML_TAIL);
+        html
+    }
+
     #[allow(dead_code)]
     fn generate_markdown_report(&self, report: &MutationReport, output_path: Option<&str>) -> Result<String, String> {
         let mut md = String::from("# Mutation Testing Report\n\n");
@@ -234,8 +527,24 @@ impl ReportGenerator {
         md.push_str(&format!("- **Error Mutations**: {}\n", report.error_mutations));
         md.push_str(&format!("- **Timeout Mutations**: {}\n", report.timeout_mutations));
         md.push_str(&format!("- **Skipped Mutations**: {}\n", report.skipped_mutations));
+        md.push_str(&format!("- **Compile Errors**: {}\n", report.compile_error_mutations));
+        md.push_str(&format!("- **Uncompilable Mutations**: {}\n", report.uncompilable_mutations));
         md.push_str(&format!("- **Mutation Score**: {:.2}%\n", report.mutation_score));
-        md.push_str(&format!("- **Execution Time**: {:.2} seconds\n\n", report.execution_time_seconds));
+        md.push_str(&format!("- **Wall Time**: {:.2} seconds\n", report.total_wall_time_seconds));
+        md.push_str(&format!("- **CPU Time**: {:.2} seconds\n", report.total_cpu_time_seconds));
+        if let Some(sampling) = &report.sampling {
+            md.push_str(&format!("- **Sampled**: {}\n", sampling_note(sampling)));
+        }
+        if let Some(error) = &report.file_error {
+            md.push_str(&format!("- **File Error**: {}\n", error));
+        }
+        if report.uncovered_mutations > 0 {
+            md.push_str(&format!("- **Uncovered**: {} (not tested)\n", report.uncovered_mutations));
+        }
+        if report.capped_mutations > 0 {
+            md.push_str(&format!("- **Over max_mutations_per_line**: {} (not tested)\n", report.capped_mutations));
+        }
+        md.push('\n');
         
         md.push_str("## Mutation Results\n\n");
         md.push_str("| Mutation Type | Line | Column | Original Code | Result | Execution Time (ms) |\n");
@@ -246,8 +555,13 @@ impl ReportGenerator {
                 TestOutcome::Killed { killing_tests } => format!("✅ Killed (by {} tests)", killing_tests.len()),
                 TestOutcome::Survived => "❌ Survived".to_string(),
                 TestOutcome::Timeout => "⏱️ Timeout".to_string(),
-                TestOutcome::Error => "⚠️ Error".to_string(),
+                TestOutcome::Error => match &result.error_message {
+                    Some(message) => format!("⚠️ Error: {}", first_line(message).replace('|', "\\|").replace('`', "\\`")),
+                    None => "⚠️ Error".to_string(),
+                },
                 TestOutcome::Skipped => "⏭️ Skipped".to_string(),
+                TestOutcome::CompileError { note } => format!("🛠️ Compile Error ({})", note),
+                TestOutcome::Uncompilable => "🚫 Uncompilable".to_string(),
             };
             
             md.push_str(&format!(
@@ -261,6 +575,19 @@ impl ReportGenerator {
             ));
         }
         
+        let survivors: Vec<String> = report
+            .results
+            .iter()
+            .filter(|result| result.test_result == TestOutcome::Survived)
+            .filter_map(|result| survivor_diff(result, false))
+            .collect();
+        if !survivors.is_empty() {
+            md.push_str("\n## Survived Mutations\n\n");
+            for diff in survivors {
+                md.push_str(&format!("```diff\n{}```\n\n", diff));
+            }
+        }
+        
         if let Some(path) = output_path {
             fs::write(path, &md)
                 .map_err(|e| format!("Failed to write Markdown report to {}: {}", path, e))?;
@@ -281,8 +608,24 @@ impl ReportGenerator {
         output.push_str(&format!("Error Mutations: {}\n", report.error_mutations));
         output.push_str(&format!("Timeout Mutations: {}\n", report.timeout_mutations));
         output.push_str(&format!("Skipped Mutations: {}\n", report.skipped_mutations));
+        output.push_str(&format!("Compile Errors: {}\n", report.compile_error_mutations));
+        output.push_str(&format!("Uncompilable Mutations: {}\n", report.uncompilable_mutations));
         output.push_str(&format!("Mutation Score: {:.2}%\n", report.mutation_score));
-        output.push_str(&format!("Execution Time: {:.2} seconds\n\n", report.execution_time_seconds));
+        output.push_str(&format!("Wall Time: {:.2} seconds\n", report.total_wall_time_seconds));
+        output.push_str(&format!("CPU Time: {:.2} seconds\n", report.total_cpu_time_seconds));
+        if let Some(sampling) = &report.sampling {
+            output.push_str(&format!("Sampled: {}\n", sampling_note(sampling)));
+        }
+        if let Some(error) = &report.file_error {
+            output.push_str(&format!("File Error: {}\n", error));
+        }
+        if report.uncovered_mutations > 0 {
+            output.push_str(&format!("Uncovered: {} (not tested)\n", report.uncovered_mutations));
+        }
+        if report.capped_mutations > 0 {
+            output.push_str(&format!("Over max_mutations_per_line: {} (not tested)\n", report.capped_mutations));
+        }
+        output.push('\n');
         
         output.push_str("Survived Mutations (need better tests):\n");
         output.push_str("----------------------------------------\n");
@@ -292,17 +635,21 @@ impl ReportGenerator {
             if matches!(result.test_result, TestOutcome::Survived) {
                 has_survived = true;
                 output.push_str(&format!(
-                    "Line {}, Col {}: {:?} '{}'\n",
+                    "Line {}, Col {}: {:?} '{}' [{}]\n",
                     result.candidate.line,
                     result.candidate.column,
                     result.candidate.mutation_type,
-                    result.candidate.original_code
+                    result.candidate.original_code,
+                    result.fingerprint()
                 ));
+                if let Some(diff) = survivor_diff(result, MutationLogger::colored()) {
+                    output.push_str(&diff);
+                }
                 
                 if let Some(suggested) = &result.suggested_improvement {
                     output.push_str(&format!("Suggestion: {}\n", suggested));
                 }
-                output.push_str("\n");
+                output.push('\n');
             }
         }
         
@@ -310,6 +657,13 @@ impl ReportGenerator {
             output.push_str("No survived mutations! Great test coverage.\n");
         }
         
+        let savings = &report.savings;
+        output.push_str("\nRun Summary:\n");
+        output.push_str(&format!("Mutations Run: {}\n", savings.mutations_run));
+        output.push_str(&format!("Cache Hits: {}\n", savings.cache_hits));
+        output.push_str(&format!("Skipped: {}\n", savings.skipped));
+        output.push_str(&format!("Estimated Time Saved: {:.2} seconds\n", savings.estimated_time_saved_seconds));
+
         output.push_str("\n=== END OF REPORT ===\n");
         
         Ok(output)
@@ -317,6 +671,11 @@ impl ReportGenerator {
 
     #[allow(dead_code)]
     fn create_pie_chart(&self, report: &MutationReport, output_path: &str) -> Result<(), String> {
+        let total = report.total_mutations as f64;
+        if total == 0.0 {
+            return self.create_empty_chart("Mutation Testing Results", output_path);
+        }
+
         let root = BitMapBackend::new(output_path, (800, 600))
             .into_drawing_area();
             
@@ -333,18 +692,13 @@ impl ReportGenerator {
             .draw()
             .map_err(|e| format!("Failed to configure chart: {}", e))?;
             
-        let total = report.total_mutations as f64;
-        if total == 0.0 {
-            return Err("No mutations to visualize".to_string());
-        }
-        
         let killed = report.killed_mutations as f64 / total;
         let survived = report.survived_mutations as f64 / total;
         let error = report.error_mutations as f64 / total;
         let timeout = report.timeout_mutations as f64 / total;
         let skipped = report.skipped_mutations as f64 / total;
         
-        let values = vec![
+        let values = [
             ("Killed", killed, &GREEN),
             ("Survived", survived, &RED),
             ("Error", error, &YELLOW),
@@ -353,7 +707,7 @@ impl ReportGenerator {
         ];
         
         let mut start_angle = 0.0;
-        for (_i, (label, value, color)) in values.iter().enumerate() {
+        for (label, value, color) in values.iter() {
             if *value > 0.0 {
                 let end_angle = start_angle + value * 360.0;
                 let y = 540 - (start_angle / 400.0 * 600.0) as i32;
@@ -387,15 +741,17 @@ impl ReportGenerator {
     #[allow(dead_code)]
     fn create_bar_chart(&self, report: &MutationReport, output_path: &str) -> Result<(), String> {
         // Count mutations by type
-        let mut type_counts: HashMap<String, i32> = HashMap::new();
+        let mut type_counts: BTreeMap<String, i32> = BTreeMap::new();
         
         for result in &report.results {
             let type_name = format!("{:?}", result.candidate.mutation_type);
             *type_counts.entry(type_name).or_insert(0) += 1;
         }
         
-        let mut types: Vec<String> = type_counts.keys().cloned().collect();
-        types.sort();
+        let types: Vec<String> = type_counts.keys().cloned().collect();
+        if types.is_empty() {
+            return self.create_empty_chart("Mutations by Type", output_path);
+        }
         
         let root = BitMapBackend::new(output_path, (800, 600))
             .into_drawing_area();
@@ -432,11 +788,10 @@ impl ReportGenerator {
         chart.draw_series(
             types.iter().enumerate().map(|(i, t)| {
                 let count = *type_counts.get(t).unwrap_or(&0) as f32;
-                let bar = Rectangle::new(
+                Rectangle::new(
                     [(i as i32, 0.0), ((i+1) as i32, count)],
                     HSLColor(i as f64 / types.len() as f64, 0.8, 0.5).filled()
-                );
-                bar
+                )
             })
         )
         .map_err(|e| format!("Failed to draw chart: {}", e))?;
@@ -446,9 +801,233 @@ impl ReportGenerator {
             
         Ok(())
     }
+
+    fn create_empty_chart(&self, title: &str, output_path: &str) -> Result<(), String> {
+        let root = BitMapBackend::new(output_path, (800, 600))
+            .into_drawing_area();
+
+        root.fill(&WHITE)
+            .map_err(|e| format!("Failed to create chart: {}", e))?;
+        root.draw(&Text::new(title.to_string(), (40, 40), ("sans-serif", 40)))
+            .map_err(|e| format!("Failed to draw chart: {}", e))?;
+        root.draw(&Text::new("No mutations to visualize", (260, 290), ("sans-serif", 24)))
+            .map_err(|e| format!("Failed to draw chart: {}", e))?;
+
+        root.present()
+            .map_err(|e| format!("Failed to save chart: {}", e))
+    }
+}
+
+fn line_rate(covered: usize, valid: usize) -> f64 {
+    if valid == 0 { 1.0 } else { covered as f64 / valid as f64 }
+}
+
+const HTML_HEAD: &str = r#"
+<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Mutation Testing Report</title>
+    <style>
+        body { font-family: Arial, sans-serif; margin: 0; padding: 20px; }
+        .summary { background-color: #f5f5f5; padding: 15px; border-radius: 5px; margin-bottom: 20px; }
+        table { width: 100%; border-collapse: collapse; margin-bottom: 20px; }
+        th, td { padding: 8px; text-align: left; border-bottom: 1px solid #ddd; }
+        th { background-color: #f2f2f2; }
+        .killed { background-color: #d4edda; }
+        .survived { background-color: #f8d7da; }
+        .timeout { background-color: #fff3cd; }
+        .error { background-color: #f5c6cb; }
+        .skipped, .uncompilable { background-color: #e2e3e5; }
+        .score-high { color: green; }
+        .score-medium { color: orange; }
+        .score-low { color: red; }
+    </style>
+</head>
+<body>
+    <h1>Mutation Testing Report</h1>
+    
+"#;
+
+const HTML_TAIL: &str = r#"
+</body>
+</html>
+"#;
+
+fn html_summary(report: &MutationReport) -> String {
+    let mut html = String::from(r#"    <div class="summary">
+        <h2>Summary</h2>
+        <p>Total Mutations: "#);
+    html.push_str(&format!("{}</p>", report.total_mutations));
+    html.push_str(&format!("<p>Killed Mutations: {}</p>", report.killed_mutations));
+    html.push_str(&format!("<p>Survived Mutations: {}</p>", report.survived_mutations));
+    html.push_str(&format!("<p>Error Mutations: {}</p>", report.error_mutations));
+    html.push_str(&format!("<p>Timeout Mutations: {}</p>", report.timeout_mutations));
+    html.push_str(&format!("<p>Skipped Mutations: {}</p>", report.skipped_mutations));
+    html.push_str(&format!("<p>Compile Errors: {}</p>", report.compile_error_mutations));
+    html.push_str(&format!("<p>Uncompilable Mutations: {}</p>", report.uncompilable_mutations));
+    if let Some(error) = &report.file_error {
+        html.push_str(&format!("<p>File Error: {}</p>", html_escape(error)));
+    }
+    
+    let score_class = if report.mutation_score >= 80.0 {
+        "score-high"
+    } else if report.mutation_score >= 60.0 {
+        "score-medium"
+    } else {
+        "score-low"
+    };
+    
+    html.push_str(&format!(
+        r#"<p>Mutation Score: <span class="{}">{:.2}%</span></p>
+        <p>Wall Time: {:.2} seconds</p>
+        <p>CPU Time: {:.2} seconds</p>
+    </div>"#,
+        score_class, report.mutation_score, report.total_wall_time_seconds, report.total_cpu_time_seconds
+    ));
+    html
+}
+
+fn html_results_table(report: &MutationReport) -> String {
+    let mut html = String::from(r#"
+<h2>Mutation Results</h2>
+<table>
+    <thead>
+        <tr>
+            <th>Mutation Type</th>
+            <th>Line</th>
+            <th>Column</th>
+            <th>Original Code</th>
+            <th>Mutated Code</th>
+            <th>Result</th>
+            <th>Execution Time (ms)</th>
+        </tr>
+    </thead>
+    <tbody>
+"#);
+    
+    for result in &report.results {
+        let row_class = match result.test_result {
+            TestOutcome::Killed { .. } => "killed",
+            TestOutcome::Survived => "survived",
+            TestOutcome::Timeout => "timeout",
+            TestOutcome::Error => "error",
+            TestOutcome::Skipped => "skipped",
+            TestOutcome::CompileError { .. } => "compile-error",
+            TestOutcome::Uncompilable => "uncompilable",
+        };
+        
+        let test_result = match &result.test_result {
+            TestOutcome::Killed { killing_tests } => format!("Killed (by {} tests)", killing_tests.len()),
+            TestOutcome::Survived => "Survived".to_string(),
+            TestOutcome::Timeout => "Timeout".to_string(),
+            TestOutcome::Error => match &result.error_message {
+                Some(message) => format!("Error: {}", html_escape(first_line(message))),
+                None => "Error".to_string(),
+            },
+            TestOutcome::Skipped => "Skipped".to_string(),
+            TestOutcome::CompileError { note } => format!("Compile Error ({})", note),
+            TestOutcome::Uncompilable => "Uncompilable".to_string(),
+        };
+        
+        html.push_str(&format!(
+            r#"<tr class="{}">
+                <td>{:?}</td>
+                <td>{}</td>
+                <td>{}</td>
+                <td><pre>{}</pre></td>
+                <td><pre>{}</pre></td>
+                <td>{}</td>
+                <td>{}</td>
+            </tr>"#,
+            row_class,
+            result.candidate.mutation_type,
+            result.candidate.line,
+            result.candidate.column,
+            html_escape(&result.candidate.original_code),
+            html_escape(&result.mutated_code),
+            test_result,
+            result.execution_time_ms
+        ));
+    }
+    html.push_str(r#"
+        </tbody>
+    </table>"#);
+    html
+}
+
+/// e.g. `200 of 1500 candidates (stratified)`.
+fn sampling_note(sampling: &SamplingSummary) -> String {
+    let strategy = format!("{:?}", sampling.strategy).to_lowercase();
+    format!("{} of {} candidates ({})", sampling.sampled_candidates, sampling.total_candidates, strategy)
+}
+
+/// The mutated line of `result` as a one-line `@@` hunk against the line it
+/// replaced. The original line is rebuilt by swapping the applied suggestion
+/// back for the original code, so syntax-tree rewrites that don't leave a
+/// suggestion at the candidate's column get no diff.
+pub fn survivor_diff(result: &MutationResult, colored: bool) -> Option<String> {
+    let candidate = &result.candidate;
+    let mutated = result.mutated_code.lines().nth(candidate.line.checked_sub(1)?)?;
+    let (start, _) = mutated.char_indices().nth(candidate.column.checked_sub(1)?)?;
+    let rest = &mutated[start..];
+    let applied = candidate
+        .suggested_mutations
+        .iter()
+        .filter(|suggestion| rest.starts_with(suggestion.as_str()))
+        .max_by_key(|suggestion| suggestion.len())?;
+    let original = format!("{}{}{}", &mutated[..start], candidate.original_code, &rest[applied.len()..]);
+
+    let paint = |color: &'static str| if colored { color } else { "" };
+    Some(format!(
+        "@@ line {} @@\n{}-{}{}\n{}+{}{}\n",
+        candidate.line,
+        paint(ANSI_REMOVED),
+        original,
+        paint(ANSI_RESET),
+        paint(ANSI_ADDED),
+        mutated,
+        paint(ANSI_RESET)
+    ))
+}
+
+const CSV_HEADER: &str = "mutation_type,original_code,test_result,execution_time_ms,line,column,error_message";
+
+fn csv_row(result: &MutationResult) -> String {
+    let test_result = match result.test_result {
+        TestOutcome::Killed { .. } => "killed",
+        TestOutcome::Survived => "survived",
+        TestOutcome::Timeout => "timeout",
+        TestOutcome::Error => "error",
+        TestOutcome::Skipped => "skipped",
+        TestOutcome::CompileError { .. } => "compile_error",
+        TestOutcome::Uncompilable => "uncompilable",
+    };
+    format!(
+        "{:?},{},{},{},{},{},{}\n",
+        result.candidate.mutation_type,
+        result.candidate.original_code.replace(',', "\\,"),
+        test_result,
+        result.execution_time_ms,
+        result.candidate.line,
+        result.candidate.column,
+        csv_quote(result.error_message.as_deref().unwrap_or(""))
+    )
+}
+
+// The gist of a possibly multi-line error message, for one table cell.
+fn first_line(message: &str) -> &str {
+    message.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("")
+}
+
+fn csv_quote(field: &str) -> String {
+    if field.is_empty() {
+        return String::new();
+    }
+    format!("\"{}\"", field.replace('"', "\"\"").replace(['\r', '\n'], " "))
 }
 
-#[allow(dead_code)]
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -489,6 +1068,48 @@ mod tests {
         assert!(csv.contains("ArithmeticOperator"));
     }
     
+    #[test]
+    fn test_error_results_keep_their_stored_shape() {
+        let mut report = create_test_report();
+        report.results[0].test_result = TestOutcome::Error;
+        report.results[0].error_message = Some("cargo: no such command".to_string());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["results"][0]["test_result"], "Error");
+        assert_eq!(json["results"][0]["error_message"], "cargo: no such command");
+        let stored: MutationReport = serde_json::from_value(json).unwrap();
+        assert_eq!(stored.results[0].test_result, TestOutcome::Error);
+    }
+
+    #[test]
+    fn test_multi_file_csv_has_one_header_and_a_file_column() {
+        let report = create_test_report();
+        let csv = ReportGenerator::new().generate_csv(&[("src/a.rs", &report), ("src/b.rs", &report)]);
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], format!("file,{}", CSV_HEADER));
+        assert_eq!(lines.len(), 1 + 2 * report.results.len());
+        assert!(lines[1].starts_with("\"src/a.rs\",") && lines.last().unwrap().starts_with("\"src/b.rs\","));
+    }
+
+    #[test]
+    fn test_error_messages_are_shown_in_tabular_reports() {
+        let mut report = create_test_report();
+        let message = "error: unexpected argument '--no-such-flag' found\n\nUsage: cargo test";
+        let mut result = report.results[0].clone();
+        result.test_result = TestOutcome::Error;
+        result.error_message = Some(message.to_string());
+        report.add_result(result);
+        let generator = ReportGenerator::new();
+
+        let csv = generator.generate_report(&report, ReportFormat::CSV, None).unwrap();
+        assert!(csv.lines().last().unwrap().ends_with(",\"error: unexpected argument '--no-such-flag' found  Usage: cargo test\""));
+        let md = generator.generate_report(&report, ReportFormat::Markdown, None).unwrap();
+        assert!(md.contains("⚠️ Error: error: unexpected argument '--no-such-flag' found |"));
+        let html = generator.generate_report(&report, ReportFormat::HTML, None).unwrap();
+        assert!(html.contains("Error: error: unexpected argument &#39;--no-such-flag&#39; found"));
+    }
+
     #[test]
     fn test_generate_markdown_report() {
         let report = create_test_report();
@@ -503,6 +1124,209 @@ mod tests {
         assert!(md.contains("## Mutation Results"));
     }
     
+    #[test]
+    fn test_html_report_with_no_mutations_still_generates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.html");
+        let generator = ReportGenerator::new();
+
+        let html = generator
+            .generate_report(&MutationReport::new(), ReportFormat::HTML, path.to_str())
+            .unwrap();
+
+        assert!(path.exists());
+        assert!(html.contains("Total Mutations: 0"));
+        assert!(html.contains("mutation_outcomes.png") || html.contains("chart-unavailable"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+    
+    #[test]
+    fn test_rustc_candidate_listing() {
+        let candidates = vec![
+            MutationCandidate {
+                line: 3,
+                column: 14,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+            MutationCandidate {
+                line: 12,
+                column: 8,
+                original_code: "true".to_string(),
+                mutation_type: MutationType::BooleanLiteral,
+                suggested_mutations: vec!["false".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+        ];
+
+        let listing = ReportGenerator::new().generate_candidate_listing("src/lib.rs", &candidates, CandidateFormat::Rustc);
+        let lines: Vec<&str> = listing.lines().collect();
+
+        assert_eq!(lines, vec![
+            "src/lib.rs:3:14: note: ArithmeticOperator mutation candidate",
+            "src/lib.rs:12:8: note: BooleanLiteral mutation candidate",
+        ]);
+    }
+
+    #[test]
+    fn test_module_summary_aggregates_per_module() {
+        let result = |module: &str, test_result: TestOutcome| crate::mutation::types::MutationResult {
+            candidate: MutationCandidate {
+                line: 1,
+                column: 1,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: module.to_string(),
+                byte_start: None,
+                byte_end: None,
+            },
+            mutated_code: "a - b".to_string(),
+            test_result,
+            execution_time_ms: 10,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        };
+        let mut report = MutationReport::new();
+        report.add_result(result("parser", TestOutcome::Killed { killing_tests: vec![] }));
+        report.add_result(result("parser", TestOutcome::Killed { killing_tests: vec![] }));
+        report.add_result(result("util", TestOutcome::Killed { killing_tests: vec![] }));
+        report.add_result(result("util", TestOutcome::Survived));
+
+        let modules = report.by_module();
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules["parser"].mutation_score, 100.0);
+        assert_eq!(modules["util"].mutation_score, 50.0);
+
+        let summary = ReportGenerator::new().generate_module_summary(&report);
+        assert!(summary.lines().any(|l| l.trim_start().starts_with("parser") && l.contains("100.0%")));
+        assert!(summary.lines().any(|l| l.trim_start().starts_with("util") && l.contains("50.0%")));
+    }
+
+    #[test]
+    fn test_cobertura_lines_reflect_kill_status() {
+        let mut report = create_test_report();
+        let survivor = |line: usize| crate::mutation::types::MutationResult {
+            candidate: MutationCandidate { line, ..report.results[0].candidate.clone() },
+            mutated_code: "a * b".to_string(),
+            test_result: TestOutcome::Survived,
+            execution_time_ms: 50,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        };
+        let (on_killed_line, elsewhere) = (survivor(10), survivor(12));
+        report.add_result(elsewhere);
+        let mut mixed = report.clone();
+        mixed.add_result(on_killed_line);
+        report.source_file = Some("src/lib.rs".to_string());
+
+        let xml = ReportGenerator::new().generate_report(&report, ReportFormat::Cobertura, None).unwrap();
+        assert!(xml.contains("<class name=\"src/lib.rs\" filename=\"src/lib.rs\" line-rate=\"0.5000\""));
+        assert!(xml.contains("<line number=\"10\" hits=\"1\"/>"));
+        assert!(xml.contains("<line number=\"12\" hits=\"0\"/>"));
+        assert!(xml.contains("lines-covered=\"1\" lines-valid=\"2\""));
+
+        let xml = ReportGenerator::new().generate_cobertura(&[("src/lib.rs", &mixed)]);
+        assert!(xml.contains("<line number=\"10\" hits=\"0\"/>"));
+    }
+
+    #[test]
+    fn test_survivors_show_the_changed_line_as_a_diff() {
+        let mut report = MutationReport::new();
+        let mut survivor = create_test_report().results.remove(0);
+        survivor.candidate.line = 2;
+        survivor.candidate.column = 7;
+        survivor.mutated_code = "pub fn add(a: i32, b: i32) -> i32 {\n    a - b\n}\n".to_string();
+        survivor.test_result = TestOutcome::Survived;
+        report.add_result(survivor);
+        let generator = ReportGenerator::new();
+
+        let console = generator.generate_report(&report, ReportFormat::Console, None).unwrap();
+        assert!(console.contains("@@ line 2 @@\n"));
+        assert!(console.contains("-    a + b"));
+        assert!(console.contains("+    a - b"));
+
+        let md = generator.generate_report(&report, ReportFormat::Markdown, None).unwrap();
+        assert!(md.contains("```diff\n@@ line 2 @@\n-    a + b\n+    a - b\n```"));
+    }
+
+    #[test]
+    fn test_sarif_report_lists_survivors() {
+        let mut report = create_test_report();
+        let mut survivor = report.results[0].clone();
+        survivor.candidate.line = 12;
+        survivor.test_result = TestOutcome::Survived;
+        report.add_result(survivor);
+        report.source_file = Some("src/lib.rs".to_string());
+
+        let sarif = ReportGenerator::new().generate_report(&report, ReportFormat::Sarif, None).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+
+        assert!(value["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+        assert_eq!(value["version"], "2.1.0");
+        let results = value["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "ArithmeticOperator");
+        assert_eq!(results[0]["level"], "warning");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(location["region"]["startLine"], 12);
+    }
+
+    #[test]
+    fn test_junit_failures_match_survivors() {
+        let mut report = create_test_report();
+        for (line, test_result) in [(12, TestOutcome::Survived), (14, TestOutcome::Survived), (16, TestOutcome::Error)] {
+            let mut result = report.results[0].clone();
+            result.candidate.line = line;
+            result.test_result = test_result;
+            result.suggested_improvement = Some("Assert on <edge> cases".to_string());
+            report.add_result(result);
+        }
+        report.source_file = Some("src/lib.rs".to_string());
+
+        let xml = ReportGenerator::new().generate_report(&report, ReportFormat::JUnit, None).unwrap();
+        let suite = xml.lines().find(|l| l.trim_start().starts_with("<testsuite ")).unwrap();
+        let attribute = |name: &str| -> usize {
+            let start = suite.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+            suite[start..].split('"').next().unwrap().parse().unwrap()
+        };
+
+        assert!(suite.contains("name=\"src/lib.rs\""));
+        assert_eq!(attribute("tests"), 4);
+        assert_eq!(attribute("failures"), report.survived_mutations);
+        assert_eq!(xml.matches("<failure ").count(), report.survived_mutations);
+        assert_eq!(attribute("errors"), 1);
+        assert!(xml.contains("Mutant survived at line 12, column 5. Assert on &lt;edge&gt; cases"));
+    }
+
+    #[test]
+    fn test_json_lines_report_has_a_summary_then_one_line_per_result() {
+        let mut report = create_test_report();
+        report.add_result(report.results[0].clone());
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("report.jsonl");
+
+        ReportGenerator::new()
+            .generate_report(&report, ReportFormat::JsonLines, path.to_str())
+            .unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), report.results.len() + 1);
+        assert_eq!(lines[0]["total_mutations"], 2);
+        assert!(lines[0].get("results").is_none());
+        assert_eq!(lines[1]["mutated_code"], "a - b");
+    }
+
     fn create_test_report() -> MutationReport {
         let mut report = MutationReport::new();
         
@@ -512,6 +1336,9 @@ mod tests {
             original_code: "+".to_string(),
             mutation_type: MutationType::ArithmeticOperator,
             suggested_mutations: vec!["-".to_string()],
+            module_path: String::new(),
+            byte_start: None,
+            byte_end: None,
         };
         
         let result = crate::mutation::types::MutationResult {
@@ -1,21 +1,184 @@
+use crate::mutation::error::MutationError;
+use async_trait::async_trait;
 use std::fs;
-use std::process::{Command, Stdio};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
-use tempfile::tempdir;
-use tokio::time::timeout;
+use tempfile::{TempDir, tempdir};
+use tokio::sync::{Mutex as AsyncMutex, OwnedSemaphorePermit, Semaphore};
+use tokio::time::error::Elapsed;
+use tokio::time::{timeout, timeout_at};
 use tracing::{debug, error, warn};
 
+/// Shortest per-mutant timeout a `timeout_multiplier` can produce, so a
+/// near-instant baseline doesn't time out mutants on scheduling noise.
+const MIN_SCALED_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Exit statuses with which `cargo test` (101) and `cargo nextest` (100) report
+/// failing tests or a failed build. Any other failure means cargo itself
+/// could not run.
+const CARGO_FAILURE_STATUSES: [i32; 2] = [100, 101];
+
 #[derive(Debug, Clone)]
 pub enum TestOutcome {
     Survived,
     Killed { killing_tests: Vec<String> },
     Timeout,
-    Error,
+    /// The tests could not be run; `message` is the command's stderr or the
+    /// IO error.
+    Error { message: String },
+    CompileError { note: String },
+    Uncompilable,
+}
+
+impl TestOutcome {
+    /// Why the tests could not be run, for an [`TestOutcome::Error`].
+    pub fn error_message(&self) -> Option<String> {
+        match self {
+            TestOutcome::Error { message } => Some(message.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Bounds how many mutants compile at once, independently of how many
+/// mutations are in flight, since parallel `cargo` builds are memory-bound.
+#[derive(Debug, Clone)]
+pub struct CompileLimiter {
+    permits: Arc<Semaphore>,
+    held: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+#[allow(dead_code)]
+impl CompileLimiter {
+    pub fn new(max_concurrent_compiles: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent_compiles.max(1))),
+            held: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub async fn compile<F: Future>(&self, work: F) -> F::Output {
+        let _permit = self.acquire().await;
+        work.await
+    }
+
+    /// Waits for a compile slot, held until the returned permit is dropped.
+    pub async fn acquire(&self) -> CompilePermit {
+        let permit = self.permits.clone().acquire_owned().await.expect("compile semaphore is never closed");
+        let held = self.held.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(held, Ordering::SeqCst);
+        CompilePermit { _permit: permit, held: self.held.clone() }
+    }
+
+    /// Highest number of compile permits held at the same time so far.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+/// A compile slot taken from a [`CompileLimiter`].
+pub struct CompilePermit {
+    _permit: OwnedSemaphorePermit,
+    held: Arc<AtomicUsize>,
+}
+
+impl Drop for CompilePermit {
+    fn drop(&mut self) {
+        self.held.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Exit status and combined stdout/stderr of a finished command.
+#[derive(Debug)]
+struct CommandOutput {
+    status: i32,
+    output: String,
+    stderr: String,
+    /// Set when the failure came from the build step, before any test ran.
+    build_failed: bool,
+}
+
+/// Names of the tests reported as failed in libtest output, in either the
+/// human format (`test tests::foo ... FAILED`) or `--format json`. Output from
+/// other test commands yields no names.
+pub fn parse_failing_tests(output: &str) -> Vec<String> {
+    let mut failing: Vec<String> = Vec::new();
+    for line in output.lines().map(str::trim) {
+        let name = if line.starts_with('{') {
+            serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .filter(|event| event["type"] == "test" && event["event"] == "failed")
+                .and_then(|event| event["name"].as_str().map(str::to_string))
+        } else {
+            line.strip_prefix("test ")
+                .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+                .map(str::to_string)
+        };
+        if let Some(name) = name
+            && !failing.contains(&name)
+        {
+            failing.push(name);
+        }
+    }
+    failing
+}
+
+/// Whether a failed build was caused only by lints promoted to errors (through
+/// `#![deny(warnings)]` or `-D warnings`) rather than a real compile error.
+pub fn is_warning_promoted_failure(output: &str) -> bool {
+    let promoted = output.contains("implied by `#[deny(warnings)]`") || output.contains("implied by `-D warnings`");
+    promoted && output.contains("could not compile") && !output.contains("error[E")
+}
+
+/// Whether the output shows an integer division or remainder by zero, either
+/// as a test panic or rejected at compile time by `unconditional_panic`.
+pub fn is_divide_by_zero_failure(output: &str) -> bool {
+    output.lines().any(|line| {
+        (line.contains("attempt to divide") && line.contains("by zero"))
+            || (line.contains("attempt to calculate the remainder") && line.contains("with a divisor of zero"))
+    })
+}
+
+/// Runs a project's tests against source code. `MutationRunner` shells out to
+/// the configured test command; benchmarks and tests can swap in a stub.
+#[async_trait]
+pub trait TestRunner: Send + Sync {
+    async fn validate_test_setup(&self, source_code: &str) -> Result<(), MutationError>;
+
+    async fn run_tests_for_mutation(&self, mutated_code: &str) -> TestOutcome;
+
+    /// Runs a mutant that only changes a function body. Runners that can
+    /// reuse a previous build for such mutants override this.
+    async fn run_tests_for_body_mutation(&self, mutated_code: &str) -> TestOutcome {
+        self.run_tests_for_mutation(mutated_code).await
+    }
 }
 
 pub struct MutationRunner {
     timeout_duration: Duration,
     test_command: String,
+    compile_limiter: Option<CompileLimiter>,
+    crate_name: String,
+    crate_edition: String,
+    cap_lints: bool,
+    workspace: Option<AsyncMutex<Option<TempDir>>>,
+    shared_build: Option<Mutex<Option<TempDir>>>,
+    timeout_multiplier: Option<f64>,
+    flaky_retries: u32,
+    baseline_duration: Mutex<Option<Duration>>,
+    project: Option<ProjectSource>,
+}
+
+/// A real project to test mutants in, and the mutated file's path within it.
+struct ProjectSource {
+    root: PathBuf,
+    file: PathBuf,
 }
 
 #[allow(dead_code)]
@@ -24,52 +187,284 @@ impl MutationRunner {
         Self {
             timeout_duration: Duration::from_secs(timeout_seconds),
             test_command,
+            compile_limiter: None,
+            crate_name: "mutation_test".to_string(),
+            crate_edition: "2021".to_string(),
+            cap_lints: false,
+            workspace: None,
+            shared_build: None,
+            timeout_multiplier: None,
+            flaky_retries: 0,
+            baseline_duration: Mutex::new(None),
+            project: None,
         }
     }
+
+    pub fn with_compile_limit(mut self, max_concurrent_compiles: Option<usize>) -> Self {
+        self.compile_limiter = max_concurrent_compiles.map(CompileLimiter::new);
+        self
+    }
+
+    pub fn with_crate(mut self, name: &str, edition: &str) -> Self {
+        self.crate_name = name.to_string();
+        self.crate_edition = edition.to_string();
+        self
+    }
+
+    /// Builds mutants with `--cap-lints=warn` so `#![deny(warnings)]` can't
+    /// turn a harmless new warning into a failed build.
+    pub fn with_cap_lints(mut self, cap_lints: bool) -> Self {
+        self.cap_lints = cap_lints;
+        self
+    }
+
+    /// Experimental: body-only mutants are built in one persistent project
+    /// where only `src/lib.rs` is rewritten, so cargo recompiles them
+    /// incrementally. Runs through the shared project are serialized.
+    pub fn with_incremental_workspace(mut self, enabled: bool) -> Self {
+        self.workspace = enabled.then(|| AsyncMutex::new(None));
+        self
+    }
+
+    /// Keeps the baseline's `CARGO_TARGET_DIR` for the whole run and starts
+    /// each mutant's own target directory from a copy of it, so the
+    /// dependencies compiled for the baseline are reused instead of rebuilt.
+    /// Mutants never write to the baseline's build, so they still run in
+    /// parallel, each in its own project.
+    pub fn with_shared_target_dir(mut self, enabled: bool) -> Self {
+        self.shared_build = enabled.then(|| Mutex::new(None));
+        self
+    }
+
+    /// Times mutants out after `multiplier` times the baseline suite's
+    /// duration instead of the flat timeout, once the baseline has run. The
+    /// baseline itself is still bounded by the flat timeout.
+    pub fn with_timeout_multiplier(mut self, multiplier: Option<f64>) -> Self {
+        self.timeout_multiplier = multiplier;
+        self
+    }
+
+    /// Re-runs the tests of a killed mutant up to `retries` more times, so a
+    /// flaky test failing by chance doesn't count as a kill.
+    pub fn with_flaky_retries(mut self, retries: u32) -> Self {
+        self.flaky_retries = retries;
+        self
+    }
+
+    /// Tests mutants in a copy of the project at `root` instead of a
+    /// generated single-file crate, with the mutated source written to
+    /// `file` (relative to `root`), so it can use its sibling modules and
+    /// the project's dependencies. `target` and `.git` are not copied; the
+    /// baseline's build is kept as with [`Self::with_shared_target_dir`] so
+    /// the dependencies aren't rebuilt for every mutant.
+    pub fn with_project(mut self, root: &Path, file: &Path) -> Self {
+        self.project = Some(ProjectSource { root: root.to_path_buf(), file: file.to_path_buf() });
+        self.shared_build.get_or_insert_with(|| Mutex::new(None));
+        self
+    }
+
+    /// The timeout each mutant runs under.
+    pub fn mutant_timeout(&self) -> Duration {
+        let baseline = *self.baseline_duration.lock().unwrap();
+        mutant_timeout(self.timeout_duration, baseline, self.timeout_multiplier)
+    }
+
+    /// The baseline's target directory mutants start from, once the baseline
+    /// has passed.
+    pub fn target_dir_path(&self) -> Option<PathBuf> {
+        let shared = self.shared_build.as_ref()?.lock().unwrap();
+        shared.as_ref().map(|root| root.path().join("target"))
+    }
+
+    /// Directory of the persistent project, once a body-only mutant has run.
+    pub async fn workspace_path(&self) -> Option<PathBuf> {
+        let workspace = self.workspace.as_ref()?.lock().await;
+        workspace.as_ref().map(|dir| dir.path().to_path_buf())
+    }
+
+    /// The build-only form of the test command, e.g. `cargo test --no-run`,
+    /// or `None` when the command isn't a cargo test invocation.
+    pub fn compile_command(&self) -> Option<String> {
+        let parts: Vec<&str> = self.test_command.split_whitespace().collect();
+        let is_cargo_test = parts.first() == Some(&"cargo")
+            && matches!(parts.get(1), Some(&"test") | Some(&"nextest"));
+        if !is_cargo_test || parts.contains(&"--no-run") {
+            return None;
+        }
+
+        let split = parts.iter().position(|part| *part == "--").unwrap_or(parts.len());
+        let mut compile = parts[..split].to_vec();
+        compile.push("--no-run");
+        Some(compile.join(" "))
+    }
+
+    // Builds first when the test command is `cargo test`, so a mutant that
+    // doesn't compile is told apart from one whose tests fail. `limit` bounds
+    // the build and the tests together; time spent queued for a compile
+    // permit doesn't count against it.
+    async fn execute_with_compile_limit(
+        &self,
+        work_dir: &Path,
+        target_dir: Option<&Path>,
+        limit: Duration,
+    ) -> Result<Result<CommandOutput, std::io::Error>, Elapsed> {
+        let Some(compile_command) = self.compile_command() else {
+            return timeout(limit, self.execute_test_command(work_dir, target_dir)).await;
+        };
+
+        let permit = match &self.compile_limiter {
+            Some(limiter) => Some(limiter.acquire().await),
+            None => None,
+        };
+        let deadline = tokio::time::Instant::now() + limit;
+        let compiled = match timeout_at(deadline, self.execute_command(&compile_command, work_dir, target_dir)).await? {
+            Ok(compiled) => compiled,
+            Err(e) => return Ok(Err(e)),
+        };
+        drop(permit);
+        if compiled.status != 0 {
+            return Ok(Ok(CommandOutput { build_failed: true, ..compiled }));
+        }
+        timeout_at(deadline, self.execute_test_command(work_dir, target_dir)).await
+    }
+
     pub async fn run_tests_for_mutation(&self, mutated_code: &str) -> TestOutcome {
         let start_time = Instant::now();
 
-        let temp_dir = match tempdir() {
-            Ok(dir) => dir,
+        let mut own_dir = None;
+        let (work_dir, target_dir) = match self.prepare_project(&mut own_dir, mutated_code) {
+            Ok(paths) => paths,
             Err(e) => {
-                error!("Failed to create temporary directory: {}", e);
-                return TestOutcome::Error;
+                error!("Failed to write mutated code to temp crate: {}", e);
+                return TestOutcome::Error { message: format!("Failed to write mutated code to temp crate: {}", e) };
             }
         };
 
-        let temp_file_path = temp_dir.path().join("main.rs");
-        if let Err(e) = fs::write(&temp_file_path, mutated_code) {
-            error!("Failed to write mutated code to temp file: {}", e);
-            return TestOutcome::Error;
+        self.run_in_project(&work_dir, target_dir.as_deref(), start_time).await
+    }
+
+    /// Writes `source_code` into a fresh project kept alive in `own_dir` and
+    /// returns its directory and the `CARGO_TARGET_DIR` to build it with.
+    /// Once the baseline's build is kept, the project gets its own copy of
+    /// it; otherwise cargo's default target directory is used.
+    fn prepare_project(
+        &self,
+        own_dir: &mut Option<TempDir>,
+        source_code: &str,
+    ) -> Result<(PathBuf, Option<PathBuf>), std::io::Error> {
+        let dir = own_dir.insert(tempdir()?);
+        self.write_project(dir.path(), source_code)?;
+        let Some(baseline_target) = self.target_dir_path() else {
+            return Ok((dir.path().to_path_buf(), None));
+        };
+
+        let target_dir = dir.path().join("target");
+        copy_build(&baseline_target, &target_dir)?;
+        Ok((dir.path().to_path_buf(), Some(target_dir)))
+    }
+
+    pub async fn run_tests_for_body_mutation(&self, mutated_code: &str) -> TestOutcome {
+        let Some(workspace) = &self.workspace else {
+            return self.run_tests_for_mutation(mutated_code).await;
+        };
+        let start_time = Instant::now();
+        let mut workspace = workspace.lock().await;
+
+        // Only lib.rs is rewritten after the first mutant, so cargo's
+        // fingerprints see a single changed source file.
+        let written = match workspace.as_ref() {
+            Some(dir) => fs::write(dir.path().join(self.mutated_file()), mutated_code),
+            None => tempdir().and_then(|dir| {
+                self.write_project(dir.path(), mutated_code)?;
+                *workspace = Some(dir);
+                Ok(())
+            }),
+        };
+        if let Err(e) = written {
+            error!(
+                "Failed to write mutated code to the incremental workspace: {}",
+                e
+            );
+            return TestOutcome::Error {
+                message: format!(
+                    "Failed to write mutated code to the incremental workspace: {}",
+                    e
+                ),
+            };
         }
 
-        match timeout(
-            self.timeout_duration,
-            self.execute_test_command(&temp_dir.path().to_path_buf()),
-        )
-        .await
-        {
-            Ok(Ok(exit_status)) => {
+        let Some(dir) = workspace.as_ref() else {
+            return TestOutcome::Error {
+                message: "The incremental workspace was not created".to_string(),
+            };
+        };
+        self.run_in_project(dir.path(), None, start_time).await
+    }
+
+    /// Runs the project's tests, repeating them while a kill needs
+    /// confirming. A mutant is killed only if every attempt kills it; a
+    /// retry whose tests pass makes it a survivor, and a retry that times
+    /// out or errors is reported as such.
+    async fn run_in_project(
+        &self,
+        work_dir: &Path,
+        target_dir: Option<&Path>,
+        start_time: Instant,
+    ) -> TestOutcome {
+        let mut outcome = self.run_attempt(work_dir, target_dir, start_time).await;
+        for attempt in 1..=self.flaky_retries {
+            if !matches!(outcome, TestOutcome::Killed { .. }) {
+                break;
+            }
+            debug!("Re-running killed mutant ({}/{}) to rule out a flaky test", attempt, self.flaky_retries);
+            let retry = self.run_attempt(work_dir, target_dir, start_time).await;
+            if !matches!(retry, TestOutcome::Killed { .. }) {
+                if matches!(retry, TestOutcome::Survived) {
+                    warn!("Mutant was killed, then survived a retry; counting it as survived");
+                }
+                outcome = retry;
+            }
+        }
+        outcome
+    }
+
+    async fn run_attempt(&self, work_dir: &Path, target_dir: Option<&Path>, start_time: Instant) -> TestOutcome {
+        let limit = self.mutant_timeout();
+        match self.execute_with_compile_limit(work_dir, target_dir, limit).await {
+            Ok(Ok(output)) => {
                 let duration = start_time.elapsed();
                 debug!(
                     "Test completed in {:?} with exit status: {}",
-                    duration, exit_status
+                    duration, output.status
                 );
 
-                if exit_status == 0 {
+                if output.status == 0 {
                     TestOutcome::Survived
+                } else if self.compile_command().is_some() && !CARGO_FAILURE_STATUSES.contains(&output.status) {
+                    warn!("Test command exited with status {} before running any test", output.status);
+                    TestOutcome::Error { message: output.stderr.trim().to_string() }
+                } else if is_divide_by_zero_failure(&output.output) {
+                    // A mutant that divides by zero is caught, whether the
+                    // tests panic or the compiler refuses it outright.
+                    let killing_tests = parse_failing_tests(&output.output);
+                    TestOutcome::Killed { killing_tests }
+                } else if is_warning_promoted_failure(&output.output) {
+                    warn!("Mutant failed to build only because a warning was promoted to an error");
+                    TestOutcome::CompileError { note: "warning-promoted".to_string() }
+                } else if output.build_failed {
+                    debug!("Mutant does not compile");
+                    TestOutcome::Uncompilable
                 } else {
-                    // Simulate capturing killing test names (replace with actual logic)
-                    let killing_tests = vec!["test_example_1".to_string(), "test_example_2".to_string()];
+                    let killing_tests = parse_failing_tests(&output.output);
                     TestOutcome::Killed { killing_tests }
                 }
             }
             Ok(Err(e)) => {
                 error!("Test execution failed: {}", e);
-                TestOutcome::Error
+                TestOutcome::Error { message: format!("Test execution failed: {}", e) }
             }
             Err(_) => {
-                warn!("Test execution timed out after {:?}", self.timeout_duration);
+                warn!("Test execution timed out after {:?}", limit);
                 TestOutcome::Timeout
             }
         }
@@ -78,13 +473,20 @@ impl MutationRunner {
     async fn execute_test_command(
         &self,
         work_dir: &std::path::Path,
-    ) -> Result<i32, std::io::Error> {
-        debug!(
-            "Executing test command: {} in {:?}",
-            self.test_command, work_dir
-        );
+        target_dir: Option<&Path>,
+    ) -> Result<CommandOutput, std::io::Error> {
+        self.execute_command(&self.test_command, work_dir, target_dir).await
+    }
 
-        let parts: Vec<&str> = self.test_command.split_whitespace().collect();
+    async fn execute_command(
+        &self,
+        command_line: &str,
+        work_dir: &std::path::Path,
+        target_dir: Option<&Path>,
+    ) -> Result<CommandOutput, std::io::Error> {
+        debug!("Executing command: {} in {:?}", command_line, work_dir);
+
+        let parts: Vec<&str> = command_line.split_whitespace().collect();
         if parts.is_empty() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
@@ -95,60 +497,99 @@ impl MutationRunner {
         let command = parts[0];
         let args = &parts[1..];
 
-        let mut cmd = Command::new(command);
+        // Async so concurrent mutants don't block runtime threads, and killed
+        // when the future is dropped so a timeout doesn't leave it running.
+        let mut cmd = tokio::process::Command::new(command);
         cmd.args(args)
             .current_dir(work_dir)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null());
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if self.cap_lints {
+            let rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+            cmd.env("RUSTFLAGS", format!("{} --cap-lints=warn", rustflags).trim_start());
+        }
+        if let Some(target_dir) = target_dir {
+            cmd.env("CARGO_TARGET_DIR", target_dir);
+        }
 
-        let output = cmd.output()?;
-        Ok(output.status.code().unwrap_or(-1))
+        let output = cmd.output().await?;
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&stderr);
+        Ok(CommandOutput {
+            status: output.status.code().unwrap_or(-1),
+            output: text,
+            stderr,
+            build_failed: false,
+        })
     }
 
-    pub async fn run_baseline_tests(&self, original_code: &str) -> Result<bool, String> {
+    pub async fn run_baseline_tests(&self, original_code: &str) -> Result<bool, MutationError> {
         debug!("Running baseline tests to ensure they pass");
 
-        let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
-        let temp_file_path = temp_dir.path().join("main.rs");
-
-        fs::write(&temp_file_path, original_code)
-            .map_err(|e| format!("Failed to write original code: {}", e))?;
+        // With a shared build, the baseline's directory is kept once it
+        // passes so mutants can start from its target directory.
+        let dir = tempdir()?;
+        let work_dir = dir.path().join("project");
+        fs::create_dir_all(&work_dir)?;
+        self.write_project(&work_dir, original_code)?;
+        let target_dir = self.shared_build.as_ref().map(|_| dir.path().join("target"));
 
+        let started = Instant::now();
         match timeout(
             self.timeout_duration,
-            self.execute_test_command(&temp_dir.path().to_path_buf()),
+            self.execute_test_command(&work_dir, target_dir.as_deref()),
         )
         .await
         {
-            Ok(Ok(exit_status)) => {
-                if exit_status == 0 {
-                    debug!("Baseline tests passed");
+            Ok(Ok(output)) => {
+                if output.status == 0 {
+                    let duration = started.elapsed();
+                    debug!("Baseline tests passed in {:?}", duration);
+                    *self.baseline_duration.lock().unwrap() = Some(duration);
+                    if let Some(shared) = &self.shared_build {
+                        *shared.lock().unwrap() = Some(dir);
+                    }
                     Ok(true)
                 } else {
-                    warn!("Baseline tests failed with exit status: {}", exit_status);
+                    warn!("Baseline tests failed with exit status: {}", output.status);
                     Ok(false)
                 }
             }
-            Ok(Err(e)) => Err(format!("Failed to execute baseline tests: {}", e)),
-            Err(_) => Err(format!(
-                "Baseline tests timed out after {:?}",
-                self.timeout_duration
-            )),
+            Ok(Err(e)) => Err(MutationError::Io(e)),
+            Err(_) => Err(MutationError::Timeout(self.timeout_duration)),
+        }
+    }
+
+    /// Path of the mutated source within a project written by
+    /// [`Self::write_project`].
+    fn mutated_file(&self) -> PathBuf {
+        match &self.project {
+            Some(project) => project.file.clone(),
+            None => Path::new("src").join("lib.rs"),
         }
     }
 
+    /// Fills `base_path` with the project to test `source_code` in: a copy of
+    /// the real project when one is set, otherwise a generated crate.
+    fn write_project(&self, base_path: &Path, source_code: &str) -> Result<(), std::io::Error> {
+        let Some(project) = &self.project else {
+            return self.create_test_project_structure(base_path, source_code);
+        };
+        copy_project(&project.root, &project.root, base_path)?;
+        fs::write(base_path.join(&project.file), source_code)
+    }
+
     pub fn create_test_project_structure(
         &self,
         base_path: &std::path::Path,
         source_code: &str,
     ) -> Result<(), std::io::Error> {
-        let cargo_toml_content = r#"[package]
-name = "mutation_test"
-version = "0.1.0"
-edition = "2021"
-
-[dependencies]
-"#;
+        let cargo_toml_content = format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"{}\"\n\n[dependencies]\n",
+            self.crate_name, self.crate_edition
+        );
 
         fs::write(base_path.join("Cargo.toml"), cargo_toml_content)?;
 
@@ -166,9 +607,33 @@ edition = "2021"
         Ok(())
     }
 
-    pub async fn validate_test_setup(&self, source_code: &str) -> Result<(), String> {
-        if !source_code.contains("#[test]") && !source_code.contains("#[cfg(test)]") {
-            return Err("No test functions found in source code. Mutation testing requires tests to be effective.".to_string());
+    pub fn contains_tests(source_code: &str) -> bool {
+        source_code.contains("#[test]") || source_code.contains("#[cfg(test)]")
+    }
+
+    /// Heuristic check that a command runs tests at all; `cargo build` or
+    /// `cargo check` would make every mutant outcome meaningless.
+    pub fn looks_like_test_command(test_command: &str) -> bool {
+        test_command
+            .split_whitespace()
+            .map(|token| token.rsplit('/').next().unwrap_or(token))
+            .filter(|token| !token.starts_with('-'))
+            .any(|token| token == "t" || token.contains("test"))
+    }
+
+    pub fn test_command_warning(test_command: &str, allow_non_test_command: bool) -> Option<String> {
+        if allow_non_test_command || Self::looks_like_test_command(test_command) {
+            return None;
+        }
+        Some(format!(
+            "test_command '{}' does not look like it runs tests (no `test`/`nextest` invocation); mutation outcomes will be meaningless. Pass --allow-non-test-command if this is intended.",
+            test_command
+        ))
+    }
+
+    pub async fn validate_test_setup(&self, source_code: &str) -> Result<(), MutationError> {
+        if !Self::contains_tests(source_code) {
+            return Err(MutationError::NoTests);
         }
 
         self.run_baseline_tests(source_code).await?;
@@ -176,3 +641,391 @@ edition = "2021"
         Ok(())
     }
 }
+
+#[async_trait]
+impl TestRunner for MutationRunner {
+    async fn validate_test_setup(&self, source_code: &str) -> Result<(), MutationError> {
+        MutationRunner::validate_test_setup(self, source_code).await
+    }
+
+    async fn run_tests_for_mutation(&self, mutated_code: &str) -> TestOutcome {
+        MutationRunner::run_tests_for_mutation(self, mutated_code).await
+    }
+
+    async fn run_tests_for_body_mutation(&self, mutated_code: &str) -> TestOutcome {
+        MutationRunner::run_tests_for_body_mutation(self, mutated_code).await
+    }
+}
+
+/// Copies `from`, a directory within the project at `root`, into `to`,
+/// leaving out build output and VCS metadata. Symlinks are recreated rather
+/// than followed: one that stays inside the project keeps its target, so it
+/// points into the copy, and any other is pointed at its resolved path.
+fn copy_project(root: &Path, from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "target" || name == ".git" {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            let link = fs::read_link(entry.path())?;
+            let target = match fs::canonicalize(entry.path()) {
+                Ok(resolved) if !(link.is_relative() && resolved.starts_with(fs::canonicalize(root)?)) => resolved,
+                _ => link,
+            };
+            symlink(&target, &to.join(&name))?;
+        } else if file_type.is_dir() {
+            copy_project(root, &entry.path(), &to.join(&name))?;
+        } else {
+            fs::copy(entry.path(), to.join(&name))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> Result<(), std::io::Error> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> Result<(), std::io::Error> {
+    if link.parent().map_or(target.to_path_buf(), |dir| dir.join(target)).is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+/// Copies the baseline's target directory at `from` into a mutant's own at
+/// `to`. Modification times are kept so cargo sees the copied artifacts as up
+/// to date; cargo's lock file and incremental caches are left out.
+fn copy_build(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == ".cargo-lock" || name == "incremental" {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_build(&entry.path(), &to.join(&name))?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), to.join(&name))?;
+            let modified = entry.metadata()?.modified()?;
+            fs::File::options().write(true).open(to.join(&name))?.set_modified(modified)?;
+        }
+    }
+    Ok(())
+}
+
+/// The per-mutant timeout: `multiplier` times the baseline duration, but no
+/// less than [`MIN_SCALED_TIMEOUT`], when both are known; otherwise `flat`.
+/// A multiplier that gives no valid duration (negative, NaN or infinite)
+/// also falls back to `flat`.
+fn mutant_timeout(flat: Duration, baseline: Option<Duration>, multiplier: Option<f64>) -> Duration {
+    match (baseline, multiplier) {
+        (Some(baseline), Some(multiplier)) => Duration::try_from_secs_f64(baseline.as_secs_f64() * multiplier)
+            .map_or(flat, |scaled| scaled.max(MIN_SCALED_TIMEOUT)),
+        _ => flat,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_flaky_kills_are_retried_until_they_settle() {
+        let dir = tempdir().unwrap();
+        let counting = |name: &str, exit: &str| {
+            let script = dir.path().join(name);
+            fs::write(&script, format!("n=$(cat \"$0.count\" 2>/dev/null || echo 0)\necho $((n + 1)) > \"$0.count\"\nexit {}\n", exit)).unwrap();
+            script
+        };
+        // Each script counts its runs; the alternating one fails every other run.
+        let alternating = counting("alternating.sh", "$(((n + 1) % 2))");
+        let passing = counting("passing.sh", "0");
+        let failing = counting("failing.sh", "1");
+        let runs = |script: &Path| fs::read_to_string(format!("{}.count", script.display())).unwrap().trim().to_string();
+        let runner = |script: &Path| MutationRunner::new(30, format!("sh {}", script.display())).with_flaky_retries(2);
+
+        let outcome = runner(&alternating).run_tests_for_mutation("pub fn f() {}").await;
+        assert!(matches!(outcome, TestOutcome::Survived), "{:?}", outcome);
+        assert_eq!(runs(&alternating), "2");
+
+        let outcome = runner(&passing).run_tests_for_mutation("pub fn f() {}").await;
+        assert!(matches!(outcome, TestOutcome::Survived), "{:?}", outcome);
+        assert_eq!(runs(&passing), "1", "a survivor is not re-run");
+
+        let outcome = runner(&failing).run_tests_for_mutation("pub fn f() {}").await;
+        assert!(matches!(outcome, TestOutcome::Killed { .. }), "{:?}", outcome);
+        assert_eq!(runs(&failing), "3");
+    }
+
+    #[test]
+    fn test_mutant_timeout_scales_the_baseline_duration() {
+        let flat = Duration::from_secs(300);
+        let baseline = Some(Duration::from_secs(4));
+
+        assert_eq!(mutant_timeout(flat, baseline, Some(2.5)), Duration::from_secs(10));
+        assert_eq!(mutant_timeout(flat, Some(Duration::from_millis(50)), Some(3.0)), MIN_SCALED_TIMEOUT);
+        assert_eq!(mutant_timeout(flat, None, Some(2.5)), flat);
+        assert_eq!(mutant_timeout(flat, baseline, None), flat);
+        for invalid in [-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(mutant_timeout(flat, baseline, Some(invalid)), flat);
+        }
+
+        let runner = MutationRunner::new(300, "cargo test".to_string()).with_timeout_multiplier(Some(2.0));
+        assert_eq!(runner.mutant_timeout(), flat);
+        *runner.baseline_duration.lock().unwrap() = baseline;
+        assert_eq!(runner.mutant_timeout(), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_parse_failing_tests() {
+        let human = "running 3 tests\ntest tests::adds ... ok\ntest tests::subtracts ... FAILED\ntest tests::divides ... FAILED\n\nfailures:\n    tests::subtracts\n    tests::divides\n";
+        assert_eq!(parse_failing_tests(human), vec!["tests::subtracts", "tests::divides"]);
+
+        let json = r#"{ "type": "suite", "event": "started", "test_count": 2 }
+{ "type": "test", "event": "started", "name": "tests::adds" }
+{ "type": "test", "name": "tests::adds", "event": "failed", "stdout": "" }
+{ "type": "test", "name": "tests::negates", "event": "ok" }"#;
+        assert_eq!(parse_failing_tests(json), vec!["tests::adds"]);
+
+        assert!(parse_failing_tests("FAIL: spec/math.js\n1 failing").is_empty());
+    }
+
+    #[test]
+    fn test_divide_by_zero_failures_are_recognized() {
+        let panic = "thread 'tests::buckets' panicked at src/lib.rs:2:5:\nattempt to calculate the remainder with a divisor of zero\ntest tests::buckets ... FAILED\n";
+        assert!(is_divide_by_zero_failure(panic));
+        assert_eq!(parse_failing_tests(panic), vec!["tests::buckets"]);
+
+        let lint = "error: this operation will panic at runtime\n  |     x / 0\n  |     ^^^^^ attempt to divide `_` by zero\n";
+        assert!(is_divide_by_zero_failure(lint));
+        assert!(!is_divide_by_zero_failure("test tests::adds ... FAILED\n"));
+    }
+
+    #[tokio::test]
+    async fn test_arithmetic_mutation_runs_in_a_real_crate() {
+        let source = include_str!("../../examples/test_arithmetic.rs");
+        let runner = MutationRunner::new(300, "cargo test".to_string()).with_crate("arithmetic_example", "2021");
+
+        assert!(runner.run_baseline_tests(source).await.unwrap());
+        let outcome = runner.run_tests_for_mutation(&source.replacen("a + b", "a - b", 1)).await;
+
+        match outcome {
+            TestOutcome::Killed { killing_tests } => assert_eq!(killing_tests, vec!["tests::test_add"]),
+            other => panic!("expected the mutant to be killed, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_body_mutations_reuse_the_incremental_workspace() {
+        let source = include_str!("../../examples/test_arithmetic.rs");
+        let runner = MutationRunner::new(300, "cargo test".to_string())
+            .with_crate("arithmetic_example", "2021")
+            .with_incremental_workspace(true);
+        let incremental_sessions = |dir: &Path| -> Vec<PathBuf> {
+            let mut entries: Vec<PathBuf> = fs::read_dir(dir.join("target/debug/incremental"))
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+            entries.sort();
+            entries
+        };
+
+        let first = runner
+            .run_tests_for_body_mutation(&source.replacen("a + b", "a - b", 1))
+            .await;
+        assert!(matches!(first, TestOutcome::Killed { .. }));
+        let workspace = runner.workspace_path().await.unwrap();
+        let sessions = incremental_sessions(&workspace);
+        assert!(!sessions.is_empty());
+
+        let second = runner
+            .run_tests_for_body_mutation(&source.replacen("a - b", "a + b", 1))
+            .await;
+        assert!(
+            matches!(second, TestOutcome::Killed { ref killing_tests } if killing_tests == &["tests::test_sub"])
+        );
+        assert_eq!(runner.workspace_path().await.unwrap(), workspace);
+        assert_eq!(incremental_sessions(&workspace), sessions);
+    }
+
+    #[tokio::test]
+    async fn test_warning_promoted_failure_is_a_compile_error() {
+        let mutant = "#![deny(warnings)]\npub fn double(x: i32) -> i32 {\n    let y = x;\n    x * 2\n}\n\n#[test]\nfn doubles() {\n    assert_eq!(double(2), 4);\n}\n";
+
+        let runner = MutationRunner::new(300, "cargo test".to_string());
+        match runner.run_tests_for_mutation(mutant).await {
+            TestOutcome::CompileError { note } => assert_eq!(note, "warning-promoted"),
+            other => panic!("expected a compile error, got {:?}", other),
+        }
+
+        let capped = MutationRunner::new(300, "cargo test".to_string()).with_cap_lints(true);
+        assert!(matches!(capped.run_tests_for_mutation(mutant).await, TestOutcome::Survived));
+    }
+
+    #[tokio::test]
+    async fn test_command_that_cannot_run_is_an_error_with_its_stderr() {
+        let runner = MutationRunner::new(300, "cargo test --no-such-flag".to_string());
+
+        match runner.run_tests_for_mutation("pub fn answer() -> i32 {\n    42\n}\n").await {
+            TestOutcome::Error { message } => assert!(message.contains("--no-such-flag"), "{}", message),
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mutant_that_fails_to_build_is_uncompilable() {
+        let mutant = "pub fn answer() -> i32 {\n    \"forty-two\"\n}\n\n#[test]\nfn answers() {\n    assert_eq!(answer(), 42);\n}\n";
+
+        let runner = MutationRunner::new(300, "cargo test".to_string());
+        assert!(matches!(runner.run_tests_for_mutation(mutant).await, TestOutcome::Uncompilable));
+    }
+
+    #[tokio::test]
+    async fn test_shared_target_dir_reuses_the_baseline_dependencies() {
+        // The dependency's build script logs every time it runs, which is
+        // exactly when cargo rebuilds the dependency.
+        let helper = tempdir().unwrap();
+        let log = tempdir().unwrap();
+        let log_file = log.path().join("builds.log");
+        fs::create_dir_all(helper.path().join("src")).unwrap();
+        fs::write(helper.path().join("Cargo.toml"), "[package]\nname = \"helper\"\nversion = \"0.1.0\"\nedition = \"2021\"\n").unwrap();
+        fs::write(
+            helper.path().join("build.rs"),
+            format!(
+                "use std::io::Write;\nfn main() {{\n    println!(\"cargo:rerun-if-changed=build.rs\");\n    let mut log = std::fs::OpenOptions::new().create(true).append(true).open({:?}).unwrap();\n    writeln!(log, \"built\").unwrap();\n}}\n",
+                log_file
+            ),
+        )
+        .unwrap();
+        fs::write(helper.path().join("src/lib.rs"), "pub fn double(x: i32) -> i32 {\n    x * 2\n}\n").unwrap();
+
+        let project = tempdir().unwrap();
+        fs::create_dir_all(project.path().join("src")).unwrap();
+        fs::write(
+            project.path().join("Cargo.toml"),
+            format!("[package]\nname = \"uses_helper\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nhelper = {{ path = {:?} }}\n", helper.path()),
+        )
+        .unwrap();
+        let source = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\npub fn doubled_sum(a: i32, b: i32) -> i32 {\n    helper::double(add(a, b))\n}\n\n#[test]\nfn doubles_the_sum() {\n    assert_eq!(doubled_sum(1, 2), 6);\n}\n";
+        fs::write(project.path().join("src/lib.rs"), source).unwrap();
+
+        let runner = MutationRunner::new(300, "cargo test".to_string())
+            .with_shared_target_dir(true)
+            .with_project(project.path(), Path::new("src/lib.rs"));
+        assert!(runner.run_baseline_tests(source).await.unwrap());
+        assert!(runner.target_dir_path().unwrap().join("debug").exists());
+
+        let (subtracted, multiplied) = (source.replacen("a + b", "a - b", 1), source.replacen("a + b", "a * b", 1));
+        let (first, second) =
+            tokio::join!(runner.run_tests_for_mutation(&subtracted), runner.run_tests_for_mutation(&multiplied));
+        assert!(matches!(first, TestOutcome::Killed { .. }), "{:?}", first);
+        assert!(matches!(second, TestOutcome::Killed { .. }), "{:?}", second);
+        assert_eq!(fs::read_to_string(&log_file).unwrap().lines().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_project_copies_keep_symlinks_and_the_baseline_build() {
+        let outside = tempdir().unwrap();
+        fs::write(outside.path().join("scale.rs"), "pub const SCALE: i32 = 1;\n").unwrap();
+        let project = tempdir().unwrap();
+        fs::create_dir_all(project.path().join("src")).unwrap();
+        fs::create_dir_all(project.path().join("shapes")).unwrap();
+        fs::write(project.path().join("Cargo.toml"), "[package]\nname = \"linked\"\nversion = \"0.1.0\"\nedition = \"2021\"\n").unwrap();
+        fs::write(project.path().join("shapes/mod.rs"), "pub fn sides() -> i32 {\n    4\n}\n").unwrap();
+        std::os::unix::fs::symlink("../shapes", project.path().join("src/shapes")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("scale.rs"), project.path().join("src/scale.rs")).unwrap();
+        let source = "mod scale;\nmod shapes;\n\npub fn perimeter(side: i32) -> i32 {\n    side * shapes::sides() * scale::SCALE\n}\n\n#[test]\nfn square() {\n    assert_eq!(perimeter(2), 8);\n}\n";
+        fs::write(project.path().join("src/lib.rs"), source).unwrap();
+
+        let runner = MutationRunner::new(300, "cargo test".to_string()).with_project(project.path(), Path::new("src/lib.rs"));
+        assert!(runner.run_baseline_tests(source).await.unwrap());
+        assert!(runner.target_dir_path().is_some());
+        let outcome = runner.run_tests_for_mutation(&source.replacen("side *", "side +", 1)).await;
+        assert!(matches!(outcome, TestOutcome::Killed { .. }), "{:?}", outcome);
+    }
+
+    #[tokio::test]
+    async fn test_setup_without_tests_is_rejected() {
+        let runner = MutationRunner::new(5, "true".to_string());
+        let result = runner.validate_test_setup("fn add(a: i32, b: i32) -> i32 { a + b }").await;
+        assert!(matches!(result, Err(MutationError::NoTests)));
+    }
+
+    #[tokio::test]
+    async fn test_compile_limiter_caps_concurrent_compiles() {
+        let limiter = CompileLimiter::new(2);
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..8 {
+            let limiter = limiter.clone();
+            tasks.spawn(async move {
+                limiter
+                    .compile(async {
+                        let held = limiter.held.load(Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        held
+                    })
+                    .await
+            });
+        }
+
+        let mut observed = Vec::new();
+        while let Some(held) = tasks.join_next().await {
+            observed.push(held.unwrap());
+        }
+
+        assert_eq!(observed.len(), 8);
+        assert!(observed.iter().all(|held| *held <= 2));
+        assert_eq!(limiter.peak(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mutants_queued_for_a_compile_permit_do_not_time_out() {
+        // Each build takes over a second, so the last of five mutants queued
+        // behind one compile permit waits longer than the whole timeout.
+        let project = tempdir().unwrap();
+        fs::create_dir_all(project.path().join("src")).unwrap();
+        fs::write(project.path().join("Cargo.toml"), "[package]\nname = \"slow_build\"\nversion = \"0.1.0\"\nedition = \"2021\"\n").unwrap();
+        fs::write(project.path().join("build.rs"), "fn main() {\n    std::thread::sleep(std::time::Duration::from_millis(1500));\n}\n").unwrap();
+        let source = "pub fn answer() -> i32 {\n    42\n}\n\n#[test]\nfn answers() {\n    assert_eq!(answer(), 42);\n}\n";
+        fs::write(project.path().join("src/lib.rs"), source).unwrap();
+
+        let runner = MutationRunner::new(6, "cargo test".to_string())
+            .with_compile_limit(Some(1))
+            .with_project(project.path(), Path::new("src/lib.rs"));
+        let outcomes = futures::future::join_all((0..5).map(|_| runner.run_tests_for_mutation(source))).await;
+
+        assert!(outcomes.iter().all(|outcome| matches!(outcome, TestOutcome::Survived)), "{:?}", outcomes);
+        assert_eq!(runner.compile_limiter.as_ref().unwrap().peak(), 1);
+    }
+
+    #[test]
+    fn test_compile_command_for_cargo_test() {
+        let runner = |cmd: &str| MutationRunner::new(30, cmd.to_string()).compile_command();
+        assert_eq!(runner("cargo test -- --test-threads=1").as_deref(), Some("cargo test --no-run"));
+        assert_eq!(runner("cargo nextest run").as_deref(), Some("cargo nextest run --no-run"));
+        assert_eq!(runner("./run_tests.sh"), None);
+    }
+
+    #[test]
+    fn test_non_test_command_warning() {
+        assert!(MutationRunner::test_command_warning("cargo build", false).is_some());
+        assert!(MutationRunner::test_command_warning("cargo check --all-targets", false).is_some());
+        assert!(MutationRunner::test_command_warning("cargo build --tests", false).is_some());
+        assert!(MutationRunner::test_command_warning("cargo build", true).is_none());
+
+        assert!(MutationRunner::test_command_warning("cargo test -- --test-threads=1", false).is_none());
+        assert!(MutationRunner::test_command_warning("cargo nextest run", false).is_none());
+        assert!(MutationRunner::test_command_warning("./scripts/run_tests.sh", false).is_none());
+    }
+}
@@ -0,0 +1,476 @@
+use crate::mutation::logger::MutationLogger;
+use crate::mutation::reports::ReportGenerator;
+use crate::mutation::reports::survivor_diff;
+use crate::mutation::types::{MutationReport, MutationTestConfig, ReportFormat, TestOutcome, WebhookFormat};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+
+/// Header carrying the body's HMAC-SHA256 when a webhook secret is set,
+/// following GitHub's webhook convention.
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+/// Environment variable read for the webhook secret when `--webhook-secret`
+/// isn't given.
+pub const WEBHOOK_SECRET_ENV: &str = "MUTATION_WEBHOOK_SECRET";
+
+/// Attempts a webhook POST gets before its failure is reported.
+pub const WEBHOOK_ATTEMPTS: u32 = 3;
+
+/// Wait before the first webhook retry; it doubles for each further retry.
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Survived mutations listed in a Slack message before the rest are elided.
+const SLACK_SURVIVOR_LIMIT: usize = 10;
+
+/// Slack rejects a section block whose text is longer than this.
+const SLACK_SECTION_LIMIT: usize = 3000;
+
+/// Characters of a source line quoted in a Slack message before it is cut.
+const SLACK_LINE_LIMIT: usize = 200;
+
+/// A destination for the final mutation report of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportSink {
+    Console,
+    File { format: ReportFormat, path: String },
+    Webhook {
+        url: String,
+        secret: Option<String>,
+        format: WebhookFormat,
+        /// Score below which a Slack message is colored as failing.
+        min_coverage_percent: Option<f64>,
+    },
+}
+
+impl ReportSink {
+    pub fn from_options(
+        config: &MutationTestConfig,
+        json: Option<&str>,
+        html: Option<&str>,
+        csv: Option<&str>,
+        webhook: Option<&str>,
+        webhook_secret: Option<&str>,
+        webhook_format: WebhookFormat,
+    ) -> Vec<ReportSink> {
+        let mut sinks = Vec::new();
+
+        match (&config.report_format, &config.report_output_path) {
+            (Some(format), Some(path)) if *format != ReportFormat::Console => {
+                sinks.push(ReportSink::File { format: format.clone(), path: path.clone() });
+            }
+            _ => sinks.push(ReportSink::Console),
+        }
+
+        for (format, path) in [(ReportFormat::JSON, json), (ReportFormat::HTML, html), (ReportFormat::CSV, csv)] {
+            let Some(path) = path else { continue };
+            let sink = ReportSink::File { format, path: path.to_string() };
+            if !sinks.contains(&sink) {
+                sinks.push(sink);
+            }
+        }
+
+        if let Some(url) = webhook {
+            sinks.push(ReportSink::Webhook {
+                url: url.to_string(),
+                secret: webhook_secret.map(str::to_string),
+                format: webhook_format,
+                min_coverage_percent: config.min_coverage_percent,
+            });
+        }
+
+        sinks
+    }
+
+    pub async fn emit(&self, reports: &[(String, MutationReport)]) -> Result<(), String> {
+        match self {
+            ReportSink::Console => {
+                let generator = ReportGenerator::new();
+                for (file, report) in reports {
+                    let output = generator.generate_report(report, ReportFormat::Console, None)?;
+                    println!("\n{}\n{}", file, output);
+                }
+                Ok(())
+            }
+            ReportSink::File { format, path } => {
+                write_report_file(reports, format, path)?;
+                MutationLogger::info_file(path, &format!("Exported {:?} report to", format));
+                Ok(())
+            }
+            ReportSink::Webhook { url, secret, format, min_coverage_percent } => {
+                let body = match format {
+                    WebhookFormat::Raw if reports.len() == 1 => serde_json::to_string_pretty(&reports[0].1),
+                    WebhookFormat::Raw => serde_json::to_string_pretty(reports),
+                    WebhookFormat::Slack => serde_json::to_string(&slack_payload(reports, *min_coverage_percent)),
+                }
+                .map_err(|e| format!("Failed to serialize report for webhook: {}", e))?;
+
+                post_webhook(url, body, secret.as_deref()).await?;
+                MutationLogger::info_file(url, &format!("Posted results to webhook: {}", url));
+                Ok(())
+            }
+        }
+    }
+
+    fn target(&self) -> &str {
+        match self {
+            ReportSink::Console => "console",
+            ReportSink::File { path, .. } => path,
+            ReportSink::Webhook { url, .. } => url,
+        }
+    }
+}
+
+/// POSTs a JSON `body` to `url`, signed with `secret` when one is given.
+/// Connection errors and 5xx responses are retried with exponential backoff,
+/// up to [`WEBHOOK_ATTEMPTS`] attempts; other error responses fail at once.
+pub async fn post_webhook(url: &str, body: String, secret: Option<&str>) -> Result<(), String> {
+    post_webhook_with_backoff(url, body, secret, WEBHOOK_RETRY_DELAY).await
+}
+
+async fn post_webhook_with_backoff(url: &str, body: String, secret: Option<&str>, first_delay: Duration) -> Result<(), String> {
+    let client = Client::new();
+    let signature = secret.map(|secret| webhook_signature(secret, body.as_bytes()));
+    let mut delay = first_delay;
+    let mut attempt = 1;
+    loop {
+        let mut request = client.post(url).header("Content-Type", "application/json");
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+        let error = match request.body(body.clone()).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if !response.status().is_server_error() => {
+                return Err(format!("Webhook POST failed: {}", response.status()));
+            }
+            Ok(response) => format!("Webhook POST failed: {}", response.status()),
+            Err(e) => format!("Webhook POST error: {}", e),
+        };
+        if attempt == WEBHOOK_ATTEMPTS {
+            return Err(format!("{} (after {} attempts)", error, attempt));
+        }
+        MutationLogger::warn_file(url, &format!("{}; retrying in {:?}", error, delay));
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+/// A Slack Block Kit message for `reports`: a header with the overall score,
+/// the first survived mutations, and an attachment colored green or red
+/// depending on whether the score reaches `min_coverage_percent`.
+pub fn slack_payload(reports: &[(String, MutationReport)], min_coverage_percent: Option<f64>) -> serde_json::Value {
+    let detected: usize = reports.iter().map(|(_, report)| report.detected_mutations()).sum();
+    let scored: usize = reports.iter().map(|(_, report)| report.scored_mutations()).sum();
+    let total: usize = reports.iter().map(|(_, report)| report.total_mutations).sum();
+    let score = if scored > 0 { detected as f64 / scored as f64 * 100.0 } else { 0.0 };
+    let passed = min_coverage_percent.is_none_or(|min| score >= min);
+
+    let survivors: Vec<String> = reports
+        .iter()
+        .flat_map(|(file, report)| {
+            report
+                .results
+                .iter()
+                .filter(|result| result.test_result == TestOutcome::Survived)
+                .map(move |result| (file, result))
+        })
+        .map(|(file, result)| match survivor_diff(result, false) {
+            Some(diff) => {
+                let diff: Vec<String> = diff.lines().map(truncate_line).collect();
+                format!("*{}*\n```{}\n```", file, diff.join("\n"))
+            }
+            None => {
+                let line = result.candidate.line.checked_sub(1).and_then(|index| result.mutated_code.lines().nth(index));
+                format!("*{}:{}* `{}`", file, result.candidate.line, truncate_line(line.unwrap_or_default().trim()))
+            }
+        })
+        .collect();
+
+    let survivor_text = if survivors.is_empty() {
+        "No mutations survived.".to_string()
+    } else {
+        // Leave room for the "…and N more" line so the section stays under Slack's limit.
+        let budget = SLACK_SECTION_LIMIT - 40;
+        let mut text = format!("*Survived mutations ({})*", survivors.len());
+        let mut shown = 0;
+        for survivor in survivors.iter().take(SLACK_SURVIVOR_LIMIT) {
+            if text.chars().count() + 1 + survivor.chars().count() > budget {
+                break;
+            }
+            text.push('\n');
+            text.push_str(survivor);
+            shown += 1;
+        }
+        if survivors.len() > shown {
+            text.push_str(&format!("\n…and {} more", survivors.len() - shown));
+        }
+        text
+    };
+
+    let verdict = match (min_coverage_percent, passed) {
+        (Some(min), true) => format!("Passed: score is at least {:.2}%", min),
+        (Some(min), false) => format!("Failed: score is below {:.2}%", min),
+        (None, _) => "No minimum score configured".to_string(),
+    };
+
+    serde_json::json!({
+        "text": format!("Mutation score: {:.2}%", score),
+        "blocks": [
+            {
+                "type": "header",
+                "text": { "type": "plain_text", "text": format!("Mutation score: {:.2}%", score) }
+            },
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": survivor_text }
+            }
+        ],
+        "attachments": [
+            {
+                "color": if passed { "#2eb886" } else { "#e01e5a" },
+                "blocks": [
+                    {
+                        "type": "section",
+                        "text": {
+                            "type": "mrkdwn",
+                            "text": format!("{}\n{} of {} scored mutations detected across {} file(s), {} generated", verdict, detected, scored, reports.len(), total)
+                        }
+                    }
+                ]
+            }
+        ]
+    })
+}
+
+/// `line` cut to [`SLACK_LINE_LIMIT`] characters, with an ellipsis when cut.
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() <= SLACK_LINE_LIMIT {
+        return line.to_string();
+    }
+    let mut cut: String = line.chars().take(SLACK_LINE_LIMIT).collect();
+    cut.push('…');
+    cut
+}
+
+/// The `X-Signature-256` value for `body`: `sha256=` followed by the hex
+/// HMAC-SHA256 of the body keyed with `secret`.
+pub fn webhook_signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Sends the same set of reports to every sink. A failing sink is logged and
+/// does not prevent the remaining sinks from receiving the report.
+pub async fn emit_reports(sinks: &[ReportSink], reports: &[(String, MutationReport)]) -> usize {
+    let mut failures = 0;
+    for sink in sinks {
+        if let Err(e) = sink.emit(reports).await {
+            MutationLogger::error_file(sink.target(), &e);
+            failures += 1;
+        }
+    }
+    failures
+}
+
+fn write_report_file(reports: &[(String, MutationReport)], format: &ReportFormat, path: &str) -> Result<(), String> {
+    let generator = ReportGenerator::new();
+
+    if let [(_, report)] = reports {
+        generator.generate_report(report, format.clone(), Some(path))?;
+        return Ok(());
+    }
+
+    if *format == ReportFormat::JsonLines {
+        let write_error = |e: std::io::Error| format!("Failed to write JSON lines report to {}: {}", path, e);
+        let mut writer = BufWriter::new(fs::File::create(path).map_err(write_error)?);
+        for (file, report) in reports {
+            generator.write_json_lines(report, Some(file), &mut writer).map_err(write_error)?;
+        }
+        return writer.flush().map_err(write_error);
+    }
+
+    let content = if *format == ReportFormat::Cobertura {
+        let reports: Vec<(&str, &MutationReport)> = reports.iter().map(|(file, report)| (file.as_str(), report)).collect();
+        generator.generate_cobertura(&reports)
+    } else if *format == ReportFormat::Sarif {
+        let reports: Vec<(&str, &MutationReport)> = reports.iter().map(|(file, report)| (file.as_str(), report)).collect();
+        generator.generate_sarif(&reports)?
+    } else if *format == ReportFormat::JUnit {
+        let reports: Vec<(&str, &MutationReport)> = reports.iter().map(|(file, report)| (file.as_str(), report)).collect();
+        generator.generate_junit(&reports)
+    } else if *format == ReportFormat::HTML {
+        let reports: Vec<(&str, &MutationReport)> = reports.iter().map(|(file, report)| (file.as_str(), report)).collect();
+        generator.generate_html(&reports)
+    } else if *format == ReportFormat::CSV {
+        let reports: Vec<(&str, &MutationReport)> = reports.iter().map(|(file, report)| (file.as_str(), report)).collect();
+        generator.generate_csv(&reports)
+    } else if *format == ReportFormat::JSON {
+        let map: BTreeMap<&String, &MutationReport> = reports.iter().map(|(file, report)| (file, report)).collect();
+        serde_json::to_string_pretty(&map)
+            .map_err(|e| format!("Failed to serialize multi-file report: {}", e))?
+    } else {
+        let mut content = String::new();
+        for (file, report) in reports {
+            content.push_str(&format!("# {}\n\n", file));
+            content.push_str(&generator.generate_report(report, format.clone(), None)?);
+            content.push('\n');
+        }
+        content
+    };
+
+    fs::write(path, content).map_err(|e| format!("Failed to write report to {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sinks_from_options() {
+        let config = MutationTestConfig::default();
+        let sinks = ReportSink::from_options(&config, Some("out.json"), None, None, Some("http://localhost/hook"), Some("s3cret"), WebhookFormat::Raw);
+
+        assert_eq!(sinks.len(), 3);
+        assert_eq!(sinks[0], ReportSink::Console);
+        assert!(matches!(&sinks[1], ReportSink::File { format: ReportFormat::JSON, path } if path == "out.json"));
+        assert!(matches!(&sinks[2], ReportSink::Webhook { url, secret, format: WebhookFormat::Raw, .. } if url == "http://localhost/hook" && secret.as_deref() == Some("s3cret")));
+    }
+
+    #[test]
+    fn test_webhook_signature_matches_known_vector() {
+        // The example from GitHub's webhook validation docs.
+        assert_eq!(
+            webhook_signature("It's a Secret to Everybody", b"Hello, World!"),
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+    }
+
+    /// Serves `statuses` in order, one per request, and counts the requests.
+    async fn scripted_server(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post(move || {
+                let hit = counter.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[hit.min(statuses.len() - 1)];
+                async move { axum::http::StatusCode::from_u16(status).unwrap() }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, hits)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_server_errors_but_not_client_errors() {
+        let delay = Duration::from_millis(1);
+
+        let (url, hits) = scripted_server(vec![500, 500, 200]).await;
+        post_webhook_with_backoff(&url, "{}".to_string(), None, delay).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, hits) = scripted_server(vec![503]).await;
+        let error = post_webhook_with_backoff(&url, "{}".to_string(), None, delay).await.unwrap_err();
+        assert!(error.contains("after 3 attempts"), "{}", error);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, hits) = scripted_server(vec![404, 200]).await;
+        assert!(post_webhook_with_backoff(&url, "{}".to_string(), None, delay).await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_slack_payload_has_blocks_and_the_score() {
+        let mut report = MutationReport::new();
+        report.total_mutations = 4;
+        report.killed_mutations = 3;
+        report.survived_mutations = 1;
+        report.mutation_score = 75.0;
+        let reports = vec![("src/lib.rs".to_string(), report)];
+
+        let payload = slack_payload(&reports, Some(80.0));
+        let blocks = payload["blocks"].as_array().unwrap();
+        assert_eq!(blocks[0]["type"], "header");
+        assert_eq!(blocks[0]["text"]["text"], "Mutation score: 75.00%");
+        assert_eq!(payload["attachments"][0]["color"], "#e01e5a");
+
+        let payload = slack_payload(&reports, Some(70.0));
+        assert_eq!(payload["attachments"][0]["color"], "#2eb886");
+    }
+
+    #[test]
+    fn test_slack_survivors_quote_only_the_mutated_line_and_fit_the_section() {
+        use crate::mutation::types::{MutationCandidate, MutationResult, MutationType};
+
+        let filler = "// padding\n".repeat(2000);
+        let mut report = MutationReport::new();
+        for line in 1..=12 {
+            report.results.push(MutationResult {
+                candidate: MutationCandidate {
+                    line,
+                    column: 1,
+                    original_code: "unmatched".to_string(),
+                    mutation_type: MutationType::ArithmeticOperator,
+                    suggested_mutations: vec![],
+                    module_path: String::new(),
+                    byte_start: None,
+                    byte_end: None,
+                },
+                mutated_code: format!("{}{}\n{}", "\n".repeat(line - 1), "x".repeat(5000), filler),
+                test_result: TestOutcome::Survived,
+                execution_time_ms: 0,
+                error_message: None,
+                killing_tests: None,
+                suggested_improvement: None,
+            });
+        }
+        report.total_mutations = 12;
+        report.survived_mutations = 12;
+        let reports = vec![("src/lib.rs".to_string(), report)];
+
+        let payload = slack_payload(&reports, None);
+        let text = payload["blocks"][1]["text"]["text"].as_str().unwrap();
+        assert!(text.chars().count() <= SLACK_SECTION_LIMIT, "{} characters", text.chars().count());
+        assert!(!text.contains("padding"));
+        assert!(text.contains(&format!("`{}…`", "x".repeat(SLACK_LINE_LIMIT))));
+        assert!(text.contains("…and 2 more"));
+    }
+
+    #[tokio::test]
+    async fn test_emit_to_two_sinks() {
+        let dir = tempdir().unwrap();
+        let json_path = dir.path().join("report.json").to_string_lossy().to_string();
+        let md_path = dir.path().join("report.md").to_string_lossy().to_string();
+
+        let config = MutationTestConfig {
+            report_format: Some(ReportFormat::Markdown),
+            report_output_path: Some(md_path.clone()),
+            ..Default::default()
+        };
+        let sinks = ReportSink::from_options(&config, Some(&json_path), None, None, None, None, WebhookFormat::Raw);
+        assert_eq!(sinks.len(), 2);
+
+        let mut report = MutationReport::new();
+        report.total_mutations = 3;
+        let reports = vec![("src/lib.rs".to_string(), report)];
+
+        let failures = emit_reports(&sinks, &reports).await;
+        assert_eq!(failures, 0);
+
+        let json: MutationReport = serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json.total_mutations, 3);
+        let markdown = fs::read_to_string(&md_path).unwrap();
+        assert!(markdown.contains("# Mutation Testing Report"));
+    }
+}
@@ -4,12 +4,16 @@ impl From<crate::mutation::runner::TestOutcome> for TestOutcome {
             crate::mutation::runner::TestOutcome::Killed { killing_tests } => TestOutcome::Killed { killing_tests },
             crate::mutation::runner::TestOutcome::Survived => TestOutcome::Survived,
             crate::mutation::runner::TestOutcome::Timeout => TestOutcome::Timeout,
-            crate::mutation::runner::TestOutcome::Error => TestOutcome::Error,
+            crate::mutation::runner::TestOutcome::Error { .. } => TestOutcome::Error,
+            crate::mutation::runner::TestOutcome::CompileError { note } => TestOutcome::CompileError { note },
+            crate::mutation::runner::TestOutcome::Uncompilable => TestOutcome::Uncompilable,
         }
     }
 }
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,90 +23,209 @@ pub struct MutationCandidate {
     pub original_code: String,
     pub mutation_type: MutationType,
     pub suggested_mutations: Vec<String>,
+    #[serde(default)]
+    pub module_path: String,
+    /// Byte offsets of `original_code` in the source, when the analyzer could
+    /// pin them down. Preferred over `line`/`column` when applying mutations.
+    #[serde(default)]
+    pub byte_start: Option<usize>,
+    #[serde(default)]
+    pub byte_end: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ValueEnum)]
+/// Variants are declared implemented-first; `ValueEnum` (and so `--help` and
+/// `--filter-types`) follows declaration order. Types without a working
+/// analyzer are hidden from the CLI until they are implemented. Candidates
+/// on the same line and column are ordered by declaration order too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
 pub enum MutationType {
     // Operator mutations
     ArithmeticOperator,
     RelationalOperator,
     LogicalOperator,
+    OverflowMethod,
     AssignmentOperator,
-    BitwiseOperator,
     IncrementDecrement,
-    
+
     // Literal mutations
     BooleanLiteral,
     NumericLiteral,
     StringLiteral,
-    CharLiteral,
-    
-    // Boundary mutations
-    ConditionalBoundary,
-    LoopBoundary,
-    
+
     // Control flow mutations
-    StatementDeletion,
+    ConditionalBoundary,
+    PanicMacro,
+    ClosurePredicate,
     ReturnValue,
     BreakContinueReplacement,
-    
-    // Pattern-based mutations
-    NullCheck,
+    SwitchCase,
+
+    // Fallback mutations
+    DefaultValue,
     OptionalUnwrap,
+
+    // Structural mutations
+    FieldSwap,
+    StatementDeletion,
+
+    // Not yet implemented
+    #[value(hide = true)]
+    BitwiseOperator,
+    #[value(hide = true)]
+    CharLiteral,
+    #[value(hide = true)]
+    LoopBoundary,
+    #[value(hide = true)]
+    NullCheck,
+    #[value(hide = true)]
     VariableReference,
+    #[value(hide = true)]
     FunctionCall,
-    
-    // Advanced mutations (requires AST)
+    #[value(hide = true)]
     ConstantReplacement,
+    #[value(hide = true)]
     MethodChain,
+    #[value(hide = true)]
     ExceptionHandling,
-    SwitchCase,
+}
+
+impl MutationType {
+    /// Mutations that only change a value or operator inside a function
+    /// body, never an item's signature.
+    pub fn is_body_only(&self) -> bool {
+        matches!(
+            self,
+            MutationType::ArithmeticOperator
+                | MutationType::RelationalOperator
+                | MutationType::LogicalOperator
+                | MutationType::OverflowMethod
+                | MutationType::AssignmentOperator
+                | MutationType::IncrementDecrement
+                | MutationType::BooleanLiteral
+                | MutationType::NumericLiteral
+                | MutationType::StringLiteral
+                | MutationType::ConditionalBoundary
+                | MutationType::ClosurePredicate
+                | MutationType::ReturnValue
+                | MutationType::BreakContinueReplacement
+                | MutationType::SwitchCase
+                | MutationType::OptionalUnwrap
+                | MutationType::StatementDeletion
+        )
+    }
+
+    pub fn is_implemented(&self) -> bool {
+        matches!(
+            self,
+            MutationType::ArithmeticOperator
+                | MutationType::RelationalOperator
+                | MutationType::LogicalOperator
+                | MutationType::OverflowMethod
+                | MutationType::AssignmentOperator
+                | MutationType::IncrementDecrement
+                | MutationType::BooleanLiteral
+                | MutationType::NumericLiteral
+                | MutationType::StringLiteral
+                | MutationType::ConditionalBoundary
+                | MutationType::PanicMacro
+                | MutationType::ClosurePredicate
+                | MutationType::ReturnValue
+                | MutationType::BreakContinueReplacement
+                | MutationType::SwitchCase
+                | MutationType::DefaultValue
+                | MutationType::OptionalUnwrap
+                | MutationType::FieldSwap
+                | MutationType::StatementDeletion
+        )
+    }
+}
+
+impl fmt::Display for MutationType {
+    // Human-facing label. Reports and stored results keep using the `Debug`
+    // variant name as the stable identifier.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)?;
+        if !self.is_implemented() {
+            write!(f, " (experimental)")?;
+        }
+        Ok(())
+    }
 }
 
 impl FromStr for MutationType {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            // Operator mutations
             "arithmeticoperator" | "arithmetic" => Ok(MutationType::ArithmeticOperator),
             "relationaloperator" | "relational" => Ok(MutationType::RelationalOperator),
             "logicaloperator" | "logical" => Ok(MutationType::LogicalOperator),
-            "assignmentoperator" | "assignment" => Ok(MutationType::AssignmentOperator),
-            "bitwiseoperator" | "bitwise" => Ok(MutationType::BitwiseOperator),
-            "incrementdecrement" | "increment" => Ok(MutationType::IncrementDecrement),
-            
-            // Literal mutations
+            "overflowmethod" | "overflow" => Ok(MutationType::OverflowMethod),
             "booleanliteral" | "boolean" => Ok(MutationType::BooleanLiteral),
             "numericliteral" | "numeric" => Ok(MutationType::NumericLiteral),
             "stringliteral" | "string" => Ok(MutationType::StringLiteral),
-            "charliteral" | "char" => Ok(MutationType::CharLiteral),
-            
-            // Boundary mutations
+            "panicmacro" | "panic" => Ok(MutationType::PanicMacro),
+            "closurepredicate" | "predicate" => Ok(MutationType::ClosurePredicate),
+            "defaultvalue" | "default" => Ok(MutationType::DefaultValue),
+            "optionalunwrap" | "optional" | "unwrap" => Ok(MutationType::OptionalUnwrap),
+            "fieldswap" | "swap" => Ok(MutationType::FieldSwap),
+            "assignmentoperator" | "assignment" => Ok(MutationType::AssignmentOperator),
+            "incrementdecrement" | "increment" | "decrement" => Ok(MutationType::IncrementDecrement),
             "conditionalboundary" | "conditional" => Ok(MutationType::ConditionalBoundary),
-            "loopboundary" | "loop" => Ok(MutationType::LoopBoundary),
-            
-            // Control flow mutations
             "statementdeletion" | "statement" => Ok(MutationType::StatementDeletion),
             "returnvalue" | "return" => Ok(MutationType::ReturnValue),
-            "breakcontinuereplacement" | "breakreplacement" => Ok(MutationType::BreakContinueReplacement),
-            
-            // Pattern-based mutations
+            "breakcontinuereplacement" | "breakreplacement" | "breakcontinue" => {
+                Ok(MutationType::BreakContinueReplacement)
+            }
+            "switchcase" | "switch" => Ok(MutationType::SwitchCase),
+
+            // Not yet implemented
+            "bitwiseoperator" | "bitwise" => Ok(MutationType::BitwiseOperator),
+            "charliteral" | "char" => Ok(MutationType::CharLiteral),
+            "loopboundary" | "loop" => Ok(MutationType::LoopBoundary),
             "nullcheck" | "null" => Ok(MutationType::NullCheck),
-            "optionalunwrap" | "optional" => Ok(MutationType::OptionalUnwrap),
             "variablereference" | "variable" => Ok(MutationType::VariableReference),
             "functioncall" | "function" => Ok(MutationType::FunctionCall),
-            
-            // Advanced mutations (requires AST)
             "constantreplacement" | "constant" => Ok(MutationType::ConstantReplacement),
             "methodchain" | "chain" => Ok(MutationType::MethodChain),
             "exceptionhandling" | "exception" => Ok(MutationType::ExceptionHandling),
-            "switchcase" | "switch" => Ok(MutationType::SwitchCase),
-            
+
             _ => Err(format!("Unknown mutation type: {}", s)),
         }
     }
 }
 
+/// A replacement for a numeric literal `n`. `MaxValue`, `MinValue` and
+/// `FlipBits` use the literal's suffix type, or `i32` without one, and apply
+/// to integers only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumericMutationKind {
+    PlusOne,
+    MinusOne,
+    Negate,
+    Zero,
+    One,
+    MaxValue,
+    MinValue,
+    FlipBits,
+}
+
+impl FromStr for NumericMutationKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['_', '-'], "").as_str() {
+            "plusone" => Ok(NumericMutationKind::PlusOne),
+            "minusone" => Ok(NumericMutationKind::MinusOne),
+            "negate" => Ok(NumericMutationKind::Negate),
+            "zero" => Ok(NumericMutationKind::Zero),
+            "one" => Ok(NumericMutationKind::One),
+            "maxvalue" | "max" => Ok(NumericMutationKind::MaxValue),
+            "minvalue" | "min" => Ok(NumericMutationKind::MinValue),
+            "flipbits" | "not" => Ok(NumericMutationKind::FlipBits),
+            _ => Err(format!("Unknown numeric mutation: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MutationResult {
     pub candidate: MutationCandidate,
@@ -114,31 +237,103 @@ pub struct MutationResult {
     pub suggested_improvement: Option<String>,
 }
 
+impl MutationResult {
+    /// Short stable id for this mutant, used to refer back to it across runs
+    /// (e.g. `explain-survivor`).
+    pub fn fingerprint(&self) -> String {
+        mutation_fingerprint(&self.candidate, &self.mutated_code)
+    }
+}
+
+/// FNV-1a over the candidate's identity and the mutated source, so the id
+/// doesn't depend on the std hasher staying the same between releases.
+pub fn mutation_fingerprint(candidate: &MutationCandidate, mutated_code: &str) -> String {
+    let key = format!(
+        "{:?}\0{}\0{}\0{}\0{}\0{}",
+        candidate.mutation_type, candidate.module_path, candidate.line, candidate.column, candidate.original_code, mutated_code
+    );
+    let hash = key
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+    format!("{:016x}", hash)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TestOutcome {
     Killed { killing_tests: Vec<String> },
     Survived,
     Timeout,
+    /// The mutant's tests could not be run; the result's `error_message`
+    /// says why.
     Error,
     Skipped,
+    /// The mutant failed to build for a reason that says nothing about the
+    /// tests, e.g. a lint promoted to an error (`note` is `warning-promoted`).
+    CompileError { note: String },
+    /// The mutated code does not build at all, so it is not a meaningful mutant.
+    Uncompilable,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ReportFormat {
     JSON,
     CSV,
     HTML,
     Markdown,
+    Cobertura,
+    Sarif,
+    JUnit,
+    /// One compact JSON object per line: a summary, then each result.
+    JsonLines,
+    #[default]
     Console
 }
 
-impl Default for ReportFormat {
-    fn default() -> Self {
-        Self::Console
-    }
+/// Output format for listing mutation candidates without running them.
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum CandidateFormat {
+    #[default]
+    Text,
+    Rustc,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How candidates are chosen when a file has more mutants than
+/// `max_total_mutations`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleStrategy {
+    /// The first candidates in source order.
+    #[default]
+    First,
+    /// A random subset, reproducible with `shuffle_seed`.
+    Random,
+    /// A random subset in which each mutation type keeps its share of the
+    /// mutants.
+    Stratified,
+}
+
+/// How many candidates a sampled run kept out of all that were found.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SamplingSummary {
+    pub strategy: SampleStrategy,
+    pub sampled_candidates: usize,
+    pub total_candidates: usize,
+    /// Mutants on the candidates left out of the sample.
+    #[serde(default)]
+    pub sampled_out_mutations: usize,
+}
+
+/// Body posted to `--webhook`: the report JSON as-is, or a Slack Block Kit
+/// message summarising it.
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum WebhookFormat {
+    #[default]
+    Raw,
+    Slack,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MutationTestConfig {
     pub timeout_seconds: u64,
     pub max_mutations_per_line: usize,
@@ -147,12 +342,114 @@ pub struct MutationTestConfig {
     pub mutation_types: Vec<MutationType>,
     pub excluded_mutations: Vec<MutationType>,
     pub excluded_files: Vec<String>,
+    #[serde(default)]
+    pub excluded_dirs: Vec<String>,
     pub excluded_functions: Vec<String>,
     pub min_coverage_percent: Option<f64>,
     pub parallel_jobs: Option<usize>,
     pub report_format: Option<ReportFormat>,
     pub report_output_path: Option<String>,
     pub ast_mutations_enabled: bool,
+    #[serde(default)]
+    pub shuffle_seed: Option<u64>,
+    #[serde(default)]
+    pub group_by_module: bool,
+    #[serde(default)]
+    pub allow_non_test_command: bool,
+    #[serde(default)]
+    pub skip_trait_impls: bool,
+    #[serde(default = "default_skipped_traits")]
+    pub skipped_traits: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_compiles: Option<usize>,
+    #[serde(default)]
+    pub debug_skips: bool,
+    #[serde(default)]
+    pub max_error_rate: Option<f64>,
+    #[serde(default)]
+    pub allowed_files: Vec<String>,
+    /// Package name and edition of the scratch crate each mutant is built in.
+    #[serde(default = "default_crate_name")]
+    pub crate_name: String,
+    #[serde(default = "default_crate_edition")]
+    pub crate_edition: String,
+    #[serde(default)]
+    pub cap_lints: bool,
+    #[serde(default)]
+    pub incremental_workspace: bool,
+    /// Shell command run for each survivor; see `hooks::render_survivor_command`.
+    #[serde(default)]
+    pub on_survivor_command: Option<String>,
+    /// Run `on_survivor_command` as each survivor is found instead of after the run.
+    #[serde(default)]
+    pub on_survivor_inline: bool,
+    #[serde(default)]
+    pub shared_target_dir: bool,
+    #[serde(default)]
+    pub timeout_multiplier: Option<f64>,
+    #[serde(default)]
+    pub flaky_retries: u32,
+    #[serde(default)]
+    pub project_root: Option<String>,
+    /// Upper bound on the mutants tested per file; when a file has more,
+    /// candidates are sampled with `sample_strategy`.
+    #[serde(default)]
+    pub max_total_mutations: Option<usize>,
+    #[serde(default)]
+    pub sample_strategy: SampleStrategy,
+    /// Replacements proposed for integer literals, in this order.
+    #[serde(default = "default_numeric_mutations")]
+    pub numeric_mutations: Vec<NumericMutationKind>,
+    /// An `llvm-cov export` or tarpaulin JSON report. Candidates on lines it
+    /// records as never executed are not tested.
+    #[serde(default)]
+    pub coverage_file: Option<String>,
+    /// Files larger than this are skipped with a warning instead of being
+    /// analyzed.
+    #[serde(default)]
+    pub max_file_size_bytes: Option<u64>,
+}
+
+fn default_skipped_traits() -> Vec<String> {
+    vec!["PartialEq".to_string(), "PartialOrd".to_string(), "Hash".to_string()]
+}
+
+fn default_numeric_mutations() -> Vec<NumericMutationKind> {
+    vec![
+        NumericMutationKind::PlusOne,
+        NumericMutationKind::MinusOne,
+        NumericMutationKind::Negate,
+        NumericMutationKind::Zero,
+        NumericMutationKind::One,
+    ]
+}
+
+fn default_crate_name() -> String {
+    "mutation_test".to_string()
+}
+
+fn default_crate_edition() -> String {
+    "2021".to_string()
+}
+
+impl MutationTestConfig {
+    /// Number of candidates tested at once. `parallel_jobs` (set by `--threads`)
+    /// is the only concurrency knob; when unset it falls back to the CPU count.
+    pub fn effective_threads(&self) -> usize {
+        self.parallel_jobs
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            .max(1)
+    }
+
+    /// Restricts the run to `filter` (from `--filter-types` or a job) when it
+    /// names at least one type; otherwise the configured types are kept.
+    pub fn apply_filter_types(&mut self, filter: Option<&[MutationType]>) {
+        if let Some(filter) = filter
+            && !filter.is_empty()
+        {
+            self.mutation_types = filter.to_vec();
+        }
+    }
 }
 
 impl Default for MutationTestConfig {
@@ -176,12 +473,37 @@ impl Default for MutationTestConfig {
             ],
             excluded_mutations: vec![],
             excluded_files: vec![],
+            excluded_dirs: vec![],
             excluded_functions: vec![],
             min_coverage_percent: Some(75.0),
             parallel_jobs: Some(4),
             report_format: Some(ReportFormat::Console),
             report_output_path: None,
             ast_mutations_enabled: false,
+            shuffle_seed: None,
+            group_by_module: false,
+            allow_non_test_command: false,
+            skip_trait_impls: false,
+            skipped_traits: default_skipped_traits(),
+            max_concurrent_compiles: None,
+            debug_skips: false,
+            max_error_rate: None,
+            allowed_files: vec![],
+            crate_name: default_crate_name(),
+            crate_edition: default_crate_edition(),
+            cap_lints: false,
+            incremental_workspace: false,
+            on_survivor_command: None,
+            on_survivor_inline: false,
+            shared_target_dir: false,
+            timeout_multiplier: None,
+            flaky_retries: 0,
+            project_root: None,
+            max_total_mutations: None,
+            sample_strategy: SampleStrategy::First,
+            numeric_mutations: default_numeric_mutations(),
+            coverage_file: None,
+            max_file_size_bytes: None,
         }
     }
 }
@@ -194,9 +516,62 @@ pub struct MutationReport {
     pub error_mutations: usize,
     pub timeout_mutations: usize,
     pub skipped_mutations: usize,
+    #[serde(default)]
+    pub compile_error_mutations: usize,
+    #[serde(default)]
+    pub uncompilable_mutations: usize,
     pub mutation_score: f64,
-    pub execution_time_seconds: f64,
+    /// Real time the run took, from discovery to the last result.
+    #[serde(default, alias = "execution_time_seconds")]
+    pub total_wall_time_seconds: f64,
+    /// Sum of the mutants' own run times; exceeds the wall time when mutants
+    /// run in parallel.
+    #[serde(default)]
+    pub total_cpu_time_seconds: f64,
     pub results: Vec<MutationResult>,
+    #[serde(default)]
+    pub savings: SavingsSummary,
+    /// Path of the mutated file, for formats that attribute results to files.
+    #[serde(default)]
+    pub source_file: Option<String>,
+    /// Set when the run tested only a sample of the candidates.
+    #[serde(default)]
+    pub sampling: Option<SamplingSummary>,
+    /// Mutants left untested because no test executes their line.
+    #[serde(default)]
+    pub uncovered_mutations: usize,
+    /// Mutants left untested because their line had more than
+    /// `max_mutations_per_line` candidates.
+    #[serde(default)]
+    pub capped_mutations: usize,
+    /// Why the file could not be tested at all, such as it not being valid
+    /// UTF-8. Such a report has no results.
+    #[serde(default)]
+    pub file_error: Option<String>,
+    /// Run time of the results that were executed, kept so the savings
+    /// average doesn't re-sum every result.
+    #[serde(skip)]
+    pub executed_time_ms: u64,
+}
+
+/// What caching and skipping saved during a run. `skipped` counts mutants
+/// skipped outright, on lines no test covers, over `max_mutations_per_line`
+/// or left out of a sample. The time estimate assumes a cached or skipped
+/// mutation would have taken the average executed run time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SavingsSummary {
+    pub mutations_run: usize,
+    /// Results reused from an earlier run. Always 0 until results are cached.
+    #[serde(default)]
+    pub cache_hits: usize,
+    pub skipped: usize,
+    pub estimated_time_saved_seconds: f64,
+}
+
+impl Default for MutationReport {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MutationReport {
@@ -208,15 +583,32 @@ impl MutationReport {
             error_mutations: 0,
             timeout_mutations: 0,
             skipped_mutations: 0,
+            compile_error_mutations: 0,
+            uncompilable_mutations: 0,
             mutation_score: 0.0,
-            execution_time_seconds: 0.0,
+            total_wall_time_seconds: 0.0,
+            total_cpu_time_seconds: 0.0,
             results: Vec::new(),
+            savings: SavingsSummary::default(),
+            source_file: None,
+            sampling: None,
+            uncovered_mutations: 0,
+            capped_mutations: 0,
+            file_error: None,
+            executed_time_ms: 0,
         }
     }
 
+    /// Records the mutants left untested because no test executes their line.
+    pub fn with_uncovered(mut self, uncovered_mutations: usize) -> Self {
+        self.uncovered_mutations = uncovered_mutations;
+        self.calculate_savings();
+        self
+    }
+
     pub fn add_result(&mut self, result: MutationResult) {
         self.total_mutations += 1;
-        self.execution_time_seconds += result.execution_time_ms as f64 / 1000.0;
+        self.total_cpu_time_seconds += result.execution_time_ms as f64 / 1000.0;
 
         match result.test_result {
             TestOutcome::Killed { .. } => self.killed_mutations += 1,
@@ -224,15 +616,68 @@ impl MutationReport {
             TestOutcome::Error => self.error_mutations += 1,
             TestOutcome::Timeout => self.timeout_mutations += 1,
             TestOutcome::Skipped => self.skipped_mutations += 1,
+            TestOutcome::CompileError { .. } => self.compile_error_mutations += 1,
+            TestOutcome::Uncompilable => self.uncompilable_mutations += 1,
+        }
+        if !matches!(result.test_result, TestOutcome::Skipped) {
+            self.executed_time_ms += result.execution_time_ms;
         }
 
         self.results.push(result);
         self.calculate_score();
+        self.calculate_savings();
+    }
+
+    fn calculate_savings(&mut self) {
+        let mutations_run = self.total_mutations - self.skipped_mutations;
+        let average_seconds = if mutations_run > 0 {
+            self.executed_time_ms as f64 / mutations_run as f64 / 1000.0
+        } else {
+            0.0
+        };
+
+        let cache_hits = 0;
+        let sampled_out = self.sampling.as_ref().map_or(0, |s| s.sampled_out_mutations);
+        let skipped = self.skipped_mutations + self.uncovered_mutations + self.capped_mutations + sampled_out;
+        self.savings = SavingsSummary {
+            mutations_run,
+            cache_hits,
+            skipped,
+            estimated_time_saved_seconds: (cache_hits + skipped) as f64 * average_seconds,
+        };
+    }
+
+    /// Splits the results into one report per module path, keyed by path
+    /// (empty for the crate root).
+    pub fn by_module(&self) -> BTreeMap<String, MutationReport> {
+        let mut modules: BTreeMap<String, MutationReport> = BTreeMap::new();
+        for result in &self.results {
+            modules
+                .entry(result.candidate.module_path.clone())
+                .or_default()
+                .add_result(result.clone());
+        }
+        modules
+    }
+
+    /// Killed or timed-out mutations, the numerator of the score.
+    pub fn detected_mutations(&self) -> usize {
+        self.killed_mutations + self.timeout_mutations
+    }
+
+    /// Mutations whose outcome says something about the tests, the
+    /// denominator of the score.
+    pub fn scored_mutations(&self) -> usize {
+        self.total_mutations
+            - self.skipped_mutations
+            - self.error_mutations
+            - self.compile_error_mutations
+            - self.uncompilable_mutations
     }
 
     fn calculate_score(&mut self) {
-        let detected = self.killed_mutations + self.timeout_mutations;
-        let total_tested = self.total_mutations - self.skipped_mutations - self.error_mutations;
+        let detected = self.detected_mutations();
+        let total_tested = self.scored_mutations();
 
         if total_tested > 0 {
             self.mutation_score = (detected as f64 / total_tested as f64) * 100.0;
@@ -247,4 +692,154 @@ pub struct MutationJob {
     pub file: String,
     pub config: Option<MutationTestConfig>,
     pub filter_types: Option<Vec<MutationType>>,
+    /// AMQP message priority; higher values are consumed first.
+    #[serde(default)]
+    pub priority: Option<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutation_type_listing_order() {
+        let listed: Vec<String> = MutationType::value_variants()
+            .iter()
+            .filter_map(|t| t.to_possible_value())
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                "arithmetic-operator",
+                "relational-operator",
+                "logical-operator",
+                "overflow-method",
+                "assignment-operator",
+                "increment-decrement",
+                "boolean-literal",
+                "numeric-literal",
+                "string-literal",
+                "conditional-boundary",
+                "panic-macro",
+                "closure-predicate",
+                "return-value",
+                "break-continue-replacement",
+                "switch-case",
+                "default-value",
+                "optional-unwrap",
+                "field-swap",
+                "statement-deletion",
+            ]
+        );
+
+        let first_unimplemented = MutationType::value_variants().iter().position(|t| !t.is_implemented()).unwrap();
+        assert!(MutationType::value_variants()[first_unimplemented..].iter().all(|t| !t.is_implemented()));
+        for mutation_type in MutationType::value_variants() {
+            let hidden = mutation_type.to_possible_value().unwrap().is_hide_set();
+            assert_eq!(hidden, !mutation_type.is_implemented(), "{:?}", mutation_type);
+        }
+
+        assert_eq!(MutationType::ArithmeticOperator.to_string(), "ArithmeticOperator");
+        assert_eq!(MutationType::ExceptionHandling.to_string(), "ExceptionHandling (experimental)");
+        assert_eq!(format!("{:?}", MutationType::PanicMacro), "PanicMacro");
+    }
+
+    #[test]
+    fn test_time_saved_reflects_skipped_and_uncovered_mutants() {
+        let result = |test_result: TestOutcome, execution_time_ms: u64| MutationResult {
+            candidate: MutationCandidate {
+                line: 1,
+                column: 1,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+            mutated_code: String::new(),
+            test_result,
+            execution_time_ms,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        };
+
+        let mut report = MutationReport::new();
+        report.add_result(result(TestOutcome::Survived, 2000));
+        report.add_result(result(TestOutcome::Killed { killing_tests: vec![] }, 4000));
+        report.add_result(result(TestOutcome::Skipped, 0));
+        assert_eq!(report.savings.estimated_time_saved_seconds, 3.0);
+
+        let mut report = MutationReport::new().with_uncovered(2);
+        report.add_result(result(TestOutcome::Survived, 2000));
+        report.add_result(result(TestOutcome::Killed { killing_tests: vec![] }, 4000));
+        report.add_result(result(TestOutcome::Skipped, 0));
+
+        assert_eq!(report.savings.mutations_run, 2);
+        assert_eq!(report.savings.cache_hits, 0);
+        assert_eq!(report.savings.skipped, 3);
+        assert_eq!(report.savings.estimated_time_saved_seconds, 9.0);
+        assert_eq!(MutationReport::new().with_uncovered(4).savings.skipped, 4);
+
+        let sampling = SamplingSummary {
+            strategy: SampleStrategy::First,
+            sampled_candidates: 1,
+            total_candidates: 3,
+            sampled_out_mutations: 5,
+        };
+        let report = MutationReport { sampling: Some(sampling), capped_mutations: 2, ..MutationReport::new() }
+            .with_uncovered(1);
+        assert_eq!(report.savings.skipped, 8);
+    }
+
+    #[test]
+    fn test_uncompilable_mutants_are_left_out_of_the_score() {
+        let result = |test_result: TestOutcome| MutationResult {
+            candidate: MutationCandidate {
+                line: 1,
+                column: 1,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+            mutated_code: String::new(),
+            test_result,
+            execution_time_ms: 10,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        };
+
+        let mut report = MutationReport::new();
+        report.add_result(result(TestOutcome::Killed { killing_tests: vec![] }));
+        report.add_result(result(TestOutcome::Survived));
+        report.add_result(result(TestOutcome::Uncompilable));
+        report.add_result(result(TestOutcome::Uncompilable));
+
+        assert_eq!(report.uncompilable_mutations, 2);
+        assert_eq!(report.mutation_score, 50.0);
+    }
+
+    #[test]
+    fn test_job_priority_survives_a_serialization_round_trip() {
+        let job = MutationJob {
+            file: "src/lib.rs".to_string(),
+            config: None,
+            filter_types: Some(vec![MutationType::ArithmeticOperator]),
+            priority: Some(7),
+        };
+
+        let parsed: MutationJob = serde_json::from_slice(&serde_json::to_vec(&job).unwrap()).unwrap();
+        assert_eq!(parsed.priority, Some(7));
+        assert_eq!(parsed.file, "src/lib.rs");
+
+        let legacy: MutationJob = serde_json::from_str(r#"{"file":"a.rs","config":null,"filter_types":null}"#).unwrap();
+        assert_eq!(legacy.priority, None);
+    }
 }
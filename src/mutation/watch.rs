@@ -0,0 +1,174 @@
+use crate::mutation::types::{MutationReport, MutationResult, MutationType, TestOutcome};
+use std::collections::{HashMap, HashSet};
+
+/// Identifies a mutant across runs of the same file by its content rather
+/// than its position, so editing lines above it doesn't make it look new:
+/// what it mutated, the mutated line it produced (which tells apart several
+/// suggestions for one candidate), and how many identical mutants precede it
+/// in the file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MutantKey {
+    mutation_type: MutationType,
+    module_path: String,
+    original_code: String,
+    mutated_line: String,
+    occurrence: usize,
+}
+
+impl MutantKey {
+    /// Keys for every result of `report`, in order.
+    pub fn all(report: &MutationReport) -> Vec<(MutantKey, &MutationResult)> {
+        let mut seen = HashMap::new();
+        report
+            .results
+            .iter()
+            .map(|result| {
+                let candidate = &result.candidate;
+                let mutated_line = candidate
+                    .line
+                    .checked_sub(1)
+                    .and_then(|index| result.mutated_code.lines().nth(index))
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                let mut key = Self {
+                    mutation_type: candidate.mutation_type.clone(),
+                    module_path: candidate.module_path.clone(),
+                    original_code: candidate.original_code.clone(),
+                    mutated_line,
+                    occurrence: 0,
+                };
+                let count = seen.entry(key.clone()).or_insert(0);
+                key.occurrence = *count;
+                *count += 1;
+                (key, result)
+            })
+            .collect()
+    }
+}
+
+/// Mutants whose outcome changed since the previous run of a file.
+#[derive(Debug, Default)]
+pub struct WatchDelta<'a> {
+    pub newly_survived: Vec<&'a MutationResult>,
+    pub newly_killed: Vec<&'a MutationResult>,
+}
+
+impl WatchDelta<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.newly_survived.is_empty() && self.newly_killed.is_empty()
+    }
+}
+
+/// The survivors of `report`, kept between runs to diff against.
+pub fn survivor_keys(report: &MutationReport) -> HashSet<MutantKey> {
+    MutantKey::all(report)
+        .into_iter()
+        .filter(|(_, result)| matches!(result.test_result, TestOutcome::Survived))
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Compares `current` against the survivors of the previous run. A survivor
+/// is new unless it survived before; a previous survivor counts as killed
+/// once it is detected. Mutants that no longer exist, e.g. because the line
+/// changed, are not reported.
+pub fn diff_runs<'a>(previous_survivors: &HashSet<MutantKey>, current: &'a MutationReport) -> WatchDelta<'a> {
+    let mut delta = WatchDelta::default();
+    for (key, result) in MutantKey::all(current) {
+        let survived_before = previous_survivors.contains(&key);
+        match result.test_result {
+            TestOutcome::Survived if !survived_before => delta.newly_survived.push(result),
+            TestOutcome::Killed { .. } | TestOutcome::Timeout if survived_before => delta.newly_killed.push(result),
+            _ => {}
+        }
+    }
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutation::types::MutationCandidate;
+
+    fn result(line: usize, original: &str, mutated_line: &str, test_result: TestOutcome) -> MutationResult {
+        MutationResult {
+            candidate: MutationCandidate {
+                line,
+                column: 7,
+                original_code: original.to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string(), "*".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+            mutated_code: format!("fn f() {{\n{}\n}}", mutated_line),
+            test_result,
+            execution_time_ms: 0,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        }
+    }
+
+    fn report(results: Vec<MutationResult>) -> MutationReport {
+        let mut report = MutationReport::new();
+        for result in results {
+            report.add_result(result);
+        }
+        report
+    }
+
+    #[test]
+    fn test_diff_reports_only_changed_survivors() {
+        let killed = || TestOutcome::Killed { killing_tests: vec!["adds".to_string()] };
+        let first = report(vec![
+            result(2, "+", "    a - b", TestOutcome::Survived),
+            result(2, "+", "    a * b", TestOutcome::Survived),
+            result(3, "-", "    c + d", killed()),
+        ]);
+        let second = report(vec![
+            result(2, "+", "    a - b", killed()),
+            result(2, "+", "    a * b", TestOutcome::Survived),
+            result(3, "-", "    c + d", TestOutcome::Survived),
+        ]);
+
+        let initial = diff_runs(&HashSet::new(), &first);
+        assert_eq!(initial.newly_survived.len(), 2);
+        assert!(initial.newly_killed.is_empty());
+
+        let delta = diff_runs(&survivor_keys(&first), &second);
+        assert_eq!(delta.newly_killed.len(), 1);
+        assert!(delta.newly_killed[0].mutated_code.contains("a - b"));
+        assert_eq!(delta.newly_survived.len(), 1);
+        assert_eq!(delta.newly_survived[0].candidate.line, 3);
+
+        assert!(diff_runs(&survivor_keys(&second), &second).is_empty());
+    }
+
+    #[test]
+    fn test_survivors_keep_their_key_when_lines_move() {
+        // The same two identical survivors, after two lines were added above them.
+        let shifted = |mut result: MutationResult, by: usize| {
+            result.candidate.line += by;
+            result.mutated_code = format!("{}{}", "// added\n".repeat(by), result.mutated_code);
+            result
+        };
+        let first = report(vec![
+            result(2, "+", "    a - b", TestOutcome::Survived),
+            result(2, "+", "    a - b", TestOutcome::Survived),
+        ]);
+        let second = report(vec![
+            shifted(result(2, "+", "    a - b", TestOutcome::Survived), 2),
+            shifted(result(2, "+", "        a - b", TestOutcome::Survived), 2),
+            shifted(result(2, "+", "    a - b", TestOutcome::Survived), 3),
+        ]);
+
+        assert_eq!(survivor_keys(&first).len(), 2);
+        let delta = diff_runs(&survivor_keys(&first), &second);
+        assert_eq!(delta.newly_survived.len(), 1);
+        assert_eq!(delta.newly_survived[0].candidate.line, 5);
+        assert!(delta.newly_killed.is_empty());
+    }
+}
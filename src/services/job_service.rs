@@ -0,0 +1,296 @@
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    app::RunHandle,
+    error::AppResult,
+    models::{
+        JobStatus, MutationProgressEvent, MutationTest, MutationTestJob, MutationTestStatus,
+        RunEvent, RunFinishedEvent, TestResult,
+    },
+    mutation::{
+        engine::{MutationEngine, RunProgress},
+        types::{self, MutationReport, MutationTestConfig},
+    },
+    services::mutation_service,
+};
+
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+pub async fn create_job(pool: &PgPool, mutation_test_id: Uuid) -> AppResult<MutationTestJob> {
+    let job = sqlx::query_as!(
+        MutationTestJob,
+        r#"
+        INSERT INTO mutation_jobs (mutation_test_id)
+        VALUES ($1)
+        RETURNING
+            id,
+            mutation_test_id,
+            status as "status: JobStatus",
+            total_mutations,
+            completed_mutations,
+            error_message,
+            started_at,
+            heartbeat_at,
+            finished_at
+        "#,
+        mutation_test_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    info!("Created job {} for mutation test {}", job.id, mutation_test_id);
+
+    Ok(job)
+}
+
+pub async fn record_heartbeat(pool: &PgPool, job_id: Uuid, progress: &RunProgress) -> AppResult<()> {
+    let total = progress.total.load(Ordering::Relaxed) as i32;
+    let completed = progress.completed.load(Ordering::Relaxed) as i32;
+
+    sqlx::query!(
+        r#"
+        UPDATE mutation_jobs
+        SET
+            heartbeat_at = clock_timestamp(),
+            total_mutations = NULLIF($2, 0),
+            completed_mutations = $3
+        WHERE id = $1
+        "#,
+        job_id,
+        total,
+        completed
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn finish_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    status: JobStatus,
+    error_message: Option<String>,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+        UPDATE mutation_jobs
+        SET
+            status = $2::mutation_job_status,
+            error_message = $3,
+            heartbeat_at = clock_timestamp(),
+            finished_at = clock_timestamp()
+        WHERE id = $1
+        "#,
+        job_id,
+        status as JobStatus,
+        error_message
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_latest_job(
+    pool: &PgPool,
+    mutation_test_id: Uuid,
+) -> AppResult<Option<MutationTestJob>> {
+    let job = sqlx::query_as!(
+        MutationTestJob,
+        r#"
+        SELECT
+            id,
+            mutation_test_id,
+            status as "status: JobStatus",
+            total_mutations,
+            completed_mutations,
+            error_message,
+            started_at,
+            heartbeat_at,
+            finished_at
+        FROM mutation_jobs
+        WHERE mutation_test_id = $1
+        ORDER BY started_at DESC
+        LIMIT 1
+        "#,
+        mutation_test_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job)
+}
+
+/// Running jobs whose heartbeat is older than `stale_after`. A slow run keeps
+/// heartbeating, so anything returned here has stopped making progress.
+pub async fn find_stale_jobs(pool: &PgPool, stale_after: Duration) -> AppResult<Vec<MutationTestJob>> {
+    let stale_after_secs = stale_after.as_secs_f64();
+
+    let jobs = sqlx::query_as!(
+        MutationTestJob,
+        r#"
+        SELECT
+            id,
+            mutation_test_id,
+            status as "status: JobStatus",
+            total_mutations,
+            completed_mutations,
+            error_message,
+            started_at,
+            heartbeat_at,
+            finished_at
+        FROM mutation_jobs
+        WHERE status = 'running'
+          AND heartbeat_at < NOW() - make_interval(secs => $1)
+        ORDER BY heartbeat_at
+        "#,
+        stale_after_secs
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(jobs)
+}
+
+/// Marks stale jobs and their mutation tests as failed, returning how many
+/// jobs were reconciled.
+pub async fn reconcile_stale_jobs(pool: &PgPool, stale_after: Duration) -> AppResult<usize> {
+    let stale_jobs = find_stale_jobs(pool, stale_after).await?;
+    for job in &stale_jobs {
+        warn!(
+            "Job {} for mutation test {} has not heartbeated since {}; marking failed",
+            job.id, job.mutation_test_id, job.heartbeat_at
+        );
+        finish_job(pool, job.id, JobStatus::Failed, Some("Job stopped heartbeating".to_string())).await?;
+        mutation_service::update_mutation_test_status(pool, job.mutation_test_id, MutationTestStatus::Failed)
+            .await?;
+    }
+    Ok(stale_jobs.len())
+}
+
+/// Reconciles stale jobs right away and then every `interval`, so jobs
+/// orphaned while the server is running get failed too, not just those left
+/// over from before a restart.
+pub async fn reconcile_stale_jobs_every(pool: PgPool, stale_after: Duration, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match reconcile_stale_jobs(&pool, stale_after).await {
+            Ok(0) => {}
+            Ok(count) => info!("Marked {} stale mutation job(s) as failed", count),
+            Err(e) => warn!("Failed to reconcile stale mutation jobs: {}", e),
+        }
+    }
+}
+
+/// Drives `work` to completion, recording a heartbeat with the current
+/// progress every `interval` until it finishes.
+pub async fn run_with_heartbeat<F: Future>(
+    pool: &PgPool,
+    job_id: Uuid,
+    interval: Duration,
+    progress: Arc<RunProgress>,
+    work: F,
+) -> F::Output {
+    tokio::pin!(work);
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            output = &mut work => return output,
+            _ = ticker.tick() => {
+                if let Err(e) = record_heartbeat(pool, job_id, &progress).await {
+                    warn!("Failed to record heartbeat for job {}: {}", job_id, e);
+                }
+            }
+        }
+    }
+}
+
+/// Runs a mutation test as a tracked job, heartbeating while it executes
+/// until it finishes or `handle.cancel` fires. Each finished mutant and the
+/// final outcome are published on `handle.events`.
+pub async fn run_mutation_job(
+    pool: &PgPool,
+    mutation_test_id: Uuid,
+    handle: &RunHandle,
+) -> AppResult<MutationTest> {
+    let job = create_job(pool, mutation_test_id).await?;
+    let (results_tx, results_rx) = mpsc::unbounded_channel();
+    let engine = MutationEngine::new(MutationTestConfig::default()).with_result_sender(results_tx);
+    let forwarder = tokio::spawn(publish_progress(results_rx, handle.events.clone()));
+
+    let result = run_with_heartbeat(
+        pool,
+        job.id,
+        HEARTBEAT_INTERVAL,
+        engine.progress(),
+        mutation_service::run_mutation_testing_with_engine(pool, mutation_test_id, &engine, &handle.cancel),
+    )
+    .await;
+
+    if let Err(e) = record_heartbeat(pool, job.id, &engine.progress()).await {
+        warn!("Failed to record heartbeat for job {}: {}", job.id, e);
+    }
+    drop(engine);
+    let tally = forwarder.await.unwrap_or_default();
+
+    let finished = match &result {
+        Ok(test) if test.status == MutationTestStatus::Cancelled => {
+            finish_job(pool, job.id, JobStatus::Cancelled, None).await
+        }
+        Ok(_) => finish_job(pool, job.id, JobStatus::Completed, None).await,
+        Err(e) => finish_job(pool, job.id, JobStatus::Failed, Some(e.to_string())).await,
+    };
+
+    let status = match &result {
+        Ok(test) => test.status.clone(),
+        Err(_) => MutationTestStatus::Failed,
+    };
+    let _ = handle.events.send(RunEvent::Done(RunFinishedEvent {
+        status,
+        total_mutations: tally.total_mutations,
+        killed_mutations: tally.killed_mutations,
+        survived_mutations: tally.survived_mutations,
+        mutation_score: tally.mutation_score,
+    }));
+
+    // Subscribers get `Done` even when the job row could not be updated.
+    finished?;
+    result
+}
+
+// Turns engine results into progress events until the engine goes away,
+// returning the tallies of everything it saw.
+async fn publish_progress(
+    mut results: mpsc::UnboundedReceiver<types::MutationResult>,
+    events: broadcast::Sender<RunEvent>,
+) -> MutationReport {
+    let mut tally = MutationReport::new();
+    while let Some(result) = results.recv().await {
+        let (line, column) = (result.candidate.line, result.candidate.column);
+        let mutation_type = format!("{:?}", result.candidate.mutation_type);
+        let test_result = TestResult::from(&result.test_result);
+        tally.add_result(result);
+        // No subscribers is fine; the run goes on regardless.
+        let _ = events.send(RunEvent::Mutation(MutationProgressEvent {
+            line,
+            column,
+            mutation_type,
+            test_result,
+            completed: tally.total_mutations,
+            killed: tally.killed_mutations,
+            survived: tally.survived_mutations,
+        }));
+    }
+    tally
+}
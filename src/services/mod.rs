@@ -1 +1,2 @@
+pub mod job_service;
 pub mod mutation_service;
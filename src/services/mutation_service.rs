@@ -1,18 +1,21 @@
 use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 use uuid::Uuid;
 
 use crate::{
     error::{AppError, AppResult},
     models::{
-        CreateMutationTestRequest, MutationResult, MutationTest, MutationTestStatus,
-        MutationTestSummary, MutationTestWithResults, TestResult,
+        CreateMutationTestRequest, ListCursor, MutationResult, MutationTest, MutationTestOverview,
+        MutationTestPage, MutationTestStatus, MutationScorePoint, MutationTestSummary, MutationTestWithResults, TestResult, TriageStatus,
     },
     mutation::logger::MutationLogger,
     mutation::{
         engine::MutationEngine,
-        types::{MutationTestConfig, TestOutcome},
+        error::MutationError,
+        types::{MutationReport, MutationTestConfig, TestOutcome},
     },
+    store::MutationStore,
 };
 
 #[allow(dead_code)]
@@ -60,22 +63,32 @@ pub async fn create_mutation_test(
 }
 
 #[allow(dead_code)]
-pub async fn run_mutation_testing(
-    pool: &PgPool,
+pub async fn run_mutation_testing<S: MutationStore + ?Sized>(
+    store: &S,
     mutation_test_id: Uuid,
 ) -> AppResult<MutationTest> {
-    let mut mutation_test =
-        update_mutation_test_status(pool, mutation_test_id, MutationTestStatus::Running).await?;
+    let engine = MutationEngine::new(MutationTestConfig::default());
+    run_mutation_testing_with_engine(store, mutation_test_id, &engine, &CancellationToken::new()).await
+}
 
-    let config = MutationTestConfig::default();
-    let engine = MutationEngine::new(config);
+/// Runs the test with `engine`. Firing `cancel` stops the run and leaves the
+/// test `Cancelled` rather than failing the call.
+pub async fn run_mutation_testing_with_engine<S: MutationStore + ?Sized>(
+    store: &S,
+    mutation_test_id: Uuid,
+    engine: &MutationEngine,
+    cancel: &CancellationToken,
+) -> AppResult<MutationTest> {
+    let mutation_test = store
+        .update_status(mutation_test_id, MutationTestStatus::Running)
+        .await?;
 
     match engine
-        .run_mutation_testing(&mutation_test.source_code)
+        .run_mutation_testing_until_cancelled(&mutation_test.source_code, cancel)
         .await
     {
         Ok(report) => {
-            for result in report.results {
+            for result in &report.results {
                 MutationLogger::step(&format!(
                     "[API] Mutation at line {}, col {}: {:?} '{}' -> '{}' | Test result: {:?}",
                     result.candidate.line,
@@ -85,53 +98,61 @@ pub async fn run_mutation_testing(
                     result.mutated_code.chars().take(30).collect::<String>(),
                     result.test_result
                 ));
-                let test_result = match result.test_result {
-                    TestOutcome::Killed { killing_tests } => {
-                        println!("Mutation killed by tests: {:?}", killing_tests);
-                        TestResult::Killed
-                    }
-                    TestOutcome::Survived => TestResult::Survived,
-                    TestOutcome::Timeout => TestResult::Timeout,
-                    TestOutcome::Error => TestResult::Error,
-                    TestOutcome::Skipped => TestResult::Skipped,
-                };
-
-                let mutation_type = format!("{:?}", result.candidate.mutation_type);
-
-                sqlx::query!(
-                    r#"
-                    INSERT INTO mutation_results 
-                    (mutation_test_id, mutation_type, original_code, mutated_code, 
-                     line_number, column_number, test_result, execution_time_ms, error_message)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7::test_result, $8, $9)
-                    "#,
-                    mutation_test_id,
-                    mutation_type,
-                    result.candidate.original_code,
-                    result.mutated_code,
-                    result.candidate.line as i32,
-                    result.candidate.column as i32,
-                    test_result as TestResult,
-                    result.execution_time_ms as i64,
-                    result.error_message
-                )
-                .execute(pool)
-                .await?;
+                if let TestOutcome::Killed { killing_tests } = &result.test_result {
+                    println!("Mutation killed by tests: {:?}", killing_tests);
+                }
             }
 
-            mutation_test =
-                update_mutation_test_status(pool, mutation_test_id, MutationTestStatus::Completed)
-                    .await?;
+            store
+                .insert_results(mutation_test_id, &report.results)
+                .await?;
+            store.save_report(mutation_test_id, &report).await?;
+
+            store
+                .update_status(mutation_test_id, MutationTestStatus::Completed)
+                .await
         }
-        Err(error) => {
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "Mutation testing failed: {}",
-                error
-            )));
+        Err(MutationError::Cancelled) => {
+            info!("Mutation test {} was cancelled", mutation_test_id);
+            store
+                .update_status(mutation_test_id, MutationTestStatus::Cancelled)
+                .await
         }
+        Err(error) => Err(error.into()),
     }
+}
 
-    Ok(mutation_test)
+pub async fn insert_mutation_results(
+    pool: &PgPool,
+    mutation_test_id: Uuid,
+    results: &[crate::mutation::types::MutationResult],
+) -> AppResult<()> {
+    for result in results {
+        let test_result = TestResult::from(&result.test_result);
+        let mutation_type = format!("{:?}", result.candidate.mutation_type);
+
+        sqlx::query!(
+            r#"
+            INSERT INTO mutation_results 
+            (mutation_test_id, mutation_type, original_code, mutated_code, 
+             line_number, column_number, test_result, execution_time_ms, error_message)
+            VALUES ($1, $2, $3, $4, $5, $6, $7::test_result, $8, $9)
+            "#,
+            mutation_test_id,
+            mutation_type,
+            result.candidate.original_code,
+            result.mutated_code,
+            result.candidate.line as i32,
+            result.candidate.column as i32,
+            test_result as TestResult,
+            result.execution_time_ms as i64,
+            result.error_message
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
 }
 
 pub async fn dry_run_mutation_testing(
@@ -149,8 +170,7 @@ pub async fn dry_run_mutation_testing(
 
     let candidates = engine
         .dry_run(&mutation_test.source_code)
-        .await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Dry run failed: {}", e)))?;
+        .await?;
 
     Ok(candidates)
 }
@@ -162,8 +182,44 @@ pub async fn list_mutation_tests(
     status_filter: Option<String>,
     language_filter: Option<String>,
 ) -> AppResult<Vec<MutationTest>> {
-    let offset = ((page - 1) * limit) as i64;
-    let limit = limit as i64;
+    if page == 0 {
+        return Err(AppError::Validation("Page must be at least 1".to_string()));
+    }
+    query_mutation_tests(pool, ListPosition::Page(page), limit, status_filter, language_filter).await
+}
+
+/// Lists the tests created before `after` (or the newest ones without a
+/// cursor). Unlike offset paging, rows inserted while a client pages through
+/// the list don't shift later pages.
+pub async fn list_mutation_tests_after(
+    pool: &PgPool,
+    after: Option<ListCursor>,
+    limit: u32,
+    status_filter: Option<String>,
+    language_filter: Option<String>,
+) -> AppResult<MutationTestPage> {
+    let position = after.map_or(ListPosition::Page(1), ListPosition::After);
+    let items = query_mutation_tests(pool, position, limit, status_filter, language_filter).await?;
+    Ok(MutationTestPage::new(items, limit))
+}
+
+enum ListPosition {
+    Page(u32),
+    After(ListCursor),
+}
+
+async fn query_mutation_tests(
+    pool: &PgPool,
+    position: ListPosition,
+    limit: u32,
+    status_filter: Option<String>,
+    language_filter: Option<String>,
+) -> AppResult<Vec<MutationTest>> {
+    if limit == 0 || limit > 100 {
+        return Err(AppError::Validation(
+            "Limit must be between 1 and 100".to_string(),
+        ));
+    }
 
     let mut query = sqlx::QueryBuilder::new(
         r#"
@@ -194,10 +250,20 @@ pub async fn list_mutation_tests(
         query.push_bind(language);
     }
 
-    query.push(" ORDER BY created_at DESC LIMIT ");
-    query.push_bind(limit);
-    query.push(" OFFSET ");
-    query.push_bind(offset);
+    if let ListPosition::After(cursor) = &position {
+        query.push(" AND (created_at, id) < (");
+        query.push_bind(cursor.created_at);
+        query.push(", ");
+        query.push_bind(cursor.id);
+        query.push(")");
+    }
+
+    query.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+    query.push_bind(limit as i64);
+    if let ListPosition::Page(page) = position {
+        query.push(" OFFSET ");
+        query.push_bind((page as i64 - 1) * limit as i64);
+    }
 
     let mutation_tests = query
         .build_query_as::<MutationTest>()
@@ -233,15 +299,21 @@ pub async fn get_mutation_test(pool: &PgPool, id: Uuid) -> AppResult<Option<Muta
     Ok(mutation_test)
 }
 
+/// The summary always covers every result; `triage_filter` only narrows the
+/// returned list.
 pub async fn get_mutation_test_with_results(
     pool: &PgPool,
     id: Uuid,
+    triage_filter: Option<TriageStatus>,
 ) -> AppResult<Option<MutationTestWithResults>> {
     let mutation_test = get_mutation_test(pool, id).await?;
 
     if let Some(test) = mutation_test {
-        let results = get_mutation_results(pool, id).await?;
+        let mut results = get_mutation_results(pool, id).await?;
         let summary = MutationTestSummary::calculate(&results);
+        if let Some(triage_status) = triage_filter {
+            results.retain(|r| r.triage_status == triage_status);
+        }
 
         Ok(Some(MutationTestWithResults {
             test,
@@ -253,6 +325,93 @@ pub async fn get_mutation_test_with_results(
     }
 }
 
+/// The test's metadata and summary, counted in the database so neither the
+/// source nor the results are loaded.
+pub async fn get_mutation_summary(pool: &PgPool, id: Uuid) -> AppResult<Option<MutationTestOverview>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            t.id,
+            t.name,
+            t.status as "status: MutationTestStatus",
+            t.created_at,
+            t.updated_at,
+            t.started_at,
+            t.completed_at,
+            COUNT(r.id) as "total_mutations!",
+            COUNT(r.id) FILTER (WHERE r.test_result = 'killed') as "killed_mutations!",
+            COUNT(r.id) FILTER (WHERE r.test_result = 'survived') as "survived_mutations!",
+            COUNT(r.id) FILTER (WHERE r.test_result = 'error') as "error_mutations!",
+            COUNT(r.id) FILTER (WHERE r.test_result = 'timeout') as "timeout_mutations!",
+            COUNT(r.id) FILTER (WHERE r.test_result = 'skipped') as "skipped_mutations!"
+        FROM mutation_tests t
+        LEFT JOIN mutation_results r ON r.mutation_test_id = t.id
+        WHERE t.id = $1
+        GROUP BY t.id
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| {
+        let mutation_score = if row.total_mutations > 0 {
+            (row.killed_mutations as f64 / row.total_mutations as f64) * 100.0
+        } else {
+            0.0
+        };
+        MutationTestOverview {
+            id: row.id,
+            name: row.name,
+            status: row.status,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            started_at: row.started_at,
+            completed_at: row.completed_at,
+            summary: MutationTestSummary {
+                total_mutations: row.total_mutations,
+                killed_mutations: row.killed_mutations,
+                survived_mutations: row.survived_mutations,
+                error_mutations: row.error_mutations,
+                timeout_mutations: row.timeout_mutations,
+                skipped_mutations: row.skipped_mutations,
+                mutation_score,
+            },
+        }
+    }))
+}
+
+pub async fn save_mutation_report(
+    pool: &PgPool,
+    mutation_test_id: Uuid,
+    report: &MutationReport,
+) -> AppResult<()> {
+    let report_json = serde_json::to_value(report)?;
+
+    sqlx::query!(
+        "UPDATE mutation_tests SET report_json = $2 WHERE id = $1",
+        mutation_test_id,
+        report_json
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// The aggregate report stored when the run completed, or `None` for an
+/// unknown test or one that has not finished.
+pub async fn get_stored_report(pool: &PgPool, id: Uuid) -> AppResult<Option<MutationReport>> {
+    let row = sqlx::query!("SELECT report_json FROM mutation_tests WHERE id = $1", id)
+        .fetch_optional(pool)
+        .await?;
+
+    match row.and_then(|row| row.report_json) {
+        Some(json) => Ok(Some(serde_json::from_value(json)?)),
+        None => Ok(None),
+    }
+}
+
 pub async fn get_mutation_results(
     pool: &PgPool,
     mutation_test_id: Uuid,
@@ -271,6 +430,7 @@ pub async fn get_mutation_results(
             test_result as "test_result: crate::models::TestResult",
             execution_time_ms,
             error_message,
+            triage_status as "triage_status: TriageStatus",
             created_at,
             updated_at
         FROM mutation_results
@@ -285,6 +445,89 @@ pub async fn get_mutation_results(
     Ok(results)
 }
 
+/// Sets the triage status of one result, returning `None` when it doesn't
+/// belong to `mutation_test_id`.
+pub async fn update_triage_status(
+    pool: &PgPool,
+    mutation_test_id: Uuid,
+    result_id: Uuid,
+    triage_status: TriageStatus,
+) -> AppResult<Option<MutationResult>> {
+    let result = sqlx::query_as!(
+        MutationResult,
+        r#"
+        UPDATE mutation_results
+        SET triage_status = $3
+        WHERE id = $2 AND mutation_test_id = $1
+        RETURNING
+            id,
+            mutation_test_id,
+            mutation_type,
+            original_code,
+            mutated_code,
+            line_number,
+            column_number,
+            test_result as "test_result: crate::models::TestResult",
+            execution_time_ms,
+            error_message,
+            triage_status as "triage_status: TriageStatus",
+            created_at,
+            updated_at
+        "#,
+        mutation_test_id,
+        result_id,
+        triage_status as TriageStatus
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(result)
+}
+
+/// Scores of the last `limit` completed runs named `name`, oldest first.
+pub async fn get_mutation_score_trend(
+    pool: &PgPool,
+    name: &str,
+    limit: u32,
+) -> AppResult<Vec<MutationScorePoint>> {
+    let points = sqlx::query_as!(
+        MutationScorePoint,
+        r#"
+        SELECT
+            mutation_test_id as "mutation_test_id!",
+            completed_at as "completed_at!",
+            total_mutations as "total_mutations!",
+            killed_mutations as "killed_mutations!",
+            CASE
+                WHEN total_mutations > 0 THEN killed_mutations * 100.0 / total_mutations
+                ELSE 0
+            END::FLOAT8 as "mutation_score!"
+        FROM (
+            SELECT
+                t.id as mutation_test_id,
+                t.completed_at,
+                COUNT(r.id) as total_mutations,
+                COUNT(r.id) FILTER (WHERE r.test_result = 'killed') as killed_mutations
+            FROM mutation_tests t
+            LEFT JOIN mutation_results r ON r.mutation_test_id = t.id
+            WHERE t.name = $1
+              AND t.status = 'completed'
+              AND t.completed_at IS NOT NULL
+            GROUP BY t.id
+            ORDER BY t.completed_at DESC
+            LIMIT $2
+        ) recent
+        ORDER BY completed_at, mutation_test_id
+        "#,
+        name,
+        limit as i64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(points)
+}
+
 pub async fn update_mutation_test_status(
     pool: &PgPool,
     id: Uuid,
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::AppResult,
+    models::{CreateMutationTestRequest, MutationResult, MutationTest, MutationTestStatus, TestResult},
+    mutation::types::{self, MutationReport, TestOutcome},
+    services::mutation_service,
+};
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// Persistence for mutation test runs, implemented for the Postgres pool used
+/// by the server and, behind the `sqlite` feature, a local SQLite file.
+#[async_trait]
+#[allow(dead_code)]
+pub trait MutationStore: Send + Sync {
+    async fn create_test(&self, request: CreateMutationTestRequest) -> AppResult<MutationTest>;
+
+    async fn get_test(&self, id: Uuid) -> AppResult<Option<MutationTest>>;
+
+    async fn update_status(&self, id: Uuid, status: MutationTestStatus) -> AppResult<MutationTest>;
+
+    async fn insert_results(&self, mutation_test_id: Uuid, results: &[types::MutationResult]) -> AppResult<()>;
+
+    async fn save_report(&self, mutation_test_id: Uuid, report: &MutationReport) -> AppResult<()>;
+
+    async fn get_results(&self, mutation_test_id: Uuid) -> AppResult<Vec<MutationResult>>;
+}
+
+#[async_trait]
+impl MutationStore for PgPool {
+    async fn create_test(&self, request: CreateMutationTestRequest) -> AppResult<MutationTest> {
+        mutation_service::create_mutation_test(self, request).await
+    }
+
+    async fn get_test(&self, id: Uuid) -> AppResult<Option<MutationTest>> {
+        mutation_service::get_mutation_test(self, id).await
+    }
+
+    async fn update_status(&self, id: Uuid, status: MutationTestStatus) -> AppResult<MutationTest> {
+        mutation_service::update_mutation_test_status(self, id, status).await
+    }
+
+    async fn insert_results(&self, mutation_test_id: Uuid, results: &[types::MutationResult]) -> AppResult<()> {
+        mutation_service::insert_mutation_results(self, mutation_test_id, results).await
+    }
+
+    async fn save_report(&self, mutation_test_id: Uuid, report: &MutationReport) -> AppResult<()> {
+        mutation_service::save_mutation_report(self, mutation_test_id, report).await
+    }
+
+    async fn get_results(&self, mutation_test_id: Uuid) -> AppResult<Vec<MutationResult>> {
+        mutation_service::get_mutation_results(self, mutation_test_id).await
+    }
+}
+
+impl From<&TestOutcome> for TestResult {
+    fn from(outcome: &TestOutcome) -> Self {
+        match outcome {
+            TestOutcome::Killed { .. } => TestResult::Killed,
+            TestOutcome::Survived => TestResult::Survived,
+            TestOutcome::Timeout => TestResult::Timeout,
+            TestOutcome::Error | TestOutcome::CompileError { .. } => TestResult::Error,
+            TestOutcome::Skipped | TestOutcome::Uncompilable => TestResult::Skipped,
+        }
+    }
+}
+
+/// Records a finished CLI run as a completed mutation test with its results.
+#[allow(dead_code)]
+pub async fn record_run<S: MutationStore + ?Sized>(
+    store: &S,
+    request: CreateMutationTestRequest,
+    report: &MutationReport,
+) -> AppResult<MutationTest> {
+    let mutation_test = store.create_test(request).await?;
+    store.update_status(mutation_test.id, MutationTestStatus::Running).await?;
+    store.insert_results(mutation_test.id, &report.results).await?;
+    store.save_report(mutation_test.id, report).await?;
+    store.update_status(mutation_test.id, MutationTestStatus::Completed).await
+}
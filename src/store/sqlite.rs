@@ -0,0 +1,438 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{
+    Executor, Row,
+    sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow},
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use super::MutationStore;
+use crate::{
+    error::{AppError, AppResult},
+    models::{CreateMutationTestRequest, MutationResult, MutationTest, MutationTestStatus, TestResult, TriageStatus},
+    mutation::types,
+};
+
+// Subset of migrations/001_initial_schema.sql. SQLite has no enum or UUID
+// types, so those columns are stored as TEXT using the Postgres labels.
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS mutation_tests (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    source_code TEXT NOT NULL,
+    language TEXT NOT NULL DEFAULT 'rust',
+    status TEXT NOT NULL DEFAULT 'pending',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    report_json TEXT
+);
+
+CREATE TABLE IF NOT EXISTS mutation_results (
+    id TEXT PRIMARY KEY,
+    mutation_test_id TEXT NOT NULL REFERENCES mutation_tests(id) ON DELETE CASCADE,
+    mutation_type TEXT NOT NULL,
+    original_code TEXT NOT NULL,
+    mutated_code TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    column_number INTEGER,
+    test_result TEXT NOT NULL DEFAULT 'pending',
+    execution_time_ms INTEGER,
+    error_message TEXT,
+    triage_status TEXT NOT NULL DEFAULT 'untriaged',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mutation_results_test_id ON mutation_results(mutation_test_id);
+"#;
+
+const TEST_COLUMNS: &str = "id, name, description, source_code, language, status, created_at, updated_at, started_at, completed_at";
+
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+#[allow(dead_code)]
+impl SqliteStore {
+    pub async fn connect(path: &str) -> AppResult<Self> {
+        let options = SqliteConnectOptions::from_str(path)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await?;
+        pool.execute(SCHEMA).await?;
+        migrate(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+// Brings databases created by older versions up to `SCHEMA`, which
+// `CREATE TABLE IF NOT EXISTS` leaves untouched. SQLite has no
+// `ADD COLUMN IF NOT EXISTS`, so each added column is checked for first.
+async fn migrate(pool: &SqlitePool) -> AppResult<()> {
+    let has_report_json = sqlx::query("SELECT 1 FROM pragma_table_info('mutation_tests') WHERE name = 'report_json'")
+        .fetch_optional(pool)
+        .await?
+        .is_some();
+    if !has_report_json {
+        pool.execute("ALTER TABLE mutation_tests ADD COLUMN report_json TEXT").await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl MutationStore for SqliteStore {
+    async fn create_test(&self, request: CreateMutationTestRequest) -> AppResult<MutationTest> {
+        if request.name.trim().is_empty() {
+            return Err(AppError::BadRequest("Mutation test name cannot be empty".to_string()));
+        }
+
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO mutation_tests (id, name, description, source_code, language, status, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id.to_string())
+        .bind(&request.name)
+        .bind(&request.description)
+        .bind(&request.source_code)
+        .bind(request.language.as_deref().unwrap_or("rust"))
+        .bind(status_label(&MutationTestStatus::Pending))
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        self.get_test(id)
+            .await?
+            .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Mutation test {} was not stored", id)))
+    }
+
+    async fn get_test(&self, id: Uuid) -> AppResult<Option<MutationTest>> {
+        let row = sqlx::query(&format!("SELECT {} FROM mutation_tests WHERE id = ?", TEST_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(test_from_row).transpose()
+    }
+
+    async fn update_status(&self, id: Uuid, status: MutationTestStatus) -> AppResult<MutationTest> {
+        let now = Utc::now();
+        let (started_at, completed_at) = match status {
+            MutationTestStatus::Running => (Some(now), None),
+            MutationTestStatus::Completed | MutationTestStatus::Failed | MutationTestStatus::Cancelled => {
+                (None, Some(now))
+            }
+            _ => (None, None),
+        };
+
+        sqlx::query(
+            "UPDATE mutation_tests
+             SET status = ?, started_at = COALESCE(?, started_at), completed_at = COALESCE(?, completed_at), updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(status_label(&status))
+        .bind(started_at)
+        .bind(completed_at)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        self.get_test(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Mutation test {} not found", id)))
+    }
+
+    async fn insert_results(&self, mutation_test_id: Uuid, results: &[types::MutationResult]) -> AppResult<()> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        for result in results {
+            sqlx::query(
+                "INSERT INTO mutation_results
+                 (id, mutation_test_id, mutation_type, original_code, mutated_code,
+                  line_number, column_number, test_result, execution_time_ms, error_message, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(mutation_test_id.to_string())
+            .bind(format!("{:?}", result.candidate.mutation_type))
+            .bind(&result.candidate.original_code)
+            .bind(&result.mutated_code)
+            .bind(result.candidate.line as i32)
+            .bind(result.candidate.column as i32)
+            .bind(result_label(&TestResult::from(&result.test_result)))
+            .bind(result.execution_time_ms as i64)
+            .bind(&result.error_message)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn save_report(&self, mutation_test_id: Uuid, report: &types::MutationReport) -> AppResult<()> {
+        sqlx::query("UPDATE mutation_tests SET report_json = ? WHERE id = ?")
+            .bind(serde_json::to_string(report)?)
+            .bind(mutation_test_id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_results(&self, mutation_test_id: Uuid) -> AppResult<Vec<MutationResult>> {
+        let rows = sqlx::query(
+            "SELECT id, mutation_test_id, mutation_type, original_code, mutated_code, line_number, column_number,
+                    test_result, execution_time_ms, error_message, triage_status, created_at, updated_at
+             FROM mutation_results
+             WHERE mutation_test_id = ?
+             ORDER BY line_number, column_number",
+        )
+        .bind(mutation_test_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(result_from_row).collect()
+    }
+}
+
+fn status_label(status: &MutationTestStatus) -> &'static str {
+    match status {
+        MutationTestStatus::Pending => "pending",
+        MutationTestStatus::Running => "running",
+        MutationTestStatus::Completed => "completed",
+        MutationTestStatus::Failed => "failed",
+        MutationTestStatus::Cancelled => "cancelled",
+    }
+}
+
+fn parse_status(label: &str) -> AppResult<MutationTestStatus> {
+    match label {
+        "pending" => Ok(MutationTestStatus::Pending),
+        "running" => Ok(MutationTestStatus::Running),
+        "completed" => Ok(MutationTestStatus::Completed),
+        "failed" => Ok(MutationTestStatus::Failed),
+        "cancelled" => Ok(MutationTestStatus::Cancelled),
+        other => Err(AppError::Internal(anyhow::anyhow!("Unknown mutation test status '{}'", other))),
+    }
+}
+
+fn result_label(result: &TestResult) -> &'static str {
+    match result {
+        TestResult::Pending => "pending",
+        TestResult::Killed => "killed",
+        TestResult::Survived => "survived",
+        TestResult::Timeout => "timeout",
+        TestResult::Error => "error",
+        TestResult::Skipped => "skipped",
+    }
+}
+
+fn parse_result(label: &str) -> AppResult<TestResult> {
+    match label {
+        "pending" => Ok(TestResult::Pending),
+        "killed" => Ok(TestResult::Killed),
+        "survived" => Ok(TestResult::Survived),
+        "timeout" => Ok(TestResult::Timeout),
+        "error" => Ok(TestResult::Error),
+        "skipped" => Ok(TestResult::Skipped),
+        other => Err(AppError::Internal(anyhow::anyhow!("Unknown test result '{}'", other))),
+    }
+}
+
+fn parse_triage(label: &str) -> AppResult<TriageStatus> {
+    match label {
+        "untriaged" => Ok(TriageStatus::Untriaged),
+        "accepted" => Ok(TriageStatus::Accepted),
+        "needs_test" => Ok(TriageStatus::NeedsTest),
+        "false_positive" => Ok(TriageStatus::FalsePositive),
+        other => Err(AppError::Internal(anyhow::anyhow!("Unknown triage status '{}'", other))),
+    }
+}
+
+fn parse_uuid(row: &SqliteRow, column: &str) -> AppResult<Uuid> {
+    let value: String = row.try_get(column)?;
+    Uuid::parse_str(&value).map_err(|e| AppError::Internal(anyhow::anyhow!("Invalid {} '{}': {}", column, value, e)))
+}
+
+fn test_from_row(row: &SqliteRow) -> AppResult<MutationTest> {
+    Ok(MutationTest {
+        id: parse_uuid(row, "id")?,
+        name: row.try_get("name")?,
+        description: row.try_get("description")?,
+        source_code: row.try_get("source_code")?,
+        language: row.try_get("language")?,
+        status: parse_status(row.try_get("status")?)?,
+        created_at: row.try_get::<DateTime<Utc>, _>("created_at")?,
+        updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")?,
+        started_at: row.try_get("started_at")?,
+        completed_at: row.try_get("completed_at")?,
+    })
+}
+
+fn result_from_row(row: &SqliteRow) -> AppResult<MutationResult> {
+    Ok(MutationResult {
+        id: parse_uuid(row, "id")?,
+        mutation_test_id: parse_uuid(row, "mutation_test_id")?,
+        mutation_type: row.try_get("mutation_type")?,
+        original_code: row.try_get("original_code")?,
+        mutated_code: row.try_get("mutated_code")?,
+        line_number: row.try_get("line_number")?,
+        column_number: row.try_get("column_number")?,
+        test_result: parse_result(row.try_get("test_result")?)?,
+        execution_time_ms: row.try_get("execution_time_ms")?,
+        error_message: row.try_get("error_message")?,
+        triage_status: parse_triage(row.try_get("triage_status")?)?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutation::types::{MutationCandidate, MutationReport, MutationTestConfig, MutationType, TestOutcome};
+    use crate::mutation::engine::MutationEngine;
+    use crate::mutation::error::MutationError;
+    use crate::mutation::runner::{TestOutcome as RunnerOutcome, TestRunner};
+    use crate::store::record_run;
+    use tempfile::tempdir;
+
+    fn result(line: usize, outcome: TestOutcome) -> types::MutationResult {
+        types::MutationResult {
+            candidate: MutationCandidate {
+                line,
+                column: 5,
+                original_code: "+".to_string(),
+                mutation_type: MutationType::ArithmeticOperator,
+                suggested_mutations: vec!["-".to_string()],
+                module_path: String::new(),
+                byte_start: None,
+                byte_end: None,
+            },
+            mutated_code: "a - b".to_string(),
+            test_result: outcome,
+            execution_time_ms: 12,
+            error_message: None,
+            killing_tests: None,
+            suggested_improvement: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_run() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("results.db").to_string_lossy().to_string();
+        let store = SqliteStore::connect(&path).await.unwrap();
+
+        let mut report = MutationReport::new();
+        report.results = vec![
+            result(7, TestOutcome::Survived),
+            result(3, TestOutcome::Killed { killing_tests: vec!["tests::add".to_string()] }),
+        ];
+        let request = CreateMutationTestRequest {
+            name: "src/lib.rs".to_string(),
+            description: None,
+            source_code: "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+            language: None,
+        };
+
+        let recorded = record_run(&store, request, &report).await.unwrap();
+        assert_eq!(recorded.status, MutationTestStatus::Completed);
+        assert!(recorded.started_at.is_some());
+        assert!(recorded.completed_at.is_some());
+
+        let reopened = SqliteStore::connect(&path).await.unwrap();
+        let stored = reopened.get_test(recorded.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "src/lib.rs");
+        assert_eq!(stored.language, "rust");
+
+        let results = reopened.get_results(recorded.id).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].line_number, 3);
+        assert_eq!(results[0].test_result, TestResult::Killed);
+        assert_eq!(results[1].test_result, TestResult::Survived);
+        assert_eq!(results[1].mutation_type, "ArithmeticOperator");
+    }
+
+    #[tokio::test]
+    async fn test_database_without_report_column_is_migrated() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("old.db").to_string_lossy().to_string();
+        let old_schema = SCHEMA.replace(",\n    report_json TEXT", "");
+        assert_ne!(old_schema, SCHEMA);
+        let options = SqliteConnectOptions::from_str(&path).unwrap().create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        pool.execute(old_schema.as_str()).await.unwrap();
+        pool.close().await;
+
+        let store = SqliteStore::connect(&path).await.unwrap();
+        let request = CreateMutationTestRequest {
+            name: "src/lib.rs".to_string(),
+            description: None,
+            source_code: "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+            language: None,
+        };
+        let mut report = MutationReport::new();
+        report.results = vec![result(1, TestOutcome::Survived)];
+        let recorded = record_run(&store, request, &report).await.unwrap();
+        assert_eq!(recorded.status, MutationTestStatus::Completed);
+        assert!(SqliteStore::connect(&path).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stubbed_run_stores_one_row_per_result() {
+        struct StubRunner;
+
+        #[async_trait]
+        impl TestRunner for StubRunner {
+            async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+                Ok(())
+            }
+
+            async fn run_tests_for_mutation(&self, _mutated_code: &str) -> RunnerOutcome {
+                RunnerOutcome::Survived
+            }
+        }
+
+        let engine = MutationEngine::new(MutationTestConfig {
+            parallel_jobs: Some(1),
+            ..Default::default()
+        })
+        .with_test_runner(StubRunner);
+        let source = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n#[test]\nfn t() {}\n";
+        let report = engine.run_mutation_testing(source).await.unwrap();
+        assert!(!report.results.is_empty());
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("run.sqlite");
+        let store = SqliteStore::connect(&path.to_string_lossy()).await.unwrap();
+        for name in ["src/lib.rs", "src/math.rs"] {
+            let request = CreateMutationTestRequest {
+                name: name.to_string(),
+                description: None,
+                source_code: source.to_string(),
+                language: None,
+            };
+            record_run(&store, request, &report).await.unwrap();
+        }
+
+        let pool = SqlitePool::connect(&format!("sqlite://{}", path.display())).await.unwrap();
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM mutation_results r JOIN mutation_tests t ON t.id = r.mutation_test_id
+             WHERE t.name = 'src/lib.rs' AND r.test_result = 'survived'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(count as usize, report.results.len());
+    }
+}
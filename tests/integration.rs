@@ -1,10 +1,23 @@
 use flux_backend::{
+    app::RunHandle,
     config::AppConfig,
+    error::AppError,
     database::setup_database,
-    models::{CreateMutationTestRequest, MutationTestStatus},
-    services::mutation_service,
+    models::{CreateMutationTestRequest, JobStatus, ListCursor, MutationTestStatus, RunEvent, TriageStatus},
+    mutation::engine::{MutationEngine, RunProgress},
+    mutation::run_file,
+    mutation::error::MutationError,
+    mutation::runner::{TestOutcome as RunnerOutcome, TestRunner},
+    mutation::types::{
+        MutationCandidate, MutationReport, MutationResult, MutationTestConfig, MutationType, TestOutcome,
+    },
+    services::{job_service, mutation_service},
 };
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
 
 async fn setup_test_db() -> PgPool {
     let config = AppConfig::load().expect("Failed to load config");
@@ -161,11 +174,60 @@ async fn test_mutation_test_lifecycle() {
         MutationTestStatus::Completed | MutationTestStatus::Failed
     ));
 
-    let results = mutation_service::get_mutation_test_with_results(&pool, mutation_test.id).await;
+    let results = mutation_service::get_mutation_test_with_results(&pool, mutation_test.id, None).await;
     assert!(results.is_ok());
 
     let test_with_results = results.unwrap().unwrap();
     assert!(!test_with_results.results.is_empty());
+
+    let report = mutation_service::get_stored_report(&pool, mutation_test.id)
+        .await
+        .unwrap()
+        .expect("completed run should store its report");
+    assert_eq!(report.total_mutations, test_with_results.results.len());
+}
+
+#[tokio::test]
+async fn test_stored_report_round_trips() {
+    let pool = setup_test_db().await;
+    let request = CreateMutationTestRequest {
+        name: "Stored Report".to_string(),
+        description: None,
+        source_code: "pub fn one() -> i32 { 1 }".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request).await.unwrap();
+    assert!(mutation_service::get_stored_report(&pool, mutation_test.id).await.unwrap().is_none());
+
+    let candidate = MutationCandidate {
+        line: 1,
+        column: 23,
+        original_code: "1".to_string(),
+        mutation_type: MutationType::NumericLiteral,
+        suggested_mutations: vec!["0".to_string()],
+        module_path: String::new(),
+        byte_start: Some(22),
+        byte_end: Some(23),
+    };
+    let mut report = MutationReport::new();
+    report.add_result(MutationResult {
+        candidate,
+        mutated_code: "pub fn one() -> i32 { 0 }".to_string(),
+        test_result: TestOutcome::Survived,
+        execution_time_ms: 12,
+        error_message: None,
+        killing_tests: None,
+        suggested_improvement: Some("Assert the exact value returned".to_string()),
+    });
+
+    mutation_service::save_mutation_report(&pool, mutation_test.id, &report).await.unwrap();
+
+    let stored = mutation_service::get_stored_report(&pool, mutation_test.id).await.unwrap().unwrap();
+    assert_eq!(serde_json::to_value(&stored).unwrap(), serde_json::to_value(&report).unwrap());
+    assert_eq!(
+        stored.results[0].suggested_improvement.as_deref(),
+        Some("Assert the exact value returned")
+    );
 }
 
 #[tokio::test]
@@ -217,3 +279,644 @@ async fn test_list_mutation_tests_pagination() {
     assert_eq!(page1.len(), 10);
     assert!(page2.len() >= 5);
 }
+
+#[tokio::test]
+async fn test_job_heartbeat_advances_during_run() {
+    let pool = setup_test_db().await;
+
+    let request = CreateMutationTestRequest {
+        name: "Heartbeat Test".to_string(),
+        description: None,
+        source_code: "fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request)
+        .await
+        .unwrap();
+    let job = job_service::create_job(&pool, mutation_test.id).await.unwrap();
+
+    let progress = Arc::new(RunProgress::default());
+    progress.total.store(4, Ordering::Relaxed);
+    let stub_progress = progress.clone();
+    let stubbed_run = async move {
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_millis(60)).await;
+            stub_progress.completed.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+
+    job_service::run_with_heartbeat(&pool, job.id, Duration::from_millis(50), progress, stubbed_run).await;
+
+    let updated = job_service::get_latest_job(&pool, mutation_test.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.id, job.id);
+    assert!(updated.heartbeat_at > job.heartbeat_at);
+    assert_eq!(updated.total_mutations, Some(4));
+    assert!(updated.completed_mutations >= 3);
+
+    let stale = job_service::find_stale_jobs(&pool, Duration::from_secs(3600)).await.unwrap();
+    assert!(stale.iter().all(|j| j.id != job.id));
+}
+
+#[tokio::test]
+async fn test_jobs_going_stale_while_running_are_reconciled() {
+    let pool = setup_test_db().await;
+    let reconciler = tokio::spawn(job_service::reconcile_stale_jobs_every(
+        pool.clone(),
+        Duration::from_secs(1800),
+        Duration::from_millis(50),
+    ));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let request = CreateMutationTestRequest {
+        name: "Stale Job Test".to_string(),
+        description: None,
+        source_code: "fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request).await.unwrap();
+    let job = job_service::create_job(&pool, mutation_test.id).await.unwrap();
+    sqlx::query("UPDATE mutation_jobs SET heartbeat_at = NOW() - INTERVAL '1 hour' WHERE id = $1")
+        .bind(job.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    reconciler.abort();
+
+    let updated = job_service::get_latest_job(&pool, mutation_test.id).await.unwrap().unwrap();
+    assert_eq!(updated.status, JobStatus::Failed);
+    let test = mutation_service::get_mutation_test(&pool, mutation_test.id).await.unwrap().unwrap();
+    assert_eq!(test.status, MutationTestStatus::Failed);
+}
+
+#[tokio::test]
+async fn test_mutation_score_trend_is_chronological() {
+    let pool = setup_test_db().await;
+    let name = format!("Trend {}", uuid::Uuid::new_v4());
+
+    for killed in 0..4 {
+        let request = CreateMutationTestRequest {
+            name: name.clone(),
+            description: None,
+            source_code: "fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+            language: Some("rust".to_string()),
+        };
+        let run = mutation_service::create_mutation_test(&pool, request)
+            .await
+            .unwrap();
+        for i in 0..4 {
+            let outcome = if i < killed { "killed" } else { "survived" };
+            sqlx::query(
+                "INSERT INTO mutation_results (mutation_test_id, mutation_type, original_code, mutated_code, line_number, test_result) \
+                 VALUES ($1, 'ArithmeticOperator', '+', '-', 1, $2::test_result)",
+            )
+            .bind(run.id)
+            .bind(outcome)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        mutation_service::update_mutation_test_status(&pool, run.id, MutationTestStatus::Completed)
+            .await
+            .unwrap();
+    }
+
+    let trend = mutation_service::get_mutation_score_trend(&pool, &name, 3)
+        .await
+        .unwrap();
+    let scores: Vec<f64> = trend.iter().map(|p| p.mutation_score).collect();
+    assert_eq!(scores, vec![25.0, 50.0, 75.0]);
+    assert!(trend.windows(2).all(|w| w[0].completed_at <= w[1].completed_at));
+    assert!(trend.iter().all(|p| p.total_mutations == 4));
+}
+
+#[tokio::test]
+async fn test_triage_status_is_persisted_and_filterable() {
+    let pool = setup_test_db().await;
+
+    let request = CreateMutationTestRequest {
+        name: "Triage Test".to_string(),
+        description: None,
+        source_code: "fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request)
+        .await
+        .unwrap();
+    for mutated in ["-", "*"] {
+        sqlx::query(
+            "INSERT INTO mutation_results (mutation_test_id, mutation_type, original_code, mutated_code, line_number, test_result) \
+             VALUES ($1, 'ArithmeticOperator', '+', $2, 1, 'survived')",
+        )
+        .bind(mutation_test.id)
+        .bind(mutated)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let results = mutation_service::get_mutation_results(&pool, mutation_test.id)
+        .await
+        .unwrap();
+    assert!(results.iter().all(|r| r.triage_status == TriageStatus::Untriaged));
+    let target = results.iter().find(|r| r.mutated_code == "*").unwrap();
+
+    let updated = mutation_service::update_triage_status(&pool, mutation_test.id, target.id, TriageStatus::NeedsTest)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.triage_status, TriageStatus::NeedsTest);
+
+    let missing = mutation_service::update_triage_status(&pool, uuid::Uuid::new_v4(), target.id, TriageStatus::Accepted)
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+
+    let filtered = mutation_service::get_mutation_test_with_results(&pool, mutation_test.id, Some(TriageStatus::NeedsTest))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(filtered.results.len(), 1);
+    assert_eq!(filtered.results[0].id, target.id);
+    assert_eq!(filtered.summary.total_mutations, 2);
+}
+
+#[tokio::test]
+async fn test_cursor_paging_visits_every_row_once() {
+    let pool = setup_test_db().await;
+    let language = format!("cursor-{}", uuid::Uuid::new_v4());
+    let mut created = Vec::new();
+    for i in 0..15 {
+        let request = CreateMutationTestRequest {
+            name: format!("Cursor {}", i),
+            description: None,
+            source_code: format!("fn c{}() -> i32 {{ {} }}", i, i),
+            language: Some(language.clone()),
+        };
+        created.push(mutation_service::create_mutation_test(&pool, request).await.unwrap().id);
+    }
+
+    let mut seen = Vec::new();
+    let mut after = None;
+    loop {
+        let page = mutation_service::list_mutation_tests_after(&pool, after, 4, None, Some(language.clone()))
+            .await
+            .unwrap();
+        seen.extend(page.items.iter().map(|test| test.id));
+        match page.next_cursor {
+            Some(cursor) => after = Some(ListCursor::decode(&cursor).unwrap()),
+            None => break,
+        }
+    }
+
+    assert_eq!(seen.len(), created.len(), "a row was repeated or skipped");
+    seen.sort();
+    seen.dedup();
+    created.sort();
+    assert_eq!(seen, created);
+}
+
+#[tokio::test]
+async fn test_list_endpoint_keeps_the_array_for_page_requests() {
+    use axum::extract::{Query, State};
+    use flux_backend::app::AppState;
+    use flux_backend::handlers::mutations::{ListMutationsQuery, list_mutations};
+
+    let pool = setup_test_db().await;
+    let language = format!("shape-{}", uuid::Uuid::new_v4());
+    for i in 0..3 {
+        let request = CreateMutationTestRequest {
+            name: format!("Shape {}", i),
+            description: None,
+            source_code: format!("fn s{}() -> i32 {{ {} }}", i, i),
+            language: Some(language.clone()),
+        };
+        mutation_service::create_mutation_test(&pool, request).await.unwrap();
+    }
+    let state = Arc::new(AppState { db: pool, config: AppConfig::default(), running: Default::default() });
+    let query = |page: Option<u32>, after: Option<&str>| ListMutationsQuery {
+        page,
+        limit: Some(2),
+        status: None,
+        language: Some(language.clone()),
+        after: after.map(str::to_string),
+    };
+
+    let body = list_mutations(State(state.clone()), Query(query(Some(1), None))).await.unwrap().0;
+    let body = serde_json::to_value(body).unwrap();
+    assert_eq!(body.as_array().map(Vec::len), Some(2), "{}", body);
+
+    let body = list_mutations(State(state.clone()), Query(query(None, Some("")))).await.unwrap().0;
+    let body = serde_json::to_value(body).unwrap();
+    assert_eq!(body["items"].as_array().map(Vec::len), Some(2), "{}", body);
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+
+    let body = list_mutations(State(state), Query(query(None, Some(&cursor)))).await.unwrap().0;
+    let body = serde_json::to_value(body).unwrap();
+    assert_eq!(body["items"].as_array().map(Vec::len), Some(1), "{}", body);
+    assert!(body["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn test_start_is_rejected_while_a_run_is_registered() {
+    use axum::extract::{Path, State};
+    use flux_backend::app::AppState;
+    use flux_backend::handlers::mutations::start_mutation_testing;
+
+    let pool = setup_test_db().await;
+    let request = CreateMutationTestRequest {
+        name: "Double Start".to_string(),
+        description: None,
+        source_code: "pub fn one() -> i32 { 1 }".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request).await.unwrap();
+    let state = Arc::new(AppState { db: pool, config: AppConfig::default(), running: Default::default() });
+
+    let missing = uuid::Uuid::new_v4();
+    let result = start_mutation_testing(State(state.clone()), Path(missing)).await;
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+    assert!(!state.running.contains_key(&missing));
+
+    let in_flight = RunHandle::default();
+    state.running.insert(mutation_test.id, in_flight.clone());
+    let result = start_mutation_testing(State(state.clone()), Path(mutation_test.id)).await;
+    assert!(matches!(result, Err(AppError::Conflict(_))));
+    let registered = state.running.get(&mutation_test.id).unwrap();
+    assert!(registered.events.same_channel(&in_flight.events));
+}
+
+#[tokio::test]
+async fn test_summary_counts_match_the_full_results() {
+    let pool = setup_test_db().await;
+
+    let request = CreateMutationTestRequest {
+        name: "Summary Test".to_string(),
+        description: None,
+        source_code: "fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request)
+        .await
+        .unwrap();
+    for (mutated, outcome) in [("-", "killed"), ("*", "killed"), ("/", "survived"), ("%", "timeout"), ("&", "error")] {
+        sqlx::query(
+            "INSERT INTO mutation_results (mutation_test_id, mutation_type, original_code, mutated_code, line_number, test_result) \
+             VALUES ($1, 'ArithmeticOperator', '+', $2, 1, $3::test_result)",
+        )
+        .bind(mutation_test.id)
+        .bind(mutated)
+        .bind(outcome)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let overview = mutation_service::get_mutation_summary(&pool, mutation_test.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let full = mutation_service::get_mutation_test_with_results(&pool, mutation_test.id, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(overview.name, "Summary Test");
+    assert_eq!(overview.status, MutationTestStatus::Pending);
+    assert_eq!(
+        serde_json::to_value(&overview.summary).unwrap(),
+        serde_json::to_value(&full.summary).unwrap()
+    );
+    assert_eq!(overview.summary.total_mutations, 5);
+
+    let missing = mutation_service::get_mutation_summary(&pool, uuid::Uuid::new_v4()).await.unwrap();
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn test_list_rejects_zero_page_and_limit() {
+    let pool = setup_test_db().await;
+
+    for (page, limit) in [(0, 20), (1, 0), (1, 101)] {
+        let result = mutation_service::list_mutation_tests(&pool, page, limit, None, None).await;
+        assert!(
+            matches!(result, Err(AppError::Validation(_))),
+            "page={} limit={} should be rejected",
+            page,
+            limit
+        );
+    }
+    assert!(mutation_service::list_mutation_tests(&pool, 1, 100, None, None).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_job_publishes_progress_then_done() {
+    let pool = setup_test_db().await;
+    let request = CreateMutationTestRequest {
+        name: "Streamed Run".to_string(),
+        description: None,
+        source_code: "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\n#[test]\nfn adds() {\n    assert_eq!(add(2, 3), 5);\n}\n".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request).await.unwrap();
+
+    let handle = RunHandle::default();
+    let mut events = handle.events.subscribe();
+    job_service::run_mutation_job(&pool, mutation_test.id, &handle).await.unwrap();
+
+    let mut progress = Vec::new();
+    let finished = loop {
+        match events.recv().await.unwrap() {
+            RunEvent::Mutation(event) => progress.push(event),
+            RunEvent::Done(finished) => break finished,
+        }
+    };
+    assert!(!progress.is_empty());
+    assert!(progress.iter().enumerate().all(|(i, event)| event.completed == i + 1));
+    assert_eq!(finished.status, MutationTestStatus::Completed);
+    assert_eq!(finished.total_mutations, progress.len());
+    let last = progress.last().unwrap();
+    assert_eq!((last.killed, last.survived), (finished.killed_mutations, finished.survived_mutations));
+}
+
+struct HangingRunner;
+
+#[async_trait::async_trait]
+impl TestRunner for HangingRunner {
+    async fn validate_test_setup(&self, _source_code: &str) -> Result<(), MutationError> {
+        Ok(())
+    }
+
+    async fn run_tests_for_mutation(&self, _mutated_code: &str) -> RunnerOutcome {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        RunnerOutcome::Survived
+    }
+}
+
+#[tokio::test]
+async fn test_cancelled_run_is_marked_cancelled() {
+    let pool = setup_test_db().await;
+    let request = CreateMutationTestRequest {
+        name: "Cancelled Run".to_string(),
+        description: None,
+        source_code: "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\n#[test]\nfn adds() {\n    assert_eq!(add(2, 3), 5);\n}\n".to_string(),
+        language: Some("rust".to_string()),
+    };
+    let mutation_test = mutation_service::create_mutation_test(&pool, request).await.unwrap();
+
+    let engine = MutationEngine::new(MutationTestConfig::default()).with_test_runner(HangingRunner);
+    let cancel = CancellationToken::new();
+    let (result, _) = tokio::join!(
+        mutation_service::run_mutation_testing_with_engine(&pool, mutation_test.id, &engine, &cancel),
+        async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            cancel.cancel();
+        }
+    );
+
+    assert_eq!(result.unwrap().status, MutationTestStatus::Cancelled);
+    let stored = mutation_service::get_mutation_test(&pool, mutation_test.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, MutationTestStatus::Cancelled);
+    assert!(stored.completed_at.is_some());
+}
+
+const SAMPLE_SOURCE: &str = "pub fn id(x: u8) -> u8 {\n    x\n}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn keeps_value() {\n        assert_eq!(id(3), 3);\n    }\n}\n";
+
+fn run_test_files(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_flux-backend"))
+        .arg("test-files")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_flux_config_toml_in_cwd_is_discovered() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+    std::fs::write(dir.path().join("flux.config.toml"), "timeout_seconds = 42\n").unwrap();
+
+    run_test_files(dir.path(), &["one.rs", "--list-files", "--save-config", "effective.toml"]);
+
+    let saved: MutationTestConfig = toml::from_str(&std::fs::read_to_string(dir.path().join("effective.toml")).unwrap()).unwrap();
+    assert_eq!(saved.timeout_seconds, 42);
+}
+
+#[test]
+fn test_explicit_config_that_cannot_load_fails_the_run() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+    std::fs::write(dir.path().join("broken.toml"), "timeout_seconds = \"soon\"\n").unwrap();
+
+    for config in ["missing.toml", "broken.toml"] {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_flux-backend"))
+            .args(["test-files", "one.rs", "--list-files", "--config", config])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{} was accepted", config);
+        assert!(String::from_utf8_lossy(&output.stderr).contains(config), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+#[test]
+fn test_list_candidates_exports_json_per_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let example = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/test_arithmetic.rs");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_flux-backend"))
+        .args(["list-candidates", example, "--json", "candidates.json"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ArithmeticOperator"));
+
+    let listings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("candidates.json")).unwrap()).unwrap();
+    let file = &listings[0];
+    assert_eq!(file["file"], example);
+    let candidates = file["candidates"].as_array().unwrap();
+    assert!(!candidates.is_empty());
+    assert_eq!(file["total"], candidates.len());
+    assert!(file["by_type"]["ArithmeticOperator"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_dry_run_accepts_a_partial_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let example = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/test_arithmetic.rs");
+    std::fs::write(dir.path().join("partial.toml"), "mutation_types = [\"ArithmeticOperator\"]\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_flux-backend"))
+        .args(["dry-run", example, "--config", "partial.toml", "--format", "rustc"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ArithmeticOperator"));
+}
+
+#[test]
+fn test_fail_under_exits_non_zero_after_writing_reports() {
+    let dir = tempfile::tempdir().unwrap();
+    let weak = "pub fn double(x: u8) -> u8 {\n    x * 2\n}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn doubles() {\n        assert!(double(2) > 0);\n    }\n}\n";
+    std::fs::write(dir.path().join("weak.rs"), weak).unwrap();
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_flux-backend"))
+        .args(["test-files", "weak.rs", "--fail-under", "100", "--json", "out.json"])
+        .current_dir(dir.path())
+        .status()
+        .unwrap();
+
+    assert_eq!(status.code(), Some(4));
+    assert!(dir.path().join("out.json").exists());
+}
+
+#[test]
+fn test_single_file_json_export_is_written() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+
+    run_test_files(dir.path(), &["one.rs", "--json", "out.json"]);
+
+    let content = std::fs::read_to_string(dir.path().join("out.json")).expect("JSON report was not written");
+    let report: MutationReport = serde_json::from_str(&content).unwrap();
+    assert!(report.total_mutations > 0);
+    assert_eq!(report.source_file.as_deref(), Some("one.rs"));
+}
+
+#[test]
+fn test_unreadable_file_is_reported_and_the_run_continues() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("binary.rs"), [0xff, 0xfe, 0x00, 0x9f]).unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+
+    run_test_files(dir.path(), &["binary.rs", "one.rs", "--json", "out.json"]);
+
+    let content = std::fs::read_to_string(dir.path().join("out.json")).expect("JSON report was not written");
+    let reports: std::collections::BTreeMap<String, MutationReport> = serde_json::from_str(&content).unwrap();
+    let binary = &reports["binary.rs"];
+    assert!(binary.file_error.as_deref().unwrap().contains("valid UTF-8"));
+    assert!(binary.results.is_empty());
+    assert!(reports["one.rs"].total_mutations > 0);
+    assert!(reports["one.rs"].file_error.is_none());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_unreadable_file_does_not_stop_the_sqlite_export() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("binary.rs"), [0xff, 0xfe, 0x00, 0x9f]).unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+
+    run_test_files(dir.path(), &["binary.rs", "one.rs", "--sqlite-out", "runs.db"]);
+
+    assert!(dir.path().join("runs.db").exists());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_databaseless_history_and_sqlite_export_are_both_written() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+
+    run_test_files(dir.path(), &["one.rs", "--databaseless", "--sqlite-db", "runs.db"]);
+
+    assert!(dir.path().join(".mutation-history.jsonl").exists());
+    assert!(dir.path().join("runs.db").exists());
+}
+
+#[cfg(not(feature = "sqlite"))]
+#[test]
+fn test_sqlite_export_without_the_feature_fails_before_testing() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_flux-backend"))
+        .args(["test-files", "one.rs", "--sqlite-db", "runs.db", "--json", "out.json"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`sqlite` feature"));
+    assert!(!dir.path().join("out.json").exists());
+}
+
+#[test]
+fn test_concurrent_files_each_get_a_report_in_input_order() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["c.rs", "a.rs", "b.rs"] {
+        std::fs::write(dir.path().join(name), SAMPLE_SOURCE).unwrap();
+    }
+
+    run_test_files(dir.path(), &["c.rs", "a.rs", "b.rs", "--threads", "3", "--html", "report.html"]);
+
+    let html = std::fs::read_to_string(dir.path().join("report.html")).expect("HTML report was not written");
+    assert_eq!(html.matches("<h2>Mutation Results</h2>").count(), 3);
+    assert!(html.contains(r##"<a href="#file-0">c.rs</a>"##));
+    assert!(html.contains(r##"<a href="#file-1">a.rs</a>"##));
+    assert!(html.contains(r##"<a href="#file-2">b.rs</a>"##));
+}
+
+#[test]
+fn test_html_export_indexes_each_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("one.rs"), SAMPLE_SOURCE).unwrap();
+    std::fs::write(dir.path().join("two.rs"), SAMPLE_SOURCE).unwrap();
+
+    run_test_files(dir.path(), &["one.rs", "two.rs", "--html", "report.html"]);
+
+    let html = std::fs::read_to_string(dir.path().join("report.html")).expect("HTML report was not written");
+    assert!(html.contains("<title>Mutation Testing Report</title>"));
+    assert!(html.contains(r##"<a href="#file-0">one.rs</a>"##));
+    assert!(html.contains(r##"<a href="#file-1">two.rs</a>"##));
+    assert_eq!(html.matches("<h2>Mutation Results</h2>").count(), 2);
+}
+
+#[tokio::test]
+async fn test_project_root_lets_a_mutated_file_use_its_sibling_modules() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"siblings\"\nversion = \"0.1.0\"\nedition = \"2021\"\n").unwrap();
+    std::fs::write(src.join("lib.rs"), "pub mod calc;\npub mod math;\n").unwrap();
+    std::fs::write(src.join("math.rs"), "pub fn double(x: i32) -> i32 {\n    x * 2\n}\n").unwrap();
+    let calc = "use crate::math::double;\n\npub fn quad(x: i32) -> i32 {\n    double(x) * 2\n}\n\n#[cfg(test)]\nmod tests {\n    use super::quad;\n\n    #[test]\n    fn quadruples() {\n        assert_eq!(quad(3), 12);\n    }\n}\n";
+    std::fs::write(src.join("calc.rs"), calc).unwrap();
+    let config = MutationTestConfig {
+        mutation_types: vec![MutationType::ArithmeticOperator],
+        project_root: Some(dir.path().to_string_lossy().to_string()),
+        ..Default::default()
+    };
+
+    let report = run_file(&src.join("calc.rs"), config).await.unwrap();
+
+    assert_eq!(report.total_mutations, 2);
+    assert_eq!(report.killed_mutations, 2, "{:?}", report.results);
+    assert!(report.results.iter().all(|result| result.killing_tests.as_deref() == Some(&["calc::tests::quadruples".to_string()][..])));
+    assert_eq!(std::fs::read_to_string(src.join("calc.rs")).unwrap(), calc);
+}
+
+#[tokio::test]
+async fn test_run_file_reports_on_a_single_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    std::fs::write(&path, SAMPLE_SOURCE).unwrap();
+    let config = MutationTestConfig { mutation_types: vec![MutationType::ReturnValue], ..Default::default() };
+
+    let report = run_file(&path, config.clone()).await.unwrap();
+
+    assert_eq!(report.total_mutations, 1);
+    assert_eq!(report.killed_mutations, 1);
+    assert_eq!(report.source_file, Some(path.to_string_lossy().to_string()));
+
+    let excluded = MutationTestConfig { excluded_files: vec!["lib.rs".to_string()], ..config };
+    let error = run_file(&path, excluded).await.unwrap_err();
+    assert!(error.contains("excluded_files"), "{}", error);
+}